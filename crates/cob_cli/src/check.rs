@@ -0,0 +1,34 @@
+use bevy_cobweb_ui::prelude::*;
+
+use crate::CliArgs;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads files and their manifest trees, resolves them, and reports diagnostics.
+///
+/// Returns `false` if any errors were found.
+pub(crate) fn run(args: &CliArgs) -> bool
+{
+    let mut project = CobProject::new(&args.assets);
+    for file in args.files.iter() {
+        project.load(args.asset_path(file));
+    }
+    project.resolve();
+
+    for diagnostic in project.diagnostics() {
+        eprintln!("{diagnostic}\n");
+    }
+
+    let num_errors = project
+        .diagnostics()
+        .iter()
+        .filter(|d| d.is_error())
+        .count();
+    let num_warnings = project.diagnostics().len() - num_errors;
+    let num_files = project.files().count();
+    eprintln!("checked {num_files} file(s): {num_errors} error(s), {num_warnings} warning(s)");
+
+    num_errors == 0
+}
+
+//-------------------------------------------------------------------------------------------------------------------
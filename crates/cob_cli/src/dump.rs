@@ -0,0 +1,131 @@
+use std::io::Write;
+
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::CliArgs;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serializer that writes values on a single line.
+///
+/// All fill (whitespace and comments) is replaced with single spaces.
+struct SingleLineSerializer<'a>
+{
+    inner: DefaultRawSerializer<'a>,
+}
+
+impl<'a> RawSerializer for SingleLineSerializer<'a>
+{
+    fn write_u128(&mut self, val: u128) -> Result<(), std::io::Error>
+    {
+        self.inner.write_u128(val)
+    }
+    fn write_i128(&mut self, val: i128) -> Result<(), std::io::Error>
+    {
+        self.inner.write_i128(val)
+    }
+    fn write_f64(&mut self, val: f64) -> Result<(), std::io::Error>
+    {
+        self.inner.write_f64(val)
+    }
+    fn write_f32(&mut self, val: f32) -> Result<(), std::io::Error>
+    {
+        self.inner.write_f32(val)
+    }
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), std::io::Error>
+    {
+        self.inner.write_bytes(bytes)
+    }
+    fn write_fill(&mut self, fill: &str) -> Result<(), std::io::Error>
+    {
+        if fill.is_empty() {
+            return Ok(());
+        }
+        self.inner.write_bytes(" ".as_bytes())
+    }
+}
+
+impl<'a> Write for SingleLineSerializer<'a>
+{
+    fn write(&mut self, bytes: &[u8]) -> Result<usize, std::io::Error>
+    {
+        self.inner.write(bytes)
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error>
+    {
+        self.inner.flush()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn dump_entry(entry: &CobSceneLayerEntry, depth: usize, output: &mut String)
+{
+    if let CobSceneLayerEntry::Layer(layer) = entry {
+        dump_layer(layer, depth, output);
+        return;
+    }
+
+    let mut bytes = vec![];
+    let mut serializer = SingleLineSerializer { inner: DefaultRawSerializer::new(&mut bytes) };
+    if entry.write_to(&mut serializer).is_err() {
+        return;
+    }
+
+    output.push_str(&"    ".repeat(depth));
+    output.push_str(String::from_utf8_lossy(&bytes).trim());
+    output.push('\n');
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn dump_layer(layer: &CobSceneLayer, depth: usize, output: &mut String)
+{
+    output.push_str(&"    ".repeat(depth));
+    output.push_str(&format!("\"{}\"", layer.name.as_str()));
+    if let Some(instance_ref) = &layer.instance_ref {
+        output.push_str(&format!(" => {}::{}", instance_ref.file.as_str(), instance_ref.scene.as_str()));
+    }
+    output.push('\n');
+    for entry in layer.entries.iter() {
+        dump_entry(entry, depth + 1, output);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Prints the resolved scene trees of files.
+///
+/// Returns `false` if any errors were found while resolving the files.
+pub(crate) fn run(args: &CliArgs) -> bool
+{
+    let mut project = CobProject::new(&args.assets);
+    for file in args.files.iter() {
+        project.load(args.asset_path(file));
+    }
+    project.resolve();
+
+    for diagnostic in project.diagnostics() {
+        eprintln!("{diagnostic}\n");
+    }
+
+    for file in args.files.iter() {
+        let Some(file) = CobFile::try_new(args.asset_path(file)) else { continue };
+        let Some(resolved) = project.get(&file).and_then(|f| f.resolved.as_ref()) else { continue };
+
+        let mut output = format!("// {}\n", file.as_str());
+        for section in resolved.sections.iter() {
+            let CobSection::Scenes(scenes) = section else { continue };
+            for layer in scenes.scenes.iter() {
+                dump_layer(layer, 0, &mut output);
+            }
+        }
+        println!("{output}");
+    }
+
+    !project.has_errors()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
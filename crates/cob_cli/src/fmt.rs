@@ -0,0 +1,75 @@
+use bevy_cobweb_ui::prelude::cob::*;
+
+use crate::CliArgs;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Formats a COB file's content.
+///
+/// Returns an error with diagnostics if the file doesn't parse cleanly.
+fn format_cob(formatter: &CobFormatter, file: &str, content: &str) -> Result<String, Vec<CobDiagnostic>>
+{
+    let span = Span::new_extra(content, CobLocationMetadata { file });
+    let data = match Cob::parse_with_recovery(span) {
+        Ok((data, errors)) if errors.is_empty() => data,
+        Ok((_, errors)) => return Err(errors),
+        Err(err) => return Err(vec![CobDiagnostic::from_span_error(file, err)]),
+    };
+
+    Ok(formatter.format_to_string(&data))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Formats files in-place, or checks if they are formatted.
+///
+/// Returns `false` if any file failed to format, or if checking and any file is not formatted.
+pub(crate) fn run(args: &CliArgs) -> bool
+{
+    let formatter = CobFormatter::new().indent_width(args.indent_width);
+    let mut success = true;
+
+    for file in args.files.iter() {
+        let content = match std::fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("error: failed reading {file}: {err}");
+                success = false;
+                continue;
+            }
+        };
+
+        let formatted = match format_cob(&formatter, file, &content) {
+            Ok(formatted) => formatted,
+            Err(diagnostics) => {
+                for diagnostic in diagnostics {
+                    eprintln!("{diagnostic}\n");
+                }
+                eprintln!("error: skipped formatting {file} because it has errors");
+                success = false;
+                continue;
+            }
+        };
+
+        if formatted == content {
+            continue;
+        }
+
+        if args.check {
+            eprintln!("{file} is not formatted");
+            success = false;
+            continue;
+        }
+
+        if let Err(err) = std::fs::write(file, formatted) {
+            eprintln!("error: failed writing {file}: {err}");
+            success = false;
+            continue;
+        }
+        eprintln!("formatted {file}");
+    }
+
+    success
+}
+
+//-------------------------------------------------------------------------------------------------------------------
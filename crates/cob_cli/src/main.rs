@@ -0,0 +1,133 @@
+//! Command-line tools for COB files.
+//!
+//! - `cob check`: Parses and resolves COB files and their manifest trees, and reports all problems found.
+//! - `cob fmt`: Rewrites COB files in a consistent style.
+//! - `cob dump`: Prints the fully-resolved scene trees in COB files.
+
+mod check;
+mod dump;
+mod fmt;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const USAGE: &str = "\
+Usage: cob <command> [options] <files...>
+
+Commands:
+    check   Parse and resolve files and their manifest trees, reporting all errors
+    fmt     Rewrite files in a consistent style
+    dump    Print the fully-resolved scene trees of files
+
+Options:
+    --assets <dir>   Asset directory that file paths are relative to (check/dump, default: assets)
+    --check          Report files that would be reformatted without changing them (fmt)
+    --indent <n>     Number of spaces per indentation level (fmt, default: 4)
+";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command-line arguments shared by all commands.
+pub(crate) struct CliArgs
+{
+    pub(crate) assets: PathBuf,
+    pub(crate) check: bool,
+    pub(crate) indent_width: usize,
+    pub(crate) files: Vec<String>,
+}
+
+impl CliArgs
+{
+    fn parse(args: &[String]) -> Result<Self, String>
+    {
+        let mut parsed = Self {
+            assets: PathBuf::from("assets"),
+            check: false,
+            indent_width: 4,
+            files: vec![],
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--assets" => {
+                    let Some(dir) = args.next() else {
+                        return Err(String::from("missing directory for --assets"));
+                    };
+                    parsed.assets = PathBuf::from(dir);
+                }
+                "--check" => parsed.check = true,
+                "--indent" => {
+                    let Some(width) = args.next().and_then(|w| w.parse().ok()) else {
+                        return Err(String::from("missing or invalid number for --indent"));
+                    };
+                    parsed.indent_width = width;
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown option {flag:?}")),
+                file => parsed.files.push(String::from(file)),
+            }
+        }
+
+        if parsed.files.is_empty() {
+            return Err(String::from("no files specified"));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Converts a file argument to a path relative to the asset directory.
+    ///
+    /// Paths that start with the asset directory (e.g. `assets/main.cob`) are accepted.
+    pub(crate) fn asset_path(&self, file: &str) -> String
+    {
+        let path = Path::new(file);
+        let path = path.strip_prefix(&self.assets).unwrap_or(path);
+        path.to_string_lossy().replace('\\', "/")
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, args)) = args.split_first() else {
+        eprint!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    if matches!(command.as_str(), "help" | "--help" | "-h") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let args = match CliArgs::parse(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}\n");
+            eprint!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let success = match command.as_str() {
+        "check" => check::run(&args),
+        "fmt" => fmt::run(&args),
+        "dump" => dump::run(&args),
+        _ => {
+            eprintln!("error: unknown command {command:?}\n");
+            eprint!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,12 @@
+//! Build-script helpers for generating Rust code from COB files.
+//!
+//! This crate doesn't depend on Bevy or `bevy_cobweb_ui`, so it can be added to `[build-dependencies]` without
+//! compiling Bevy for the build script. COB files are read directly instead of being parsed and resolved, so only
+//! content written in the files is visible. See [`generate_scene_paths`] for details.
+
+#![allow(clippy::needless_doctest_main)]
+
+mod scene_paths;
+mod scene_reader;
+
+pub use scene_paths::*;
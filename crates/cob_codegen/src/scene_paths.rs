@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::scene_reader::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts a file path or scene node name to a module name.
+fn module_name(name: &str) -> String
+{
+    let mut module: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if module.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        module.insert(0, '_');
+    }
+    if RUST_KEYWORDS.contains(&module.as_str()) {
+        module.push('_');
+    }
+    module
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts a scene path (e.g. `footer::content`) to a constant name (e.g. `FOOTER_CONTENT`).
+fn const_name(path: &str) -> String
+{
+    let mut name = path.replace(SCENE_PATH_SEPARATOR, "_").to_ascii_uppercase();
+    if name.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn write_scene_module(file: &str, scene: &ReadScene, output: &mut String, errors: &mut Vec<ScenePathsError>)
+{
+    let scene_name = scene.name.as_str();
+
+    let _ = writeln!(output, "\n    /// Scene `\"{scene_name}\"`.");
+    let _ = writeln!(output, "    pub mod {}\n    {{", module_name(scene_name));
+    let _ = writeln!(output, "        /// Path of the scene's root node.");
+    let _ = writeln!(output, "        pub const SCENE: &str = {scene_name:?};");
+
+    // Node paths are relative to the root node, for use with `LoadedScene::get` and `LoadedScene::edit`.
+    let mut names = HashSet::from([String::from("SCENE")]);
+    for path in scene.node_paths.iter() {
+        let name = const_name(path);
+        if !names.insert(name.clone()) {
+            errors.push(ScenePathsError::new(
+                file,
+                format!("failed generating scene paths for scene {scene_name:?}; node path {path:?} has the same \
+                    constant name {name} as another node"),
+            ));
+            continue;
+        }
+        let _ = writeln!(output, "        pub const {name}: &str = {path:?};");
+    }
+
+    let _ = writeln!(output, "\n        /// Gets a reference to the scene's root node.");
+    let _ = writeln!(output, "        pub fn scene_ref() -> ::bevy_cobweb_ui::prelude::SceneRef");
+    let _ = writeln!(output, "        {{\n            (super::FILE, SCENE).into()\n        }}");
+    let _ = writeln!(output, "    }}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An error found while generating code from COB files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenePathsError
+{
+    /// The file that caused the error.
+    pub file: String,
+    pub message: String,
+}
+
+impl ScenePathsError
+{
+    fn new(file: impl Into<String>, message: impl Into<String>) -> Self
+    {
+        Self { file: file.into(), message: message.into() }
+    }
+}
+
+impl std::fmt::Display for ScenePathsError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}: {}", self.file, self.message)
+    }
+}
+
+impl std::error::Error for ScenePathsError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Generates Rust source code with typed scene path constants for COB files.
+///
+/// Takes `(file path, file content)` pairs, where file paths are the paths used to load the files in
+/// `bevy_cobweb_ui`. Each file gets a module named after its path (e.g. `ui/main.cob` becomes `ui_main`), which
+/// contains a `FILE` constant and a module for each scene. Scene modules contain a `SCENE` constant, a constant
+/// for each named node path relative to the scene root, and a `scene_ref()` function.
+///
+/// For example, the node `"game_menu"` -> `"footer"` -> `"content"` in `main.cob` can be accessed with
+/// `main::game_menu::FOOTER_CONTENT`, which equals `"footer::content"`.
+///
+/// Only scene nodes written in `#scenes` sections get constants. Nodes added by scene macros, nodes in `#for`
+/// loops, and anonymous nodes (and their children) are skipped. Files are not validated, so use `bevy_cobweb_ui`
+/// or the `cob check` tool to find errors in them.
+///
+/// Returns errors if a file path doesn't end with `.cob` or if generated names collide.
+pub fn generate_scene_paths<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<String, Vec<ScenePathsError>>
+{
+    let mut output = String::from("// Generated by cob_codegen from COB files. Do not edit.\n");
+    let mut errors = vec![];
+    let mut modules = HashSet::new();
+
+    for (file, content) in files {
+        let Some(file_stem) = file.strip_suffix(".cob") else {
+            errors.push(ScenePathsError::new(
+                file,
+                "failed generating scene paths; file does not have '.cob' extension",
+            ));
+            continue;
+        };
+
+        let module = module_name(file_stem);
+        if !modules.insert(module.clone()) {
+            errors.push(ScenePathsError::new(
+                file,
+                format!("failed generating scene paths; module name {module} is used by another file"),
+            ));
+            continue;
+        }
+
+        let _ = writeln!(output, "\n/// Scene paths in `{file}`.");
+        let _ = writeln!(output, "#[allow(dead_code)]\npub mod {module}\n{{");
+        let _ = writeln!(output, "    /// The file's path.");
+        let _ = writeln!(output, "    pub const FILE: &str = {file:?};");
+
+        let mut scene_modules = HashSet::new();
+        for scene in read_scenes(content) {
+            if !scene_modules.insert(module_name(scene.name.as_str())) {
+                errors.push(ScenePathsError::new(
+                    file,
+                    format!("failed generating scene paths; scene {:?} has the same module name as another \
+                        scene", scene.name.as_str()),
+                ));
+                continue;
+            }
+            write_scene_module(file, &scene, &mut output, &mut errors);
+        }
+
+        let _ = writeln!(output, "}}");
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(output)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Build-script helper that generates typed scene paths for `files` and writes them to `out_file`.
+///
+/// File paths should be relative to `asset_dir`. Files are read and passed to [`generate_scene_paths`]. Cargo is
+/// told to rerun the build script when any of the files change.
+///
+/// Example `build.rs`:
+/// ```rust
+/// fn main()
+/// {
+///     let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+///     if let Err(errors) = cob_codegen::write_scene_paths("assets", &["main.cob"], out_dir.join("scenes.rs")) {
+///         for error in errors {
+///             println!("cargo:warning={error}");
+///         }
+///         panic!("failed generating scene paths");
+///     }
+/// }
+/// ```
+///
+/// Then include the generated code in your crate:
+/// ```rust
+/// mod scenes
+/// {
+///     include!(concat!(env!("OUT_DIR"), "/scenes.rs"));
+/// }
+/// ```
+///
+/// Returns errors if a file can't be read or if generating code fails.
+pub fn write_scene_paths(
+    asset_dir: impl Into<PathBuf>,
+    files: &[&str],
+    out_file: impl AsRef<Path>,
+) -> Result<(), Vec<ScenePathsError>>
+{
+    let asset_dir = asset_dir.into();
+    let out_file = out_file.as_ref();
+
+    let mut contents = vec![];
+    let mut errors = vec![];
+    for file in files.iter() {
+        let path = asset_dir.join(file);
+        println!("cargo:rerun-if-changed={}", path.display());
+        match std::fs::read_to_string(&path) {
+            Ok(content) => contents.push((*file, content)),
+            Err(err) => errors.push(ScenePathsError::new(*file, format!("failed reading file: {err}"))),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let code = generate_scene_paths(
+        contents
+            .iter()
+            .map(|(file, content)| (*file, content.as_str())),
+    )?;
+    std::fs::write(out_file, code).map_err(|err| {
+        vec![ScenePathsError::new(
+            out_file.display().to_string(),
+            format!("failed writing scene paths: {err}"),
+        )]
+    })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
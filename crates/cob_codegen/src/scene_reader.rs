@@ -0,0 +1,217 @@
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Separator between scene node names in scene paths.
+pub(crate) const SCENE_PATH_SEPARATOR: &str = "::";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A scene found in a `#scenes` section.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReadScene
+{
+    pub(crate) name: String,
+    /// Paths of named scene nodes relative to the scene root, in the order they appear.
+    pub(crate) node_paths: Vec<String>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token
+{
+    /// A string, without quotes.
+    Str(String),
+    /// A `#` followed by a keyword (e.g. `#scenes` or `#for`). The keyword is empty for conditions (`#[if(..)]`).
+    Hash(String),
+    /// An opening bracket.
+    Open,
+    /// A closing bracket.
+    Close,
+    /// Anything else, e.g. loadable names and values.
+    Other,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Splits COB content into tokens.
+///
+/// Each token is passed to `handler` with its column, the bracket depth before the token, and whether it is the
+/// first token on its line. Comments, whitespace, and filler characters are skipped.
+fn tokenize(content: &str, mut handler: impl FnMut(Token, usize, usize, bool))
+{
+    let mut chars = content.chars().peekable();
+    let mut column = 0;
+    let mut depth = 0usize;
+    let mut line_has_token = false;
+
+    while let Some(c) = chars.next() {
+        let start_column = column;
+        column += 1;
+
+        let token = match c {
+            '\n' => {
+                column = 0;
+                line_has_token = false;
+                continue;
+            }
+            ' ' | ',' | ';' => continue,
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                column += 1;
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    column += 1;
+                    if c == '\n' {
+                        column = 0;
+                        line_has_token = false;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                continue;
+            }
+            '"' => {
+                let mut string = String::new();
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    column += 1;
+                    if c == '\n' {
+                        column = 0;
+                    }
+                    match (escaped, c) {
+                        (false, '"') => break,
+                        (false, '\\') => escaped = true,
+                        _ => escaped = false,
+                    }
+                    string.push(c);
+                }
+                Token::Str(string)
+            }
+            '#' => {
+                let mut keyword = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    column += 1;
+                    keyword.push(c);
+                }
+                Token::Hash(keyword)
+            }
+            '(' | '[' | '{' => Token::Open,
+            ')' | ']' | '}' => Token::Close,
+            _ => {
+                while chars
+                    .next_if(|c| {
+                        !matches!(c, '\n' | ' ' | ',' | ';' | '"' | '(' | '[' | '{' | ')' | ']' | '}' | '/')
+                    })
+                    .is_some()
+                {
+                    column += 1;
+                }
+                Token::Other
+            }
+        };
+
+        let token_depth = depth;
+        match token {
+            Token::Open => depth += 1,
+            Token::Close => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+        (handler)(token, start_column, token_depth, !line_has_token);
+        line_has_token = true;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reads the scenes and named scene nodes written in the `#scenes` sections of a COB file.
+///
+/// Scene nodes are found by their indentation, the same way scene layers are parsed. Anonymous nodes and nodes in
+/// `#for` loops are skipped along with their children, since their names are generated when scenes are extracted.
+/// Content inside brackets (e.g. scene macro invocations) is skipped.
+pub(crate) fn read_scenes(content: &str) -> Vec<ReadScene>
+{
+    let mut scenes: Vec<ReadScene> = vec![];
+    let mut in_scenes = false;
+    // [ (indent, node name) ]
+    let mut layer_stack: Vec<(usize, String)> = vec![];
+    // Nodes indented deeper than this are skipped.
+    let mut skip_indent: Option<usize> = None;
+
+    tokenize(content, |token, column, depth, first_on_line| {
+        if depth > 0 || !first_on_line {
+            return;
+        }
+
+        // Section headers.
+        if let Token::Hash(keyword) = &token {
+            if column == 0 && !keyword.is_empty() && keyword != "for" {
+                in_scenes = keyword == "scenes";
+                layer_stack.clear();
+                skip_indent = None;
+                return;
+            }
+        }
+        if !in_scenes {
+            return;
+        }
+
+        // Skip content of anonymous nodes and loops.
+        if let Some(indent) = skip_indent {
+            if column > indent {
+                return;
+            }
+            skip_indent = None;
+        }
+
+        match token {
+            Token::Str(name) => {
+                while layer_stack
+                    .last()
+                    .is_some_and(|(indent, _)| *indent >= column)
+                {
+                    layer_stack.pop();
+                }
+                if name.is_empty() {
+                    skip_indent = Some(column);
+                    return;
+                }
+
+                // Scene roots.
+                if column == 0 {
+                    scenes.push(ReadScene { name: name.clone(), node_paths: vec![] });
+                    layer_stack.push((column, name));
+                    return;
+                }
+
+                // Scene nodes.
+                if layer_stack.is_empty() {
+                    return;
+                }
+                let Some(scene) = scenes.last_mut() else { return };
+                layer_stack.push((column, name));
+                let path = layer_stack[1..]
+                    .iter()
+                    .map(|(_, name)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(SCENE_PATH_SEPARATOR);
+                if !scene.node_paths.contains(&path) {
+                    scene.node_paths.push(path);
+                }
+            }
+            Token::Hash(keyword) if keyword == "for" => {
+                skip_indent = Some(column);
+            }
+            _ => (),
+        }
+    });
+
+    scenes
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,107 @@
+//! Language server for COB files.
+//!
+//! Runs over stdio. Supports:
+//! - Live diagnostics for open files and their manifest trees.
+//! - Completion of loadable names and fields, using a schema file exported by the app.
+//! - Go-to-definition for constants, macros, and manifest keys.
+//! - Hover for resolved constant values and loadable schemas.
+//!
+//! The asset directory and schema file can be set with the `--assets <dir>` and `--schema <file>` arguments, or
+//! with the `assets` and `schema` initialization options. Relative paths are resolved against the workspace root.
+//! By default the asset directory is `assets` and the schema file is `cob_schema.json`.
+//!
+//! Apps can export their schema file with `LoadableSchemaPlugin` by running with `--dump-schema cob_schema.json`.
+
+mod schema;
+mod server;
+mod symbols;
+
+use std::path::PathBuf;
+
+use bevy_cobweb_ui::prelude::LoadableSchemas;
+use lsp_server::Connection;
+use lsp_types::{
+    CompletionOptions, HoverProviderCapability, InitializeParams, OneOf, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+
+use crate::schema::read_schemas;
+use crate::server::Server;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn capabilities() -> ServerCapabilities
+{
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![String::from("{")]),
+            ..Default::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets a path from the command-line arguments, then the initialization options, then the default.
+fn get_path(args: &[String], key: &str, params: &InitializeParams, default: &str) -> PathBuf
+{
+    let flag = format!("--{key}");
+    let from_args = args
+        .iter()
+        .position(|a| *a == flag)
+        .and_then(|idx| args.get(idx + 1))
+        .cloned();
+    let from_options = params
+        .initialization_options
+        .as_ref()
+        .and_then(|o| o.get(key))
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let path = PathBuf::from(
+        from_args
+            .or(from_options)
+            .unwrap_or_else(|| String::from(default)),
+    );
+
+    #[allow(deprecated)]
+    let root = params
+        .root_uri
+        .as_ref()
+        .and_then(|uri| uri.to_file_path().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let path = root.join(path);
+    path.canonicalize().unwrap_or(path)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn main() -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (connection, io_threads) = Connection::stdio();
+    let params = connection.initialize(serde_json::to_value(capabilities())?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    let asset_dir = get_path(&args, "assets", &params, "assets");
+    let schema_path = get_path(&args, "schema", &params, "cob_schema.json");
+    let schemas = if schema_path.exists() {
+        read_schemas(&schema_path).unwrap_or_else(|err| {
+            eprintln!("cob-lsp: {err}");
+            LoadableSchemas::default()
+        })
+    } else {
+        LoadableSchemas::default()
+    };
+
+    Server::new(asset_dir, schemas).run(&connection)?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
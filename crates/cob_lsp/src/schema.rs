@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use bevy_cobweb_ui::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reads loadable schemas from a JSON file exported by the app with [`LoadableSchemaPlugin`].
+pub(crate) fn read_schemas(path: &Path) -> Result<LoadableSchemas, String>
+{
+    let content =
+        std::fs::read_to_string(path).map_err(|err| format!("failed reading {}: {err}", path.display()))?;
+    LoadableSchemas::from_json(&content).map_err(|err| format!("{err} ({})", path.display()))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Summarizes a loadable in markdown for hover text.
+pub(crate) fn describe(schema: &LoadableSchema) -> String
+{
+    let mut description = format!("**{}**", schema.name);
+    if !schema.kinds.is_empty() {
+        let kinds: Vec<_> = schema.kinds.iter().map(|k| k.as_str()).collect();
+        description.push_str(&format!(" ({})", kinds.join(", ")));
+    }
+    if !schema.type_path.is_empty() {
+        description.push_str(&format!("\n\n`{}`", schema.type_path));
+    }
+    for field in schema.fields.iter() {
+        description.push_str(&format!("\n- `{}`: `{}`", field.name, field.type_path));
+        if let Some(default) = &field.default {
+            description.push_str(&format!(" = `{default}`"));
+        }
+    }
+    for variant in schema.variants.iter() {
+        description.push_str(&format!("\n- `{}`", variant.name));
+    }
+    description
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, Location, MarkupContent, MarkupKind, Position,
+    PublishDiagnosticsParams, Range, Url,
+};
+
+use crate::schema::describe;
+use crate::symbols::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits how many imports are followed when searching for a definition.
+const MAX_IMPORT_DEPTH: usize = 32;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serializer for hover text. Fill sequences are normalized with [`CobFill::normalize_whitespace`].
+struct HoverSerializer<'a>
+{
+    inner: DefaultRawSerializer<'a>,
+}
+
+impl<'a> RawSerializer for HoverSerializer<'a>
+{
+    fn write_u128(&mut self, val: u128) -> Result<(), std::io::Error>
+    {
+        self.inner.write_u128(val)
+    }
+    fn write_i128(&mut self, val: i128) -> Result<(), std::io::Error>
+    {
+        self.inner.write_i128(val)
+    }
+    fn write_f64(&mut self, val: f64) -> Result<(), std::io::Error>
+    {
+        self.inner.write_f64(val)
+    }
+    fn write_f32(&mut self, val: f32) -> Result<(), std::io::Error>
+    {
+        self.inner.write_f32(val)
+    }
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), std::io::Error>
+    {
+        self.inner.write_bytes(bytes)
+    }
+    fn write_fill(&mut self, fill: &str) -> Result<(), std::io::Error>
+    {
+        self.inner
+            .write_bytes(CobFill::normalize_whitespace(fill).as_bytes())
+    }
+}
+
+impl<'a> std::io::Write for HoverSerializer<'a>
+{
+    fn write(&mut self, bytes: &[u8]) -> Result<usize, std::io::Error>
+    {
+        self.inner.write(bytes)
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error>
+    {
+        self.inner.flush()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn to_lsp_diagnostic(diagnostic: &CobDiagnostic) -> Diagnostic
+{
+    let range = match &diagnostic.location {
+        Some(location) => {
+            let line = location.line.saturating_sub(1);
+            let start = utf16_column(&location.source_line, location.column);
+            let end = utf16_column(&location.source_line, location.column + location.len);
+            Range::new(Position::new(line, start), Position::new(line, end))
+        }
+        None => Range::default(),
+    };
+    let severity = match diagnostic.severity {
+        CobDiagnosticSeverity::Error => DiagnosticSeverity::ERROR,
+        CobDiagnosticSeverity::Warning => DiagnosticSeverity::WARNING,
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some(String::from("cob")),
+        message: diagnostic.message.clone(),
+        ..Default::default()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Language server state.
+pub(crate) struct Server
+{
+    asset_dir: PathBuf,
+    schemas: LoadableSchemas,
+    /// Text of open documents.
+    documents: HashMap<Url, String>,
+    /// Project containing all open documents and their manifest trees. Rebuilt when documents change.
+    project: CobProject,
+    /// Documents with published diagnostics.
+    published: HashSet<Url>,
+}
+
+impl Server
+{
+    pub(crate) fn new(asset_dir: PathBuf, schemas: LoadableSchemas) -> Self
+    {
+        Self {
+            project: CobProject::new(&asset_dir),
+            asset_dir,
+            schemas,
+            documents: HashMap::default(),
+            published: HashSet::default(),
+        }
+    }
+
+    /// Handles messages until the client shuts down the server.
+    pub(crate) fn run(&mut self, connection: &Connection) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+    {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    if self.handle_notification(notification) {
+                        for notification in self.refresh_diagnostics() {
+                            connection
+                                .sender
+                                .send(Message::Notification(notification))?;
+                        }
+                    }
+                }
+                Message::Response(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response
+    {
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let result = match method.as_str() {
+            Completion::METHOD => {
+                Self::extract(request, |p: CompletionParams| serde_json::to_value(self.completion(p)))
+            }
+            GotoDefinition::METHOD => Self::extract(request, |p: GotoDefinitionParams| {
+                serde_json::to_value(self.goto_definition(p))
+            }),
+            HoverRequest::METHOD => Self::extract(request, |p: HoverParams| serde_json::to_value(self.hover(p))),
+            _ => {
+                return Response::new_err(
+                    id,
+                    lsp_server::ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request {method:?}"),
+                );
+            }
+        };
+
+        match result {
+            Ok(value) => Response::new_ok(id, value),
+            Err(err) => Response::new_err(id, lsp_server::ErrorCode::InvalidParams as i32, err),
+        }
+    }
+
+    fn extract<P: serde::de::DeserializeOwned>(
+        request: Request,
+        callback: impl FnOnce(P) -> Result<serde_json::Value, serde_json::Error>,
+    ) -> Result<serde_json::Value, String>
+    {
+        let params: P = serde_json::from_value(request.params).map_err(|err| err.to_string())?;
+        (callback)(params).map_err(|err| err.to_string())
+    }
+
+    /// Returns `true` if documents changed.
+    fn handle_notification(&mut self, notification: Notification) -> bool
+    {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidOpenTextDocumentParams>(notification.params) else {
+                    return false;
+                };
+                self.documents
+                    .insert(params.text_document.uri, params.text_document.text);
+                true
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidChangeTextDocumentParams>(notification.params) else {
+                    return false;
+                };
+                // Text is synced in full, so the last change contains the entire document.
+                let Some(change) = params.content_changes.into_iter().last() else { return false };
+                self.documents.insert(params.text_document.uri, change.text);
+                true
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(params) = serde_json::from_value::<DidCloseTextDocumentParams>(notification.params) else {
+                    return false;
+                };
+                self.documents.remove(&params.text_document.uri);
+                true
+            }
+            // Saving may change files that open documents depend on.
+            DidSaveTextDocument::METHOD => true,
+            _ => false,
+        }
+    }
+
+    /// Gets the COB file name of a document relative to the asset directory.
+    fn cob_file(&self, uri: &Url) -> Option<CobFile>
+    {
+        let path = uri.to_file_path().ok()?;
+        let path = path.strip_prefix(&self.asset_dir).ok()?;
+        CobFile::try_new(&path.to_string_lossy().replace('\\', "/"))
+    }
+
+    fn file_uri(&self, file: &CobFile) -> Option<Url>
+    {
+        Url::from_file_path(self.asset_dir.join(file.as_str())).ok()
+    }
+
+    /// Gets the text of a file, preferring the open document if there is one.
+    fn file_text(&self, file: &CobFile) -> Option<String>
+    {
+        if let Some(text) = self.file_uri(file).and_then(|uri| self.documents.get(&uri)) {
+            return Some(text.clone());
+        }
+        std::fs::read_to_string(self.asset_dir.join(file.as_str())).ok()
+    }
+
+    /// Rebuilds the project from open documents, then makes notifications to publish its diagnostics.
+    fn refresh_diagnostics(&mut self) -> Vec<Notification>
+    {
+        let mut project = CobProject::new(&self.asset_dir);
+        let open_files: Vec<CobFile> = self
+            .documents
+            .keys()
+            .filter_map(|uri| self.cob_file(uri))
+            .collect();
+        for file in open_files.iter() {
+            let uri = self.file_uri(file).unwrap();
+            project.override_file(file.clone(), self.documents.get(&uri).unwrap().clone());
+        }
+        for file in open_files.iter() {
+            project.load(file.as_str());
+        }
+        project.resolve();
+        self.project = project;
+
+        // Group diagnostics by file.
+        let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::default();
+        for uri in self.published.drain() {
+            diagnostics.insert(uri, vec![]);
+        }
+        for diagnostic in self.project.diagnostics() {
+            let Some(uri) = CobFile::try_new(&diagnostic.file).and_then(|f| self.file_uri(&f)) else { continue };
+            diagnostics
+                .entry(uri)
+                .or_default()
+                .push(to_lsp_diagnostic(diagnostic));
+        }
+
+        diagnostics
+            .into_iter()
+            .map(|(uri, diagnostics)| {
+                if !diagnostics.is_empty() {
+                    self.published.insert(uri.clone());
+                }
+                Notification::new(
+                    String::from(PublishDiagnostics::METHOD),
+                    PublishDiagnosticsParams { uri, diagnostics, version: None },
+                )
+            })
+            .collect()
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse>
+    {
+        let position = params.text_document_position;
+        let text = self.documents.get(&position.text_document.uri)?;
+
+        let items: Vec<CompletionItem> =
+            match completion_context(text, position.position.line, position.position.character)? {
+                CompletionContext::Loadable => self
+                    .schemas
+                    .loadables
+                    .iter()
+                    .map(|schema| CompletionItem {
+                        label: schema.name.clone(),
+                        kind: Some(CompletionItemKind::STRUCT),
+                        detail: Some(
+                            schema
+                                .kinds
+                                .iter()
+                                .map(|k| k.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                        ..Default::default()
+                    })
+                    .collect(),
+                CompletionContext::Field { loadable } => self
+                    .schemas
+                    .get(&loadable)?
+                    .fields
+                    .iter()
+                    .map(|field| CompletionItem {
+                        label: field.name.clone(),
+                        kind: Some(CompletionItemKind::FIELD),
+                        detail: Some(field.type_path.clone()),
+                        ..Default::default()
+                    })
+                    .collect(),
+            };
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn goto_definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse>
+    {
+        let position = params.text_document_position_params;
+        let uri = &position.text_document.uri;
+        let text = self.documents.get(uri)?;
+        let symbol = symbol_at(text, position.position.line, position.position.character)?;
+        let file = self.cob_file(uri)?;
+
+        let (file, line, column) = match symbol.kind {
+            SymbolKind::ManifestKey => {
+                let file = self
+                    .project
+                    .get_manifest_file(&ManifestKey::new(&symbol.path))?;
+                (file.clone(), 0, 0)
+            }
+            SymbolKind::Loadable => return None,
+            kind => self.find_definition(&file, kind, &symbol.path, 0)?,
+        };
+
+        let text = self.file_text(&file)?;
+        let line_text = text.lines().nth(line as usize).unwrap_or_default();
+        let character = utf16_column(line_text, line_text[..column].chars().count() + 1);
+        let position = Position::new(line, character);
+
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            self.file_uri(&file)?,
+            Range::new(position, position),
+        )))
+    }
+
+    /// Finds where a definition is located, following imports the same way definitions are resolved.
+    ///
+    /// Returns the file, line, and byte column of the definition.
+    fn find_definition(
+        &self,
+        file: &CobFile,
+        kind: SymbolKind,
+        path: &str,
+        depth: usize,
+    ) -> Option<(CobFile, u32, usize)>
+    {
+        if depth > MAX_IMPORT_DEPTH {
+            return None;
+        }
+
+        // Definitions in the file shadow imported definitions.
+        if !path.contains("::") {
+            if let Some((line, column)) = self
+                .file_text(file)
+                .and_then(|text| find_definition(&text, kind, path))
+            {
+                return Some((file.clone(), line, column));
+            }
+        }
+
+        let project_file = self.project.get(file)?;
+        project_file.imports.iter().find_map(|(key, import)| {
+            let path = match &import.alias {
+                CobImportAlias::None => path,
+                CobImportAlias::Alias(alias) => path.strip_prefix(alias.as_str())?.strip_prefix("::")?,
+            };
+            if !import.selects(path) {
+                return None;
+            }
+            let import = self.project.get_manifest_file(key)?;
+            self.find_definition(import, kind, path, depth + 1)
+        })
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover>
+    {
+        let position = params.text_document_position_params;
+        let uri = &position.text_document.uri;
+        let text = self.documents.get(uri)?;
+        let symbol = symbol_at(text, position.position.line, position.position.character)?;
+
+        let value = match symbol.kind {
+            SymbolKind::Constant => {
+                let file = self.cob_file(uri)?;
+                let resolver = self.project.resolver(&file)?;
+                let value = resolver.loadables.constants.get(&symbol.path)?;
+
+                let mut bytes = vec![];
+                value
+                    .write_to(&mut HoverSerializer { inner: DefaultRawSerializer::new(&mut bytes) })
+                    .ok()?;
+                let value = String::from_utf8_lossy(&bytes);
+                format!("```cob\n${} = {}\n```", symbol.path, value.trim())
+            }
+            SymbolKind::Loadable => describe(self.schemas.get(&symbol.path)?),
+            _ => return None,
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+            range: None,
+        })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,286 @@
+//! Text utilities for finding symbols in COB source.
+//!
+//! These work on raw text so they can be used on files that don't parse cleanly.
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Section keywords that start at the beginning of a line.
+const SECTION_KEYWORDS: [&str; 8] =
+    ["#manifest", "#import", "#export", "#assets", "#defs", "#commands", "#scenes", "#patches"];
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_symbol_char(c: char) -> bool
+{
+    c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '.'
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The kind of a symbol referenced in COB source.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SymbolKind
+{
+    /// `$name`
+    Constant,
+    /// `*name`
+    DataMacro,
+    /// `@name`
+    LoadableMacro,
+    /// `+name`
+    SceneMacro,
+    /// A key in a `#manifest` or `#import` entry.
+    ManifestKey,
+    /// A loadable name (e.g. `FlexNode`).
+    Loadable,
+}
+
+impl SymbolKind
+{
+    /// The character that prefixes definitions of this kind, if it can be defined in `#defs`.
+    pub(crate) fn def_prefix(&self) -> Option<char>
+    {
+        match self {
+            Self::Constant => Some('$'),
+            Self::DataMacro => Some('*'),
+            Self::LoadableMacro => Some('@'),
+            Self::SceneMacro => Some('+'),
+            Self::ManifestKey | Self::Loadable => None,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A symbol referenced in COB source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol
+{
+    pub(crate) kind: SymbolKind,
+    /// The symbol's path without its prefix character (e.g. `alias::name` for `$alias::name`).
+    pub(crate) path: String,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts an LSP character offset (UTF-16 code units) to a byte offset in `line`.
+pub(crate) fn byte_offset(line: &str, character: u32) -> usize
+{
+    let mut utf16 = 0;
+    for (idx, c) in line.char_indices() {
+        if utf16 >= character as usize {
+            return idx;
+        }
+        utf16 += c.len_utf16();
+    }
+    line.len()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Converts a column in UTF-8 characters (starting from 1) to an LSP character offset (UTF-16 code units).
+pub(crate) fn utf16_column(line: &str, column: usize) -> u32
+{
+    line.chars()
+        .take(column.saturating_sub(1))
+        .map(|c| c.len_utf16() as u32)
+        .sum()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the section keyword that line `line` is in, if any.
+pub(crate) fn section_at(text: &str, line: usize) -> Option<&'static str>
+{
+    text.lines()
+        .take(line + 1)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find_map(|l| SECTION_KEYWORDS.iter().find(|k| l.starts_with(*k)).copied())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the symbol at a position in the text.
+pub(crate) fn symbol_at(text: &str, line: u32, character: u32) -> Option<Symbol>
+{
+    let line_idx = line as usize;
+    let line = text.lines().nth(line_idx)?;
+    let offset = byte_offset(line, character);
+
+    let start = line[..offset]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_symbol_char(*c))
+        .map(|(idx, c)| idx + c.len_utf8())
+        .unwrap_or(0);
+    let end = line[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_symbol_char(*c))
+        .map(|(idx, _)| offset + idx)
+        .unwrap_or(line.len());
+    let path = line[start..end].trim_matches(|c| c == ':' || c == '.');
+    if path.is_empty() {
+        return None;
+    }
+
+    let kind = match line[..start].chars().last() {
+        Some('$') => SymbolKind::Constant,
+        Some('*') => SymbolKind::DataMacro,
+        Some('@') => SymbolKind::LoadableMacro,
+        Some('+') => SymbolKind::SceneMacro,
+        _ => match section_at(text, line_idx) {
+            // Import entries: `key as alias`
+            Some("#import") if start == 0 => SymbolKind::ManifestKey,
+            // Manifest entries: `"file.cob" as key`
+            Some("#manifest") if line[..start].trim_end().ends_with(" as") => SymbolKind::ManifestKey,
+            _ if path.starts_with(|c: char| c.is_ascii_uppercase()) => SymbolKind::Loadable,
+            _ => return None,
+        },
+    };
+
+    Some(Symbol { kind, path: String::from(path) })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the position (line, UTF-8 byte column) where a symbol is defined in a file's `#defs` sections.
+///
+/// If there are multiple definitions, the last one is returned since it overrides the others.
+pub(crate) fn find_definition(text: &str, kind: SymbolKind, name: &str) -> Option<(u32, usize)>
+{
+    let prefix = kind.def_prefix()?;
+    let mut in_defs = false;
+    let mut found = None;
+
+    for (idx, line) in text.lines().enumerate() {
+        if let Some(keyword) = SECTION_KEYWORDS.iter().find(|k| line.starts_with(*k)) {
+            in_defs = *keyword == "#defs";
+            continue;
+        }
+        if !in_defs {
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let Some(def) = trimmed.strip_prefix(prefix) else { continue };
+        let Some(after) = def.strip_prefix(name) else { continue };
+        if after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        found = Some((idx as u32, line.len() - trimmed.len()));
+    }
+
+    found
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Completion context at a position in the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CompletionContext
+{
+    /// Completing a loadable name at the start of an item.
+    Loadable,
+    /// Completing a field name inside a loadable's `{ ... }`.
+    Field
+    {
+        loadable: String
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the completion context at a position in the text.
+pub(crate) fn completion_context(text: &str, line: u32, character: u32) -> Option<CompletionContext>
+{
+    let line_idx = line as usize;
+    let current = text.lines().nth(line_idx).unwrap_or_default();
+    let offset = byte_offset(current, character);
+
+    // Collect the text before the cursor.
+    let mut before = String::default();
+    for line in text.lines().take(line_idx) {
+        before.push_str(line);
+        before.push('\n');
+    }
+    before.push_str(&current[..offset]);
+
+    // Strip the partial word being completed.
+    let word_start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_'))
+        .map(|(idx, c)| idx + c.len_utf8())
+        .unwrap_or(0);
+    let before = &before[..word_start];
+
+    // Find the innermost unclosed bracket.
+    let mut depth = 0usize;
+    let mut opener = None;
+    for (idx, c) in before.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' | '[' | '{' => {
+                opener = Some((idx, c));
+                break;
+            }
+            _ => (),
+        }
+    }
+
+    let Some((idx, opener)) = opener else {
+        // Loadables start on their own lines.
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        if !before[line_start..].trim().is_empty() {
+            return None;
+        }
+        return match section_at(text, line_idx) {
+            Some("#commands") | Some("#scenes") | Some("#patches") | Some("#defs") => {
+                Some(CompletionContext::Loadable)
+            }
+            _ => None,
+        };
+    };
+
+    // Fields can only be completed in key position.
+    if opener != '{' || before.trim_end().ends_with(':') {
+        return None;
+    }
+
+    // Get the loadable name before the brace, skipping generics.
+    let mut name_end = before[..idx].trim_end();
+    if name_end.ends_with('>') {
+        let mut depth = 0usize;
+        let generics_start = name_end.char_indices().rev().find_map(|(i, c)| {
+            match c {
+                '>' => depth += 1,
+                '<' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => (),
+            }
+            None
+        })?;
+        name_end = &name_end[..generics_start];
+    }
+    let name_start = name_end
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_'))
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    let loadable = &name_end[name_start..];
+    if !loadable.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    Some(CompletionContext::Field { loadable: String::from(loadable) })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
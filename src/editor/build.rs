@@ -224,16 +224,23 @@ fn build_file_view(In((base_entity, file)): In<(Entity, CobFile)>, mut c: Comman
                         let CobSection::Commands(commands) = s else { return None };
                         Some(commands)
                     }) {
-                        for command in commands_section.entries.iter() {
-                            build_loadable(
-                                l,
-                                &registry,
-                                &loadables,
-                                &widgets,
-                                file_data.last_save_hash,
-                                commands_ref.clone(),
-                                &command.0,
-                            );
+                        for entry in commands_section.entries.iter() {
+                            match entry {
+                                CobCommandEntry::Loadable(command) => {
+                                    build_loadable(
+                                        l,
+                                        &registry,
+                                        &loadables,
+                                        &widgets,
+                                        file_data.last_save_hash,
+                                        commands_ref.clone(),
+                                        command,
+                                    );
+                                }
+                                CobCommandEntry::Condition(_) => {
+                                    l.load_scene(("editor.frame", "unsupported"));
+                                }
+                            }
                         }
                     }
                 });
@@ -20,7 +20,7 @@ pub(crate) struct CobHashRegistry
 impl CobHashRegistry
 {
     /// Returns `true` if the file's hash changed.
-    pub(crate) fn try_refresh_file(&self, file: &String, hash: CobFileHash) -> bool
+    pub(crate) fn try_refresh_file(&self, file: &str, hash: CobFileHash) -> bool
     {
         let Ok(mut registry) = self.registry.lock() else {
             warn_once!("CobHashRegistry's internal mutex is poisoned, which may reduce COB file loading perf \
@@ -28,7 +28,7 @@ impl CobHashRegistry
             return true;
         };
 
-        let Some(prev) = registry.insert(file.clone(), hash) else { return true };
+        let Some(prev) = registry.insert(String::from(file), hash) else { return true };
         prev != hash
     }
 
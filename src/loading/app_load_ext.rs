@@ -1,7 +1,9 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use bevy::asset::AssetApp;
 use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
 
 use crate::prelude::*;
 
@@ -34,23 +36,64 @@ pub(crate) struct LoadedCobAssetFiles
 {
     preset_files: Vec<CobFile>,
     handles: HashMap<AssetId<CobAssetFile>, Handle<CobAssetFile>>,
+
+    /// Contents of files loaded from strings instead of the asset server.
+    sources: HashMap<CobFile, String>,
+    /// Files loaded from strings that need to be parsed.
+    pending_sources: Vec<CobFile>,
 }
 
 impl LoadedCobAssetFiles
 {
-    fn add_preset_file(&mut self, file: &str)
+    fn add_preset_file(&mut self, file: &str) -> Option<CobFile>
     {
         match CobFile::try_new(file) {
             Some(file) => {
                 tracing::info!("registered COB file {}", file.as_str());
-                self.preset_files.push(file);
+                self.preset_files.push(file.clone());
+                Some(file)
             }
             None => {
-                tracing::warn!("failed registering COB file {}; does not have '.cob' extension", file)
+                tracing::warn!("failed registering COB file {}; does not have '.cob' extension", file);
+                None
             }
         }
     }
 
+    /// Sets the contents of a file loaded from a string.
+    ///
+    /// Returns `false` if the file already has the same contents.
+    fn set_source(&mut self, file: CobFile, text: String) -> bool
+    {
+        if self.sources.get(&file) == Some(&text) {
+            return false;
+        }
+        self.sources.insert(file, text);
+        true
+    }
+
+    /// Queues a file loaded from a string to be parsed.
+    fn queue_source(&mut self, file: CobFile)
+    {
+        if self.pending_sources.contains(&file) {
+            return;
+        }
+        self.pending_sources.push(file);
+    }
+
+    /// Takes files loaded from strings that need to be parsed, along with their contents.
+    pub(crate) fn take_pending_sources(&mut self) -> Vec<(CobFile, String)>
+    {
+        let pending = std::mem::take(&mut self.pending_sources);
+        pending
+            .into_iter()
+            .filter_map(|file| {
+                let text = self.sources.get(&file)?.clone();
+                Some((file, text))
+            })
+            .collect()
+    }
+
     fn take_preset_files(&mut self) -> Vec<CobFile>
     {
         std::mem::take(&mut self.preset_files)
@@ -63,8 +106,13 @@ impl LoadedCobAssetFiles
         asset_server: &AssetServer,
     )
     {
-        let handle = asset_server.load(String::from(file.as_str()));
-        self.handles.insert(handle.id(), handle);
+        // Files loaded from strings are parsed directly instead of going through the asset server.
+        if self.sources.contains_key(&file) {
+            self.queue_source(file.clone());
+        } else {
+            let handle = asset_server.load(String::from(file.as_str()));
+            self.handles.insert(handle.id(), handle);
+        }
         cob_cache.prepare_file(file);
     }
 
@@ -81,6 +129,19 @@ impl LoadedCobAssetFiles
     {
         self.handles.remove(&id)
     }
+
+    /// Drops the handle of an unloaded file so the asset can be freed.
+    ///
+    /// Contents of files loaded from strings are dropped too, so the file must be loaded from a string again
+    /// before it can be reloaded.
+    pub(crate) fn release_handle(&mut self, file: &CobFile)
+    {
+        let path = Path::new(file.as_str());
+        self.handles
+            .retain(|_, handle| handle.path().is_none_or(|p| p.path() != path));
+        self.sources.remove(file);
+        self.pending_sources.retain(|pending| pending != file);
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -90,6 +151,12 @@ pub trait LoadedCobAssetFilesAppExt
 {
     /// Registers a cobweb asset file to be loaded.
     fn load(&mut self, file: impl AsRef<str>) -> &mut Self;
+
+    /// Registers a cobweb asset file to be loaded from a string instead of the asset server.
+    ///
+    /// The file name can be anything ending in `.cob` (e.g. `virtual://mods/hud.cob`). It is used in place of a
+    /// file path, so the file can be referenced in manifests like other files.
+    fn load_cob_from_str(&mut self, file: impl AsRef<str>, text: impl Into<String>) -> &mut Self;
 }
 
 impl LoadedCobAssetFilesAppExt for App
@@ -105,6 +172,114 @@ impl LoadedCobAssetFilesAppExt for App
             .add_preset_file(file.as_ref());
         self
     }
+
+    fn load_cob_from_str(&mut self, file: impl AsRef<str>, text: impl Into<String>) -> &mut Self
+    {
+        if !self.world().contains_resource::<LoadedCobAssetFiles>() {
+            self.init_resource::<LoadedCobAssetFiles>();
+        }
+
+        let mut files = self.world_mut().resource_mut::<LoadedCobAssetFiles>();
+        if let Some(file) = files.add_preset_file(file.as_ref()) {
+            files.set_source(file, text.into());
+        }
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn load_cob_from_str(
+    In((file, text)): In<(String, String)>,
+    asset_server: Res<AssetServer>,
+    mut cob_files: ResMut<LoadedCobAssetFiles>,
+    mut cob_cache: ResMut<CobAssetCache>,
+    commands_buffer: Option<ResMut<CommandsBuffer>>,
+)
+{
+    let Some(file) = CobFile::try_new(&file) else {
+        tracing::warn!("failed loading COB file {} from string; does not have '.cob' extension", file);
+        return;
+    };
+
+    if !cob_files.set_source(file.clone(), text) {
+        tracing::debug!("ignoring COB file {:?} loaded from string; contents did not change", file.as_str());
+        return;
+    }
+
+    // Replace the contents of a known file.
+    // - Files that are still loading will be parsed with the new contents.
+    if cob_cache.is_file_registered(&file) {
+        if cob_cache.is_file_pending(&file) {
+            return;
+        }
+
+        #[cfg(feature = "hot_reload")]
+        {
+            tracing::info!("reloading COB file {:?} from string", file.as_str());
+            cob_files.queue_source(file);
+        }
+
+        #[cfg(not(feature = "hot_reload"))]
+        {
+            tracing::warn!("failed replacing COB file {:?} from string; replacing loaded files requires the \
+                \"hot_reload\" feature", file.as_str());
+        }
+
+        return;
+    }
+
+    // Load a new file.
+    let Some(mut commands_buffer) = commands_buffer else {
+        tracing::warn!("failed loading COB file {:?} from string; new files can only be loaded in \
+            LoadState::Loading unless the \"hot_reload\" feature is enabled", file.as_str());
+        return;
+    };
+    tracing::info!("loading COB file {:?} from string", file.as_str());
+    commands_buffer.add_root_file(file.clone());
+    cob_files.start_loading(file, &mut cob_cache, &asset_server);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for loading a COB file from a string, or replacing the contents of a COB file that was loaded from a
+/// string.
+///
+/// New files are loaded after all other files, and their commands are applied after the commands of other files.
+/// Replacing a file that already loaded will refresh it like a hot-reloaded file, which requires the
+/// `hot_reload` feature. New files can only be added in [`LoadState::Loading`] unless the `hot_reload` feature
+/// is enabled or there are lazy files.
+#[derive(Debug, Clone)]
+pub struct LoadCobFromStr
+{
+    pub file: String,
+    pub text: String,
+}
+
+impl Command for LoadCobFromStr
+{
+    fn apply(self, world: &mut World)
+    {
+        world.syscall((self.file, self.text), load_cob_from_str);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `Commands` with methods supporting cob file loading.
+pub trait LoadedCobAssetFilesCommandsExt
+{
+    /// Queues a [`LoadCobFromStr`] command.
+    fn load_cob_from_str(&mut self, file: impl Into<String>, text: impl Into<String>) -> &mut Self;
+}
+
+impl LoadedCobAssetFilesCommandsExt for Commands<'_, '_>
+{
+    fn load_cob_from_str(&mut self, file: impl Into<String>, text: impl Into<String>) -> &mut Self
+    {
+        self.queue(LoadCobFromStr { file: file.into(), text: text.into() });
+        self
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
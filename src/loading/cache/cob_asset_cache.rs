@@ -16,7 +16,7 @@ struct PreprocessedSceneFile
     file: CobFile,
     /// Imports for detecting when a re-load is required.
     /// - Can include both manifest keys and file paths.
-    imports: HashMap<ManifestKey, CobImportAlias>,
+    imports: HashMap<ManifestKey, CobImportInfo>,
     /// Data cached for re-loading when dependencies are reloaded.
     data: Cob,
     /// File hash for editor use.
@@ -33,7 +33,7 @@ struct ProcessedSceneFile
     resolver: CobResolver,
     /// Imports for detecting when a re-load is required.
     #[cfg(feature = "hot_reload")]
-    imports: HashMap<ManifestKey, CobImportAlias>,
+    imports: HashMap<ManifestKey, CobImportInfo>,
     /// Un-extracted data cached for re-loading when imports are reloaded.
     #[cfg(feature = "hot_reload")]
     data: Cob,
@@ -83,6 +83,11 @@ pub(crate) struct CobAssetCache
     /// Tracks files that have been processed but not scene-extracted.
     #[cfg(feature = "hot_reload")]
     needs_scene_extraction: HashMap<CobFile, Cob>,
+    /// Tracks files that have been processed but not scene-extracted.
+    ///
+    /// Scenes are extracted after all pending files are processed so patches from any file can apply to them.
+    #[cfg(not(feature = "hot_reload"))]
+    needs_scene_extraction: Vec<(CobFile, Cob)>,
 }
 
 impl CobAssetCache
@@ -107,9 +112,15 @@ impl CobAssetCache
     /// Returns `(num uninitialized files, num total files)`.
     ///
     /// Does not include files recursively loaded via manifests.
+    ///
+    /// Lazy files that were requested but haven't started loading are included.
     fn loading_progress(&self) -> (usize, usize)
     {
-        (self.pending.len(), self.total_expected_sheets)
+        let num_requests = self.manifest_map.lock().unwrap().num_lazy_requests();
+        (
+            self.pending.len() + num_requests,
+            self.total_expected_sheets + num_requests,
+        )
     }
 
     /// Gets the number of files waiting to be processed.
@@ -151,6 +162,59 @@ impl CobAssetCache
         self.register_manifest_key(file, None);
     }
 
+    /// Returns `true` if the file has been registered for loading.
+    pub(crate) fn is_file_registered(&self, file: &CobFile) -> bool
+    {
+        self.file_to_manifest_key.contains_key(file)
+    }
+
+    /// Returns `true` if the file hasn't loaded yet, including lazy files that haven't been requested.
+    pub(crate) fn is_file_pending(&self, file: &CobFile) -> bool
+    {
+        if self.pending.contains(file) {
+            return true;
+        }
+        let status = self.manifest_map.lock().unwrap().lazy_file_status(file);
+        matches!(status, Some(LazyFileStatus::Unloaded | LazyFileStatus::Loading))
+    }
+
+    /// Marks a file as lazy. It will only be loaded when requested.
+    pub(crate) fn mark_lazy_file(&mut self, file: CobFile)
+    {
+        self.manifest_map().mark_lazy_file(file);
+    }
+
+    /// Returns `true` if the file is marked as lazy.
+    pub(crate) fn is_lazy_file(&self, file: &CobFile) -> bool
+    {
+        self.manifest_map
+            .lock()
+            .unwrap()
+            .lazy_file_status(file)
+            .is_some()
+    }
+
+    /// Requests a lazy file if it hasn't been requested yet.
+    ///
+    /// Returns `true` if the file is lazy and hasn't finished loading.
+    pub(crate) fn request_lazy_file(&mut self, file: &CobFile) -> bool
+    {
+        self.manifest_map().request_lazy_file(file)
+    }
+
+    /// Takes lazy files that should start loading.
+    pub(super) fn take_lazy_requests(&mut self) -> Vec<CobFile>
+    {
+        self.manifest_map().take_lazy_requests()
+    }
+
+    /// Returns `true` if any manifest has lazy files.
+    #[cfg(not(feature = "hot_reload"))]
+    pub(crate) fn has_lazy_files(&self) -> bool
+    {
+        self.manifest_map.lock().unwrap().has_lazy_files()
+    }
+
     /// Sets the manifest key for a file.
     ///
     /// The `manifest_key` may be `None` if loaded via the App extension. We use manifest key presence as a proxy
@@ -225,7 +289,7 @@ impl CobAssetCache
     pub(crate) fn add_preprocessed_file(
         &mut self,
         file: CobFile,
-        imports: HashMap<ManifestKey, CobImportAlias>,
+        imports: HashMap<ManifestKey, CobImportInfo>,
         data: Cob,
         #[cfg(feature = "editor")] hash: crate::editor::CobFileHash,
     )
@@ -253,6 +317,11 @@ impl CobAssetCache
             // Try to convert to file. This may fail if the imported file is not initialized yet.
             let Some(import_file) = self.manifest_map().get(import) else { continue };
 
+            // Check if lazy. Lazy files are requested when imported.
+            if self.request_lazy_file(&import_file) {
+                continue;
+            }
+
             // Check if pending.
             if self.pending.contains(&import_file) {
                 continue;
@@ -302,19 +371,17 @@ impl CobAssetCache
         mut preprocessed: PreprocessedSceneFile,
         type_registry: &TypeRegistry,
         loadables: &LoadableRegistry,
-        _c: &mut Commands,
+        flags: &CobFlags,
         commands_buffer: &mut CommandsBuffer,
-        _scene_buffer: &mut SceneBuffer,
-        _scene_loader: &mut SceneLoader,
     )
     {
         // Initialize resolver from dependencies.
         let mut resolver = CobResolver::default();
 
-        for (dependency, alias) in preprocessed.imports.iter() {
-            let Some(dependency) = self.manifest_map().get(&dependency) else {
+        for (key, import) in preprocessed.imports.iter() {
+            let Some(dependency) = self.manifest_map().get(key) else {
                 tracing::error!("failed extracting import {:?} for {:?}; failed manifest key lookup (this is a bug)",
-                    dependency, preprocessed.file);
+                    key, preprocessed.file);
                 continue;
             };
             let Some(processed) = self.processed.get(&dependency) else {
@@ -323,7 +390,7 @@ impl CobAssetCache
                 continue;
             };
 
-            resolver.append(alias, &processed.resolver);
+            resolver.append(&preprocessed.file, key, import, &processed.resolver);
         }
 
         // Prepare to process the file.
@@ -342,6 +409,9 @@ impl CobAssetCache
             processed.hash = preprocessed.hash;
         }
 
+        // Apply conditions before anything else is extracted.
+        apply_cob_conditions(&preprocessed.file, &mut preprocessed.data, flags);
+
         // Process the file.
         // - This updates the constants/specs maps with info extracted from the file.
         extract_cob_importables(preprocessed.file.clone(), &mut preprocessed.data, &mut resolver);
@@ -357,17 +427,9 @@ impl CobAssetCache
 
         #[cfg(not(feature = "hot_reload"))]
         {
-            // Extract scenes immediately.
-            extract_cob_scenes(
-                type_registry,
-                _c,
-                _scene_buffer,
-                _scene_loader,
-                preprocessed.file.clone(),
-                preprocessed.data,
-                loadables,
-                &mut resolver,
-            );
+            // Defer scene extraction until patches from all files are known.
+            self.needs_scene_extraction
+                .push((preprocessed.file.clone(), preprocessed.data));
         }
         #[cfg(feature = "hot_reload")]
         {
@@ -427,12 +489,24 @@ impl CobAssetCache
         &mut self,
         type_registry: &TypeRegistry,
         loadables: &LoadableRegistry,
-        c: &mut Commands,
+        flags: &CobFlags,
+        _c: &mut Commands,
         commands_buffer: &mut CommandsBuffer,
-        scene_buffer: &mut SceneBuffer,
-        scene_loader: &mut SceneLoader,
+        _scene_buffer: &mut SceneBuffer,
+        _scene_loader: &mut SceneLoader,
     ) -> bool
     {
+        // Request lazy files imported by preprocessed files.
+        // - Imports are also checked when files are preprocessed, but a lazy file's manifest entry might not have
+        //   been loaded at that point.
+        {
+            let mut manifest_map = self.manifest_map.lock().unwrap();
+            for import in self.preprocessed.iter().flat_map(|p| p.imports.keys()) {
+                let Some(import_file) = manifest_map.get(import) else { continue };
+                manifest_map.request_lazy_file(&import_file);
+            }
+        }
+
         // Loop preprocessed until nothing can be processed.
         let mut num_processed = 0;
         let mut preprocessed = Vec::new();
@@ -466,15 +540,7 @@ impl CobAssetCache
                     }
                 }
 
-                self.process_cobweb_asset_file(
-                    preprocessed,
-                    type_registry,
-                    loadables,
-                    c,
-                    commands_buffer,
-                    scene_buffer,
-                    scene_loader,
-                );
+                self.process_cobweb_asset_file(preprocessed, type_registry, loadables, flags, commands_buffer);
                 num_processed += 1;
             }
 
@@ -485,7 +551,9 @@ impl CobAssetCache
         }
 
         // Check for failed loads.
-        if self.pending.is_empty() && !self.preprocessed.is_empty() {
+        // - Lazy files that were requested but haven't started loading count as pending.
+        let num_lazy_requests = self.manifest_map().num_lazy_requests();
+        if self.pending.is_empty() && num_lazy_requests == 0 && !self.preprocessed.is_empty() {
             for preproc in self.preprocessed.drain(..) {
                 tracing::error!("discarding COB file {:?} that failed to resolve imports; it either has a \
                     dependency cycle or tries to import unknown manifest keys; note that builtin colors/widgets/etc. \
@@ -494,32 +562,149 @@ impl CobAssetCache
         }
 
         // Clean up memory once all files are loaded and processed.
+        // - Scenes are extracted first, since scene extraction needs the processed files.
+        // - Processed files are kept if there are lazy files, since lazy files may import them.
         #[cfg(not(feature = "hot_reload"))]
         {
-            if self.pending.is_empty() && self.preprocessed.is_empty() {
+            if self.pending.is_empty() && self.preprocessed.is_empty() && num_lazy_requests == 0 {
+                self.extract_pending_scenes(
+                    type_registry,
+                    loadables,
+                    _c,
+                    commands_buffer,
+                    _scene_buffer,
+                    _scene_loader,
+                );
                 tracing::info!("done loading (enable hot_reload feature if you want to reload files)");
                 self.pending = HashSet::default();
                 self.preprocessed = Vec::default();
-                self.processed = HashMap::default();
+                if !self.has_lazy_files() {
+                    self.processed = HashMap::default();
+                }
             }
         }
 
         num_processed > 0
     }
 
+    /// Extracts scenes from processed files.
+    ///
+    /// Patches are extracted from all files before any scenes, so they can apply to scenes in files that were
+    /// processed earlier. Patches from files that are loaded later (e.g. lazy files) won't apply to scenes that
+    /// were already extracted.
+    #[cfg(not(feature = "hot_reload"))]
+    fn extract_pending_scenes(
+        &mut self,
+        type_registry: &TypeRegistry,
+        loadables: &LoadableRegistry,
+        c: &mut Commands,
+        commands_buffer: &CommandsBuffer,
+        scene_buffer: &mut SceneBuffer,
+        scene_loader: &mut SceneLoader,
+    )
+    {
+        let pending = std::mem::take(&mut self.needs_scene_extraction);
+
+        for (file, data) in pending.iter() {
+            let Some(processed) = self.processed.get_mut(file) else { continue };
+            let order = commands_buffer.file_order_key(file);
+            extract_cob_patches(scene_buffer, file, order, data, &mut processed.resolver);
+        }
+
+        for (file, data) in pending {
+            let Some(processed) = self.processed.get_mut(&file) else { continue };
+            extract_cob_scenes(
+                type_registry,
+                c,
+                scene_buffer,
+                scene_loader,
+                file.clone(),
+                data,
+                loadables,
+                &mut processed.resolver,
+            );
+            self.manifest_map().set_lazy_file_loaded(&file);
+        }
+    }
+
+    /// Removes a file's cached data so the file can be unloaded.
+    ///
+    /// Returns the file, or an error if it isn't loaded or is still loading.
+    pub(crate) fn unload_file(&mut self, manifest_key: &ManifestKey) -> Result<CobFile, String>
+    {
+        let Some(file) = self.manifest_map().get(manifest_key) else {
+            return Err(format!("no file has manifest key {:?}", manifest_key.as_str()));
+        };
+
+        let is_loading = self.pending.contains(&file) || self.preprocessed.iter().any(|p| p.file == file);
+        #[cfg(feature = "hot_reload")]
+        let is_loading = is_loading || self.needs_scene_extraction.contains_key(&file);
+        #[cfg(not(feature = "hot_reload"))]
+        let is_loading = is_loading || self.needs_scene_extraction.iter().any(|(f, _)| *f == file);
+        match self.manifest_map().lazy_file_status(&file) {
+            Some(LazyFileStatus::Unloaded) => return Err(format!("file {:?} is not loaded", file.as_str())),
+            Some(LazyFileStatus::Loading) => return Err(format!("file {:?} is still loading", file.as_str())),
+            _ if is_loading => return Err(format!("file {:?} is still loading", file.as_str())),
+            _ => (),
+        }
+
+        self.processed.remove(&file);
+        self.manifest_map().set_lazy_file_unloaded(&file);
+
+        Ok(file)
+    }
+
+    /// Re-queues all processed files so they will be re-extracted.
+    ///
+    /// Used when [`CobFlags`] change, since conditions are evaluated when files are extracted.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn reprocess_all(&mut self, commands_buffer: &mut CommandsBuffer)
+    {
+        let files: Vec<CobFile> = self.processed.keys().cloned().collect();
+
+        for file in files {
+            commands_buffer.prep_commands_refresh(file.clone());
+            let processed = self.processed.remove(&file).unwrap();
+            self.add_preprocessed_file(
+                file,
+                processed.imports,
+                processed.data,
+                #[cfg(feature = "editor")]
+                processed.hash,
+            );
+        }
+    }
+
     #[cfg(feature = "hot_reload")]
     pub(crate) fn handle_pending_scene_extraction(
         &mut self,
         type_registry: &TypeRegistry,
         loadables: &LoadableRegistry,
         c: &mut Commands,
+        commands_buffer: &mut CommandsBuffer,
         scene_buffer: &mut SceneBuffer,
         scene_loader: &mut SceneLoader,
         #[cfg(feature = "editor")] editor: &mut crate::editor::CobEditor,
     )
     {
+        let pending: Vec<(CobFile, Cob)> = self.needs_scene_extraction.drain().collect();
+
+        // Extract patches first so they apply to scenes extracted in this batch.
+        // - Files with scenes targeted by changed patches are re-extracted if they aren't in this batch.
+        let mut needs_rebuild = vec![];
+        for (file, data) in pending.iter() {
+            let Some(processed) = self.processed.get_mut(file) else { continue };
+            let order = commands_buffer.file_order_key(file);
+            for target in extract_cob_patches(scene_buffer, file, order, data, &mut processed.resolver) {
+                if pending.iter().any(|(f, _)| *f == target) || needs_rebuild.contains(&target) {
+                    continue;
+                }
+                needs_rebuild.push(target);
+            }
+        }
+
         // Note: We assume it doesn't matter what file order scenes are extracted in.
-        for (file, data) in self.needs_scene_extraction.drain() {
+        for (file, data) in pending {
             let Some(processed) = self.processed.get_mut(&file) else { continue };
 
             extract_cob_scenes(
@@ -527,11 +712,15 @@ impl CobAssetCache
                 c,
                 scene_buffer,
                 scene_loader,
-                file,
+                file.clone(),
                 data,
                 loadables,
                 &mut processed.resolver,
             );
+            self.manifest_map
+                .lock()
+                .unwrap()
+                .set_lazy_file_loaded(&file);
 
             // Pass to editor.
             // - We wait until after scene extraction to make sure the editor view synchronizes with the
@@ -541,6 +730,18 @@ impl CobAssetCache
                 editor.add_processed(c, processed.hash, &processed.data);
             }
         }
+
+        for file in needs_rebuild {
+            let Some(processed) = self.processed.remove(&file) else { continue };
+            commands_buffer.prep_commands_refresh(file.clone());
+            self.add_preprocessed_file(
+                file,
+                processed.imports,
+                processed.data,
+                #[cfg(feature = "editor")]
+                processed.hash,
+            );
+        }
     }
 }
 
@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -7,6 +11,7 @@ use crate::prelude::*;
 pub struct CobLoadableResolver
 {
     pub constants: ConstantsResolver,
+    pub data_macros: DataMacrosResolver,
 }
 
 impl CobLoadableResolver
@@ -14,16 +19,26 @@ impl CobLoadableResolver
     pub(crate) fn start_new_file(&mut self)
     {
         self.constants.start_new_file();
+        self.data_macros.start_new_file();
     }
 
     pub(crate) fn end_new_file(&mut self)
     {
         self.constants.end_new_file();
+        self.data_macros.end_new_file();
+    }
+
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
+    {
+        self.constants
+            .append(file, alias, filters, &to_append.constants);
+        self.data_macros
+            .append(file, alias, filters, &to_append.data_macros);
     }
 
-    pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
     {
-        self.constants.append(alias, &to_append.constants);
+        self.constants.selects_any(name, exports) || self.data_macros.selects_any(name, exports)
     }
 }
 
@@ -33,6 +48,7 @@ impl CobLoadableResolver
 #[derive(Default, Debug)]
 pub struct CobSceneResolver
 {
+    pub loadable_macros: LoadableMacrosResolver,
     pub scene_macros: SceneMacrosResolver,
 }
 
@@ -40,17 +56,27 @@ impl CobSceneResolver
 {
     pub(crate) fn start_new_file(&mut self)
     {
+        self.loadable_macros.start_new_file();
         self.scene_macros.start_new_file();
     }
 
     pub(crate) fn end_new_file(&mut self)
     {
+        self.loadable_macros.end_new_file();
         self.scene_macros.end_new_file();
     }
 
-    pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
     {
-        self.scene_macros.append(alias, &to_append.scene_macros);
+        self.loadable_macros
+            .append(file, alias, filters, &to_append.loadable_macros);
+        self.scene_macros
+            .append(file, alias, filters, &to_append.scene_macros);
+    }
+
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        self.loadable_macros.selects_any(name, exports) || self.scene_macros.selects_any(name, exports)
     }
 }
 
@@ -62,6 +88,8 @@ pub struct CobResolver
 {
     pub loadables: CobLoadableResolver,
     pub scenes: CobSceneResolver,
+    /// Names listed in the file's `#export` sections. If `None`, then all definitions are exported.
+    pub exports: Option<Arc<[SmolStr]>>,
 }
 
 impl CobResolver
@@ -78,10 +106,42 @@ impl CobResolver
         self.scenes.end_new_file();
     }
 
-    pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
+    /// Appends the definitions of an imported file.
+    ///
+    /// Only definitions exported by the imported file and selected by the import are visible. Collisions with
+    /// previously-appended definitions are recorded as diagnostics.
+    pub(crate) fn append(&mut self, file: &CobFile, key: &ManifestKey, import: &CobImportInfo, to_append: &Self)
+    {
+        let filters: Vec<Arc<[SmolStr]>> = to_append
+            .exports
+            .iter()
+            .chain(import.selection.iter())
+            .cloned()
+            .collect();
+        let alias = import.alias.as_str();
+        self.loadables
+            .append(file, alias, &filters, &to_append.loadables);
+        self.scenes.append(file, alias, &filters, &to_append.scenes);
+
+        // Check for selected names that don't match anything.
+        for name in import
+            .selection
+            .iter()
+            .flat_map(|selection| selection.iter())
+        {
+            if to_append.selects_any(name, to_append.exports.as_deref()) {
+                continue;
+            }
+            let message = format!("import {}::{{{name}}} doesn't match any definitions exported by {}",
+                key.as_str(), key.as_str());
+            emit_cob_diagnostic(CobDiagnostic::warning(file.as_str(), message));
+        }
+    }
+
+    /// Checks if `name` selects any visible definitions that are also selected by `exports`.
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
     {
-        self.loadables.append(alias, &to_append.loadables);
-        self.scenes.append(alias, &to_append.scenes);
+        self.loadables.selects_any(name, exports) || self.scenes.selects_any(name, exports)
     }
 }
 
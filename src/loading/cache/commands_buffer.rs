@@ -139,7 +139,7 @@ const GLOBAL_PSEUDO_FILE: &'static str = "__g.cob";
 /// Manages commands loaded from COB files to ensure they are applied in global order.
 ///
 /// When the `hot_reload` feature is not enabled, this resource will be removed in schedule
-/// `OnExit(LoadState::Loading)`.
+/// `OnExit(LoadState::Loading)` unless there are lazy files.
 #[derive(Resource, Debug)]
 pub(crate) struct CommandsBuffer
 {
@@ -147,6 +147,11 @@ pub(crate) struct CommandsBuffer
     ///
     /// If `None` then no file is targeted for pending commands.
     traversal_point: Option<CobFile>,
+    /// Indicates if the descendants of the traversal point have been traversed.
+    ///
+    /// Descendants of a file are unknown while it is pending, so they need to be traversed when resuming at it.
+    #[cfg(not(feature = "hot_reload"))]
+    traversal_point_descendants_done: bool,
 
     /// Flattened file hierarchy.
     hierarchy: HashMap<CobFile, FileCommandsInfo>,
@@ -201,6 +206,8 @@ impl CommandsBuffer
         let global = Self::global_file();
         let mut buffer = Self {
             traversal_point: Some(global.clone()), // Start traversal at global file.
+            #[cfg(not(feature = "hot_reload"))]
+            traversal_point_descendants_done: false,
             hierarchy: HashMap::default(),
             #[cfg(feature = "hot_reload")]
             file_order: vec![], // file_order is empty, indicating a 'fresh traversal'
@@ -274,6 +281,83 @@ impl CommandsBuffer
         self.set_file_commands(file, vec![]);
     }
 
+    /// Adds a descendant to the 'global root' (no hot reloading). Used for lazy files, which are loaded after
+    /// the app's other files.
+    #[cfg(not(feature = "hot_reload"))]
+    pub(crate) fn add_root_file(&mut self, file: CobFile)
+    {
+        debug_assert!(file.as_str() != GLOBAL_PSEUDO_FILE);
+
+        let global_file = Self::global_file();
+        let global_parent = FileParent::Parent(global_file.clone());
+        match self.hierarchy.get_mut(&file) {
+            // Unloaded root files are reset so they can be loaded again.
+            Some(info) if info.parent == global_parent && info.status == FileStatus::Loaded => {
+                info.status = FileStatus::Pending;
+                info.initialized = false;
+            }
+            Some(_) => {
+                tracing::warn!("ignoring duplicate root file {:?} in commands buffer", file);
+                return;
+            }
+            None => {
+                // Initialize the file's slot.
+                self.hierarchy.insert(
+                    file.clone(),
+                    FileCommandsInfo {
+                        status: FileStatus::Pending,
+                        parent: global_parent,
+                        commands: vec![],
+                        descendants: self.empty_descendants.clone(),
+                        is_orphaned: false,
+                        initialized: false,
+                    },
+                );
+            }
+        }
+        self.file_counter.add(1);
+
+        // Append to the end of the global file's descendants.
+        let global = self.hierarchy.get_mut(&global_file).unwrap();
+        global.descendants = global
+            .descendants
+            .iter()
+            .filter(|d| **d != file)
+            .cloned()
+            .chain(std::iter::once(file.clone()))
+            .collect();
+
+        // Restart traversal at the new file if all other files have been traversed.
+        if self.traversal_point.is_none() {
+            self.traversal_point = Some(file);
+            self.traversal_point_descendants_done = false;
+        }
+    }
+
+    /// Adds a descendant to the 'global root' (with hot reloading). Used for lazy files, which are loaded after
+    /// the app's other files.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn add_root_file(&mut self, file: CobFile)
+    {
+        let global = Self::global_file();
+        let Some(info) = self.hierarchy.get(&global) else {
+            tracing::error!("failed adding root file {:?}; the global file is missing (this is a bug)", file);
+            return;
+        };
+        if info.descendants.contains(&file) {
+            return;
+        }
+
+        let descendants = info
+            .descendants
+            .iter()
+            .cloned()
+            .chain(std::iter::once(file))
+            .collect();
+        self.set_file_descendants(global.clone(), descendants);
+        self.set_file_commands(global, vec![]);
+    }
+
     /// Tries to update the traversal point to the requested file.
     ///
     /// Will truncate `self.file_order` to the new traversal point.
@@ -369,6 +453,15 @@ impl CommandsBuffer
         for descendant in descendants.iter() {
             debug_assert!(descendant.as_str() != GLOBAL_PSEUDO_FILE);
 
+            // Descendants are already registered if this file was unloaded and is being loaded again.
+            if self
+                .hierarchy
+                .get(descendant)
+                .is_some_and(|d| d.parent == FileParent::Parent(file.clone()))
+            {
+                continue;
+            }
+
             if let Some(prev) = self.hierarchy.insert(
                 descendant.clone(),
                 FileCommandsInfo {
@@ -757,6 +850,52 @@ impl CommandsBuffer
         }
     }
 
+    /// Discards a file's cached commands when the file is unloaded.
+    ///
+    /// Commands that were already applied are not reverted.
+    pub(crate) fn unload_file(&mut self, file: &CobFile)
+    {
+        let Some(info) = self.hierarchy.get_mut(file) else { return };
+
+        if !info.is_orphaned {
+            let num_pending = info.commands.iter().filter(|c| c.is_pending).count();
+            self.command_counter.remove(num_pending);
+        }
+        info.commands.clear();
+    }
+
+    /// Gets a key that orders files by their position in the manifest hierarchy.
+    ///
+    /// The key is the index of each ancestor in its parent's descendants, starting at the root. Keys compare in
+    /// the same order that commands are applied. Orphaned and unknown files sort after all other files.
+    pub(crate) fn file_order_key(&self, file: &CobFile) -> Vec<usize>
+    {
+        let mut key = vec![];
+        let mut current = file;
+
+        loop {
+            let Some(info) = self.hierarchy.get(current) else { return vec![usize::MAX] };
+            match &info.parent {
+                FileParent::SelfIsRoot => break,
+                #[cfg(feature = "hot_reload")]
+                FileParent::SelfIsOrphan => return vec![usize::MAX],
+                FileParent::Parent(parent) => {
+                    let Some(parent_info) = self.hierarchy.get(parent) else { return vec![usize::MAX] };
+                    let idx = parent_info
+                        .descendants
+                        .iter()
+                        .position(|d| d == current)
+                        .unwrap_or(usize::MAX);
+                    key.push(idx);
+                    current = parent;
+                }
+            }
+        }
+
+        key.reverse();
+        key
+    }
+
     /// Replaces a specific command in a file.
     #[cfg(feature = "editor")]
     pub(crate) fn patch_command(&mut self, file: CobFile, longname: &'static str, command: ErasedLoadable)
@@ -887,7 +1026,8 @@ impl CommandsBuffer
             // Set the current stack-top to descendants-done, because this 'traversal point' is *after*
             // descendants.
             // - If self is the root then we must be starting from the 'beginning'.
-            if info.parent != FileParent::SelfIsRoot {
+            // - If the traversal paused while the file was pending, then its descendants are new.
+            if info.parent != FileParent::SelfIsRoot && self.traversal_point_descendants_done {
                 stack.last_mut().map(|(_, _, desc_done)| *desc_done = true);
             }
         }
@@ -931,6 +1071,7 @@ impl CommandsBuffer
                     #[cfg(not(feature = "hot_reload"))]
                     {
                         buff.traversal_point = Some(file.clone());
+                        buff.traversal_point_descendants_done = info.status != FileStatus::Pending;
                     }
 
                     #[cfg(feature = "hot_reload")]
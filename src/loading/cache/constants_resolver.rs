@@ -2,12 +2,10 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use smallvec::SmallVec;
 use smol_str::SmolStr;
 
 use super::*;
 use crate::loading::{CobConstantValue, CobFile};
-use crate::prelude::CobImportAlias;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -22,8 +20,11 @@ type ConstantsMap = HashMap<SmolStr, CobConstantValue>;
 #[derive(Default, Debug)]
 pub struct ConstantsResolver
 {
-    stack: SmallVec<[(SmolStr, Arc<ConstantsMap>); 5]>,
+    stack: DefsStack<CobConstantValue>,
     new_file: ConstantsMap,
+    /// Macro parameters and `#for` bindings. These shadow all other constants while a macro is being resolved or
+    /// expanded, or while a `#for` loop is being expanded. Later entries shadow earlier entries.
+    params: Vec<(SmolStr, CobConstantValue)>,
 }
 
 impl ConstantsResolver
@@ -36,12 +37,20 @@ impl ConstantsResolver
     pub(crate) fn end_new_file(&mut self)
     {
         let map = std::mem::take(&mut self.new_file);
-        self.stack.push((SmolStr::default(), Arc::new(map)));
+        self.stack.push_file(map);
     }
 
     /// Adds an entry to the new file being collected.
     pub(crate) fn insert(&mut self, file: &CobFile, name: SmolStr, value: CobConstantValue)
     {
+        if self.stack.get(name.as_str()).is_some() {
+            record_def_collision(
+                file,
+                format!("constant ${} is also imported; local definitions can't shadow imported definitions",
+                    name.as_str()),
+            );
+        }
+
         match self.new_file.entry(name) {
             Entry::Vacant(vacant) => {
                 vacant.insert(value);
@@ -53,41 +62,57 @@ impl ConstantsResolver
         }
     }
 
+    /// Sets macro parameters that will shadow other constants until [`Self::clear_params`] is called.
+    pub(crate) fn set_params(&mut self, params: impl IntoIterator<Item = (SmolStr, CobConstantValue)>)
+    {
+        self.params.clear();
+        self.params.extend(params);
+    }
+
+    pub(crate) fn clear_params(&mut self)
+    {
+        self.params.clear();
+    }
+
+    /// Adds parameters that will shadow other constants and existing parameters.
+    ///
+    /// Returns the number of parameters that were set previously. Pass it to [`Self::truncate_params`] to remove
+    /// the added parameters.
+    pub(crate) fn push_params(&mut self, params: impl IntoIterator<Item = (SmolStr, CobConstantValue)>) -> usize
+    {
+        let prev_len = self.params.len();
+        self.params.extend(params);
+        prev_len
+    }
+
+    pub(crate) fn truncate_params(&mut self, len: usize)
+    {
+        self.params.truncate(len);
+    }
+
     /// Searches backward through the stack until a match is found.
+    ///
+    /// Macro parameters are checked first if any are set.
     pub fn get(&self, path: impl AsRef<str>) -> Option<&CobConstantValue>
     {
         let path = path.as_ref();
-        self.new_file.get(path).or_else(|| {
-            self.stack.iter().rev().find_map(|(prefix, m)| {
-                let stripped = path.strip_prefix(prefix.as_str())?;
-                let cleaned = stripped.strip_prefix(DEFS_SEPARATOR).unwrap_or(stripped);
-                m.get(cleaned)
-            })
-        })
+        if let Some((_, param)) = self.params.iter().rev().find(|(name, _)| name == path) {
+            return Some(param);
+        }
+        self.new_file.get(path).or_else(|| self.stack.get(path))
     }
 
-    pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
     {
-        let alias = alias.as_str();
-
-        // Remove duplicate maps in self.
-        for (to_append_prefix, to_append) in to_append.stack.iter() {
-            let new_to_append_prefix = path_to_string(DEFS_SEPARATOR, &[alias, &*to_append_prefix]);
-            let Some(existing) = self.stack.iter().position(|(prefix, m)| {
-                *prefix == new_to_append_prefix && Arc::as_ptr(m) == Arc::as_ptr(to_append)
-            }) else {
-                continue;
-            };
-            self.stack.remove(existing);
+        for path in self.stack.append(alias, filters, &to_append.stack) {
+            record_def_collision(file, format!("constant ${path} is provided by more than one import"));
         }
+    }
 
-        // Append.
-        self.stack.reserve(to_append.stack.len());
-        self.stack
-            .extend(to_append.stack.iter().map(|(old_prefix, map)| {
-                let new_prefix = path_to_string(DEFS_SEPARATOR, &[alias, &*old_prefix]);
-                (new_prefix, map.clone())
-            }));
+    /// Checks if `name` selects any def in the stack that is also selected by `exports`.
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        self.stack.selects_any(name, exports)
     }
 }
 
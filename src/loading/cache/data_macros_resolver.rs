@@ -0,0 +1,140 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+
+use super::*;
+use crate::loading::{
+    CobConstant, CobConstantPath, CobConstantValue, CobDataMacroCall, CobDataMacroParams, CobFile, CobFill,
+    CobValue,
+};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// [ identifier : (macro params, macro value) ]
+type DataMacrosMap = HashMap<SmolStr, (CobDataMacroParams, CobValue)>;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a placeholder for a data or scene macro parameter.
+///
+/// Placeholders resolve to themselves, which lets parameters survive def resolution so they can be replaced when
+/// the macro is expanded.
+pub(crate) fn macro_param_placeholder(name: &str) -> CobConstantValue
+{
+    CobConstantValue::Value(CobValue::Constant(CobConstant {
+        start_fill: CobFill::default(),
+        path: CobConstantPath { path: SmolStr::from(name) },
+    }))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a stack of data macro maps.
+///
+/// Used to efficiently merge data macros when importing them into new files.
+#[derive(Default, Debug)]
+pub struct DataMacrosResolver
+{
+    stack: DefsStack<(CobDataMacroParams, CobValue)>,
+    new_file: DataMacrosMap,
+}
+
+impl DataMacrosResolver
+{
+    pub(crate) fn start_new_file(&mut self)
+    {
+        self.new_file = HashMap::default();
+    }
+
+    pub(crate) fn end_new_file(&mut self)
+    {
+        let map = std::mem::take(&mut self.new_file);
+        self.stack.push_file(map);
+    }
+
+    /// Adds an entry to the new file being collected.
+    ///
+    /// The value should already be resolved, except for references to the macro's own parameters.
+    pub(crate) fn insert(&mut self, file: &CobFile, name: SmolStr, params: CobDataMacroParams, value: CobValue)
+    {
+        if self.stack.get(name.as_str()).is_some() {
+            record_def_collision(
+                file,
+                format!("data macro *{} is also imported; local definitions can't shadow imported definitions",
+                    name.as_str()),
+            );
+        }
+
+        match self.new_file.entry(name) {
+            Entry::Vacant(vacant) => {
+                vacant.insert((params, value));
+            }
+            Entry::Occupied(mut occupied) => {
+                tracing::warn!("overwriting data macro definition *{} in {:?}", occupied.key().as_str(), file);
+                occupied.insert((params, value));
+            }
+        }
+    }
+
+    /// Searches backward through the stack until a match is found.
+    pub fn get(&self, path: impl AsRef<str>) -> Option<(&CobDataMacroParams, &CobValue)>
+    {
+        let path = path.as_ref();
+        self.new_file
+            .get(path)
+            .or_else(|| self.stack.get(path))
+            .map(|(params, value)| (params, value))
+    }
+
+    /// Expands a data macro invocation into a value.
+    ///
+    /// The invocation's arguments should already be resolved.
+    pub fn expand(&self, call: &CobDataMacroCall) -> Result<CobValue, String>
+    {
+        let path = call.path.as_str();
+        let (params, value) = self
+            .get(path)
+            .ok_or_else(|| format!("no data macro definition at '{path}'"))?;
+
+        if params.len() != call.args.entries.len() {
+            return Err(
+                format!("data macro *{path} expects {} argument(s) but {} were provided",
+                params.len(), call.args.entries.len()),
+            );
+        }
+
+        // Substitute arguments for parameters. Since the macro value is already resolved, the only remaining
+        // constants are parameter placeholders.
+        let mut args = CobLoadableResolver::default();
+        args.constants.set_params(
+            params
+                .iter()
+                .zip(call.args.entries.iter())
+                .map(|(param, arg)| (SmolStr::from(param), CobConstantValue::Value(arg.clone()))),
+        );
+
+        let mut expanded = value.clone();
+        if expanded.resolve(&args)?.is_some() {
+            return Err(format!("data macro *{path} unexpectedly expanded to a value group"));
+        }
+
+        Ok(expanded)
+    }
+
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
+    {
+        for path in self.stack.append(alias, filters, &to_append.stack) {
+            record_def_collision(file, format!("data macro *{path} is provided by more than one import"));
+        }
+    }
+
+    /// Checks if `name` selects any def in the stack that is also selected by `exports`.
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        self.stack.selects_any(name, exports)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use smallvec::SmallVec;
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks if a def path is selected by a list of names.
+///
+/// A name selects the def at that path and all defs in that namespace (`a` selects `a` and `a::b`).
+pub(crate) fn def_path_is_selected(path: &str, names: &[SmolStr]) -> bool
+{
+    names
+        .iter()
+        .any(|name| match path.strip_prefix(name.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with(DEFS_SEPARATOR),
+            None => false,
+        })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prefix_names(prefix: &str, names: &Arc<[SmolStr]>) -> Arc<[SmolStr]>
+{
+    if prefix.is_empty() {
+        return names.clone();
+    }
+    names
+        .iter()
+        .map(|name| path_to_string(DEFS_SEPARATOR, &[prefix, name.as_str()]))
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a def collision.
+pub(super) fn record_def_collision(file: &CobFile, message: String)
+{
+    emit_cob_diagnostic(CobDiagnostic::error(file.as_str(), message));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct DefsStackEntry<T>
+{
+    /// Prefix added to all def names in the map.
+    prefix: SmolStr,
+    /// Lists of full def paths selected by exports and selective imports.
+    ///
+    /// A def is only visible if every list selects it.
+    filters: SmallVec<[Arc<[SmolStr]>; 1]>,
+    map: Arc<HashMap<SmolStr, T>>,
+}
+
+impl<T> DefsStackEntry<T>
+{
+    fn is_visible(&self, path: &str) -> bool
+    {
+        self.filters
+            .iter()
+            .all(|filter| def_path_is_selected(path, filter))
+    }
+
+    fn get(&self, path: &str) -> Option<&T>
+    {
+        let name = match self.prefix.is_empty() {
+            true => path,
+            false => path
+                .strip_prefix(self.prefix.as_str())?
+                .strip_prefix(DEFS_SEPARATOR)?,
+        };
+        let value = self.map.get(name)?;
+        self.is_visible(path).then_some(value)
+    }
+
+    /// Iterates the full paths of visible defs.
+    fn visible_paths(&self) -> impl Iterator<Item = SmolStr> + '_
+    {
+        self.map
+            .keys()
+            .map(|name| path_to_string(DEFS_SEPARATOR, &[self.prefix.as_str(), name.as_str()]))
+            .filter(|path| self.is_visible(path))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stack of def maps shared by the def resolvers.
+///
+/// Later maps shadow earlier maps.
+#[derive(Debug)]
+pub(super) struct DefsStack<T>
+{
+    stack: SmallVec<[DefsStackEntry<T>; 5]>,
+}
+
+impl<T> DefsStack<T>
+{
+    /// Adds the defs of a file.
+    pub(super) fn push_file(&mut self, map: HashMap<SmolStr, T>)
+    {
+        self.stack.push(DefsStackEntry {
+            prefix: SmolStr::default(),
+            filters: SmallVec::default(),
+            map: Arc::new(map),
+        });
+    }
+
+    /// Searches backward through the stack until a visible def is found.
+    pub(super) fn get(&self, path: &str) -> Option<&T>
+    {
+        self.stack.iter().rev().find_map(|entry| entry.get(path))
+    }
+
+    /// Checks if any visible def is selected by `name` and by `exports`.
+    pub(super) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        let name = [SmolStr::from(name)];
+        self.stack.iter().any(|entry| {
+            entry.visible_paths().any(|path| {
+                def_path_is_selected(&path, &name) && exports.is_none_or(|e| def_path_is_selected(&path, e))
+            })
+        })
+    }
+
+    /// Appends the stack of an imported file.
+    ///
+    /// `filters` are lists of names relative to the imported file that select which of its defs are visible.
+    ///
+    /// Returns visible def paths that are provided by both stacks with different defs.
+    pub(super) fn append(&mut self, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self) -> Vec<SmolStr>
+    {
+        let filters: SmallVec<[Arc<[SmolStr]>; 2]> = filters
+            .iter()
+            .map(|filter| prefix_names(alias, filter))
+            .collect();
+        let new_entries: SmallVec<[DefsStackEntry<T>; 5]> = to_append
+            .stack
+            .iter()
+            .map(|entry| DefsStackEntry {
+                prefix: path_to_string(DEFS_SEPARATOR, &[alias, entry.prefix.as_str()]),
+                filters: entry
+                    .filters
+                    .iter()
+                    .map(|filter| prefix_names(alias, filter))
+                    .chain(filters.iter().cloned())
+                    .collect(),
+                map: entry.map.clone(),
+            })
+            .collect();
+
+        // Remove duplicate maps in self.
+        for new_entry in new_entries.iter() {
+            let Some(existing) = self.stack.iter().position(|entry| {
+                entry.prefix == new_entry.prefix
+                    && Arc::ptr_eq(&entry.map, &new_entry.map)
+                    && entry.filters == new_entry.filters
+            }) else {
+                continue;
+            };
+            self.stack.remove(existing);
+        }
+
+        // Find collisions.
+        let mut collisions = vec![];
+        for new_entry in new_entries.iter() {
+            collisions.extend(new_entry.visible_paths().filter(|path| {
+                self.stack
+                    .iter()
+                    .any(|entry| !Arc::ptr_eq(&entry.map, &new_entry.map) && entry.get(path).is_some())
+            }));
+        }
+
+        // Append.
+        self.stack.extend(new_entries);
+
+        collisions
+    }
+}
+
+impl<T> Default for DefsStack<T>
+{
+    fn default() -> Self
+    {
+        Self { stack: SmallVec::default() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,112 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+
+use super::*;
+use crate::loading::{CobFile, CobLoadableMacroCall, CobLoadableMacroValue, CobSceneLayerEntry};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// [ identifier : macro value ]
+type LoadableMacrosMap = HashMap<SmolStr, CobLoadableMacroValue>;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a stack of loadable macro maps.
+///
+/// Used to efficiently merge loadable macros when importing them into new files.
+#[derive(Default, Debug)]
+pub struct LoadableMacrosResolver
+{
+    stack: DefsStack<CobLoadableMacroValue>,
+    new_file: LoadableMacrosMap,
+    id_scratch: String,
+}
+
+impl LoadableMacrosResolver
+{
+    pub(crate) fn start_new_file(&mut self)
+    {
+        self.new_file = HashMap::default();
+    }
+
+    pub(crate) fn end_new_file(&mut self)
+    {
+        let map = std::mem::take(&mut self.new_file);
+        self.stack.push_file(map);
+    }
+
+    /// Adds an entry to the new file being collected.
+    pub(crate) fn insert(&mut self, file: &CobFile, name: SmolStr, mut value: CobLoadableMacroValue)
+    {
+        // Canonicalize all loadable names in the macro value.
+        self.id_scratch = canonicalize_loadable_names(std::mem::take(&mut self.id_scratch), &mut value.entries);
+
+        if self.stack.get(name.as_str()).is_some() {
+            record_def_collision(
+                file,
+                format!("loadable macro @{} is also imported; local definitions can't shadow imported definitions",
+                    name.as_str()),
+            );
+        }
+
+        match self.new_file.entry(name) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(value);
+            }
+            Entry::Occupied(mut occupied) => {
+                tracing::warn!("overwriting loadable macro definition @{} in {:?}", occupied.key().as_str(), file);
+                occupied.insert(value);
+            }
+        }
+    }
+
+    /// Searches backward through the stack until a match is found.
+    pub fn get(&self, path: impl AsRef<str>) -> Option<&CobLoadableMacroValue>
+    {
+        let path = path.as_ref();
+        self.new_file.get(path).or_else(|| self.stack.get(path))
+    }
+
+    /// Expands a loadable macro invocation into loadables.
+    ///
+    /// Loadables in the invocation overwrite or are appended to the macro's loadables, and scene macro commands
+    /// are applied to the result.
+    pub fn expand(&mut self, call: &CobLoadableMacroCall) -> Result<Vec<CobSceneLayerEntry>, String>
+    {
+        let path = call.path.as_str();
+        let mut result_entries = self
+            .get(path)
+            .ok_or_else(|| format!("no loadable macro definition at '{path}'"))?
+            .entries
+            .clone();
+
+        self.id_scratch = expand_macro_recursive(
+            std::mem::take(&mut self.id_scratch),
+            &mut result_entries,
+            &call.container.entries,
+        );
+
+        Ok(result_entries)
+    }
+
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
+    {
+        for path in self.stack.append(alias, filters, &to_append.stack) {
+            record_def_collision(
+                file,
+                format!("loadable macro @{path} is provided by more than one import"),
+            );
+        }
+    }
+
+    /// Checks if `name` selects any def in the stack that is also selected by `exports`.
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        self.stack.selects_any(name, exports)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -4,11 +4,31 @@ use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Load status of a file marked `lazy` in a manifest.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum LazyFileStatus
+{
+    /// The file has not been requested.
+    Unloaded,
+    /// The file has been requested but its scenes have not been extracted.
+    Loading,
+    /// The file's scenes have been extracted.
+    Loaded,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Stores a map between manifest aliases and canonical file names.
+///
+/// Also tracks files that are loaded on demand.
 #[derive(Default, Debug)]
 pub(crate) struct ManifestMap
 {
     map: HashMap<ManifestKey, CobFile>,
+    /// Files marked `lazy` in manifests.
+    lazy_files: HashMap<CobFile, LazyFileStatus>,
+    /// Lazy files that should start loading.
+    lazy_requests: Vec<CobFile>,
 }
 
 impl ManifestMap
@@ -43,6 +63,85 @@ impl ManifestMap
         };
         *maybe_key = SceneFile::File(file_ref.clone());
     }
+
+    /// Marks a file as lazy. It won't be loaded until requested.
+    pub(crate) fn mark_lazy_file(&mut self, file: CobFile)
+    {
+        self.lazy_files
+            .entry(file)
+            .or_insert(LazyFileStatus::Unloaded);
+    }
+
+    /// Gets the status of a lazy file.
+    ///
+    /// Returns `None` if the file isn't lazy.
+    pub(crate) fn lazy_file_status(&self, file: &CobFile) -> Option<LazyFileStatus>
+    {
+        self.lazy_files.get(file).copied()
+    }
+
+    /// Requests a lazy file if it hasn't been requested yet.
+    ///
+    /// Returns `true` if the file is lazy and hasn't finished loading.
+    pub(crate) fn request_lazy_file(&mut self, file: &CobFile) -> bool
+    {
+        let Some(status) = self.lazy_files.get_mut(file) else { return false };
+        match status {
+            LazyFileStatus::Unloaded => {
+                *status = LazyFileStatus::Loading;
+                self.lazy_requests.push(file.clone());
+                true
+            }
+            LazyFileStatus::Loading => true,
+            LazyFileStatus::Loaded => false,
+        }
+    }
+
+    /// Marks a lazy file as loaded once its scenes have been extracted.
+    ///
+    /// Does nothing if the file isn't lazy.
+    pub(crate) fn set_lazy_file_loaded(&mut self, file: &CobFile)
+    {
+        let Some(status) = self.lazy_files.get_mut(file) else { return };
+        *status = LazyFileStatus::Loaded;
+    }
+
+    /// Marks a lazy file as unloaded so it can be requested again.
+    ///
+    /// Does nothing if the file isn't lazy.
+    pub(crate) fn set_lazy_file_unloaded(&mut self, file: &CobFile)
+    {
+        let Some(status) = self.lazy_files.get_mut(file) else { return };
+        *status = LazyFileStatus::Unloaded;
+    }
+
+    /// Takes lazy files that should start loading.
+    pub(crate) fn take_lazy_requests(&mut self) -> Vec<CobFile>
+    {
+        std::mem::take(&mut self.lazy_requests)
+    }
+
+    /// Gets the number of lazy files waiting to start loading.
+    pub(crate) fn num_lazy_requests(&self) -> usize
+    {
+        self.lazy_requests.len()
+    }
+
+    /// Returns `true` if any manifest has lazy files.
+    #[cfg(not(feature = "hot_reload"))]
+    pub(crate) fn has_lazy_files(&self) -> bool
+    {
+        !self.lazy_files.is_empty()
+    }
+
+    /// Iterates lazy files that are loaded.
+    pub(crate) fn loaded_lazy_files(&self) -> impl Iterator<Item = &CobFile> + '_
+    {
+        self.lazy_files
+            .iter()
+            .filter(|(_, status)| **status == LazyFileStatus::Loaded)
+            .map(|(file, _)| file)
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -2,18 +2,26 @@ mod cob_asset_cache;
 mod cob_resolver;
 mod commands_buffer;
 mod constants_resolver;
+mod data_macros_resolver;
+mod defs_stack;
+mod loadable_macros_resolver;
 mod manifest_map;
 mod plugin;
 mod scene_buffer;
 mod scene_macros_resolver;
+mod unload_cob_file;
 mod utils;
 
 pub(crate) use cob_asset_cache::*;
 pub use cob_resolver::*;
 pub(crate) use commands_buffer::*;
 pub use constants_resolver::*;
+pub use data_macros_resolver::*;
+pub(crate) use defs_stack::*;
+pub use loadable_macros_resolver::*;
 pub(crate) use manifest_map::*;
 pub(crate) use plugin::*;
 pub use scene_buffer::*;
 pub use scene_macros_resolver::*;
+pub use unload_cob_file::*;
 pub(self) use utils::*;
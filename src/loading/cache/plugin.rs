@@ -8,6 +8,25 @@ use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Starts loading lazy files that were requested by scene loads or imports.
+fn load_lazy_cob_files(
+    asset_server: Res<AssetServer>,
+    mut cob_files: ResMut<LoadedCobAssetFiles>,
+    mut cob_cache: ResMut<CobAssetCache>,
+    mut commands_buffer: ResMut<CommandsBuffer>,
+)
+{
+    for file in cob_cache.take_lazy_requests() {
+        tracing::info!("loading lazy COB file {:?}", file.as_str());
+
+        // Lazy files are added to the commands buffer after all other files, since they are loaded last.
+        commands_buffer.add_root_file(file.clone());
+        cob_files.start_loading(file, &mut cob_cache, &asset_server);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 fn preprocess_cobweb_asset_files(
     asset_server: Res<AssetServer>,
     mut events: EventReader<AssetEvent<CobAssetFile>>,
@@ -15,8 +34,46 @@ fn preprocess_cobweb_asset_files(
     mut assets: ResMut<Assets<CobAssetFile>>,
     mut cob_cache: ResMut<CobAssetCache>,
     mut commands_buffer: ResMut<CommandsBuffer>,
+    diagnostics: Res<CobDiagnosticsQueue>,
+    #[cfg(feature = "editor")] registry: Res<crate::editor::CobHashRegistry>,
 )
 {
+    // Files loaded from strings are parsed here so they go through the same pipeline as asset files.
+    for (file, text) in cob_files.take_pending_sources() {
+        let asset = match parse_cob_file(
+            file.as_str(),
+            &text,
+            &diagnostics,
+            #[cfg(feature = "editor")]
+            &registry,
+        ) {
+            Ok(asset) => asset,
+            Err(err) => {
+                tracing::error!("failed loading COB file {:?} from string; {}", file.as_str(), err);
+                continue;
+            }
+        };
+
+        match asset {
+            CobAssetFile::Ignore => continue,
+            CobAssetFile::File {
+                #[cfg(feature = "editor")]
+                hash,
+                data,
+            } => {
+                preprocess_cob_file(
+                    &asset_server,
+                    &mut cob_files,
+                    &mut cob_cache,
+                    &mut commands_buffer,
+                    data,
+                    #[cfg(feature = "editor")]
+                    hash,
+                );
+            }
+        }
+    }
+
     for event in events.read() {
         let id = match event {
             AssetEvent::Added { id } | AssetEvent::Modified { id } => id,
@@ -58,6 +115,32 @@ fn preprocess_cobweb_asset_files(
 
     // Note: we don't try to handle asset load failures here because a file load failure is assumed to be
     // catastrophic.
+
+    diagnostics.extend(take_cob_diagnostics());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Re-extracts all files when [`CobFlags`] change so conditions will be re-evaluated.
+#[cfg(feature = "hot_reload")]
+fn refresh_cob_flags(
+    flags: Res<CobFlags>,
+    mut prev_flags: Local<Option<CobFlags>>,
+    mut cob_cache: ResMut<CobAssetCache>,
+    mut commands_buffer: ResMut<CommandsBuffer>,
+)
+{
+    if !flags.is_changed() || (*prev_flags).as_ref() == Some(&*flags) {
+        return;
+    }
+
+    // The first time this runs there is nothing to refresh.
+    if prev_flags.replace((*flags).clone()).is_none() {
+        return;
+    }
+
+    tracing::info!("COB flags changed, reloading all COB files");
+    cob_cache.reprocess_all(&mut commands_buffer);
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -65,23 +148,29 @@ fn preprocess_cobweb_asset_files(
 fn process_cobweb_asset_files(
     types: Res<AppTypeRegistry>,
     loadables: Res<LoadableRegistry>,
+    flags: Res<CobFlags>,
     mut cob_cache: ResMut<CobAssetCache>,
     mut c: Commands,
     mut commands_buffer: ResMut<CommandsBuffer>,
     mut scene_buffer: ResMut<SceneBuffer>,
     mut scene_loader: ResMut<SceneLoader>,
+    diagnostics: Res<CobDiagnosticsQueue>,
 )
 {
     let type_registry = types.read();
 
-    if cob_cache.process_cobweb_asset_files(
+    let updated = cob_cache.process_cobweb_asset_files(
         &type_registry,
         &loadables,
+        &flags,
         &mut c,
         &mut commands_buffer,
         &mut scene_buffer,
         &mut scene_loader,
-    ) {
+    );
+    diagnostics.extend(take_cob_diagnostics());
+
+    if updated {
         c.react().broadcast(CobCacheUpdated);
     }
 }
@@ -95,6 +184,14 @@ fn apply_pending_commands(mut c: Commands, mut buffer: ResMut<CommandsBuffer>, l
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Loads scenes that were waiting for lazy files.
+fn spawn_pending_scenes(mut c: Commands, mut scene_loader: ResMut<SceneLoader>)
+{
+    scene_loader.spawn_pending_scenes(&mut c);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Only enabled for hot_reload because normally entities are loaded only once, the first time they subscribe
 /// to a loadable ref.
 #[cfg(feature = "hot_reload")]
@@ -124,10 +221,11 @@ fn apply_pending_node_updates_extract(
     loadables: Res<LoadableRegistry>,
     mut cob_cache: ResMut<CobAssetCache>,
     mut c: Commands,
-    commands_buffer: Res<CommandsBuffer>,
+    mut commands_buffer: ResMut<CommandsBuffer>,
     mut scene_buffer: ResMut<SceneBuffer>,
     mut scene_loader: ResMut<SceneLoader>,
     #[cfg(feature = "editor")] mut editor: ResMut<crate::editor::CobEditor>,
+    diagnostics: Res<CobDiagnosticsQueue>,
 )
 {
     // Check if blocked.
@@ -141,11 +239,13 @@ fn apply_pending_node_updates_extract(
         &type_registry,
         &loadables,
         &mut c,
+        &mut commands_buffer,
         &mut scene_buffer,
         &mut scene_loader,
         #[cfg(feature = "editor")]
         &mut editor,
     );
+    diagnostics.extend(take_cob_diagnostics());
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -218,6 +318,9 @@ impl Plugin for CobAssetCachePlugin
             .add_systems(
                 First,
                 (
+                    #[cfg(feature = "hot_reload")]
+                    refresh_cob_flags,
+                    load_lazy_cob_files,
                     preprocess_cobweb_asset_files,
                     process_cobweb_asset_files.run_if(|s: Res<CobAssetCache>| s.num_preprocessed_pending() > 0),
                     #[cfg(feature = "hot_reload")]
@@ -229,6 +332,7 @@ impl Plugin for CobAssetCachePlugin
                     apply_pending_node_updates_extract,
                     #[cfg(feature = "hot_reload")]
                     apply_pending_node_updates_post,
+                    spawn_pending_scenes,
                 )
                     .chain()
                     .in_set(FileProcessingSet),
@@ -255,10 +359,21 @@ impl Plugin for CobAssetCachePlugin
 
         #[cfg(not(feature = "hot_reload"))]
         {
-            app.configure_sets(First, FileProcessingSet.run_if(in_state(LoadState::Loading)))
-                .add_systems(OnExit(LoadState::Loading), |mut c: Commands| {
-                    c.remove_resource::<CommandsBuffer>();
-                });
+            // Files are only processed while loading, unless lazy files can be loaded later.
+            app.configure_sets(
+                First,
+                FileProcessingSet.run_if(
+                    in_state(LoadState::Loading).or(|cob_cache: Res<CobAssetCache>| cob_cache.has_lazy_files()),
+                ),
+            )
+            .add_systems(
+                OnExit(LoadState::Loading),
+                |mut c: Commands, cob_cache: Res<CobAssetCache>| {
+                    if !cob_cache.has_lazy_files() {
+                        c.remove_resource::<CommandsBuffer>();
+                    }
+                },
+            );
         }
 
         #[cfg(feature = "hot_reload")]
@@ -11,6 +11,7 @@ use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
 use smallvec::SmallVec;
 
+use super::*;
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -202,6 +203,44 @@ impl RefreshCtx
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Scene items from a `#patches` section that overlay a scene node in another file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScenePatch
+{
+    /// The patched scene node. The file is a manifest key.
+    pub(crate) target: SceneRef,
+    /// Fully-resolved scene items.
+    pub(crate) entries: Vec<CobSceneLayerEntry>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the items a patch applies to the scene node at `path`.
+///
+/// Patches apply to their target node and to child nodes of the target that appear in the patch.
+fn get_patch_entries<'a>(
+    target: &ScenePath,
+    path: &ScenePath,
+    entries: &'a [CobSceneLayerEntry],
+) -> Option<&'a [CobSceneLayerEntry]>
+{
+    if path.len() < target.len() || target.iter().zip(path.iter()).any(|(a, b)| a != b) {
+        return None;
+    }
+
+    let mut entries = entries;
+    for segment in path.iter().skip(target.len()) {
+        entries = entries.iter().find_map(|entry| {
+            let CobSceneLayerEntry::Layer(layer) = entry else { return None };
+            (layer.name.as_str() == segment).then_some(layer.entries.as_slice())
+        })?;
+    }
+
+    Some(entries)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Copy, Clone, Debug)]
 struct SubscriptionRef
 {
@@ -223,6 +262,9 @@ pub struct SceneBuffer
     /// repaired).
     loadables: HashMap<SceneRef, SmallVec<[ErasedLoadable; 4]>>,
 
+    /// Tracks scene patches from each file, along with the file's manifest order key.
+    patches: HashMap<CobFile, (Vec<usize>, Vec<ScenePatch>)>,
+
     /// Tracks subscriptions to scene paths.
     #[cfg(feature = "hot_reload")]
     subscriptions: HashMap<SceneRef, SmallVec<[SubscriptionRef; 1]>>,
@@ -255,6 +297,97 @@ impl SceneBuffer
         self.loadables.entry(scene_ref).or_default();
     }
 
+    /// Sets the scene patches extracted from a file.
+    ///
+    /// Returns files with scenes targeted by patches that were added, changed, or removed.
+    pub(crate) fn set_file_patches(
+        &mut self,
+        file: &CobFile,
+        order: Vec<usize>,
+        patches: Vec<ScenePatch>,
+    ) -> Vec<CobFile>
+    {
+        let prev = self.patches.remove(file).unwrap_or_default();
+        let new = (order, patches);
+        if prev == new {
+            if !new.1.is_empty() {
+                self.patches.insert(file.clone(), new);
+            }
+            return vec![];
+        }
+
+        let manifest_map = self.manifest_map.lock().unwrap();
+        let mut targets = vec![];
+        for patch in prev.1.iter().chain(new.1.iter()) {
+            let target = match &patch.target.file {
+                SceneFile::File(file) => Some(file.clone()),
+                SceneFile::ManifestKey(key) => manifest_map.get(key),
+            };
+            let Some(target) = target else { continue };
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+
+        if !new.1.is_empty() {
+            self.patches.insert(file.clone(), new);
+        }
+
+        targets
+    }
+
+    /// Applies scene patches that target a scene node to the node's scene items.
+    ///
+    /// The items should only be resolved for the node's own layer. Child nodes added by patches are empty until
+    /// patches are applied to them.
+    pub(crate) fn apply_scene_patches(
+        &self,
+        mut id_scratch: String,
+        scene_location: &SceneRef,
+        entries: &mut Vec<CobSceneLayerEntry>,
+    ) -> String
+    {
+        if self.patches.is_empty() {
+            return id_scratch;
+        }
+        let Some(file) = scene_location.file.file() else { return id_scratch };
+
+        // Collect patches in manifest order.
+        let mut matches = vec![];
+        {
+            let manifest_map = self.manifest_map.lock().unwrap();
+            for (order, patches) in self.patches.values() {
+                for patch in patches.iter() {
+                    let target = match &patch.target.file {
+                        SceneFile::File(file) => Some(file.clone()),
+                        SceneFile::ManifestKey(key) => manifest_map.get(key),
+                    };
+                    if target.as_ref() != Some(file) {
+                        continue;
+                    }
+                    let Some(patch_entries) =
+                        get_patch_entries(&patch.target.path, &scene_location.path, &patch.entries)
+                    else {
+                        continue;
+                    };
+                    matches.push((order, patch_entries));
+                }
+            }
+        }
+        if matches.is_empty() {
+            return id_scratch;
+        }
+        matches.sort_by_key(|(order, _)| *order);
+
+        // Apply the patches.
+        id_scratch = canonicalize_loadable_names(id_scratch, entries);
+        for (_, patch_entries) in matches {
+            id_scratch = apply_scene_patch(id_scratch, entries, patch_entries);
+        }
+
+        id_scratch
+    }
+
     /// Inserts a loadable at the specified path and index if its value will change.
     pub(crate) fn insert_loadable(
         &mut self,
@@ -492,6 +625,33 @@ impl SceneBuffer
         }
     }
 
+    /// Removes loadables extracted from a file that is being unloaded.
+    ///
+    /// Returns entities subscribed to scene nodes in the file. Subscriptions are kept so the entities will be
+    /// updated if the file is loaded again.
+    ///
+    /// The file's patches are removed, but scenes they were applied to keep the patched content until they are
+    /// reloaded.
+    pub(crate) fn unload_file(&mut self, file: &CobFile) -> Vec<Entity>
+    {
+        self.loadables
+            .retain(|scene_ref, _| scene_ref.file.file() != Some(file));
+        self.patches.remove(file);
+
+        #[cfg(feature = "hot_reload")]
+        {
+            self.subscriptions
+                .iter()
+                .filter(|(scene_ref, _)| scene_ref.file.file() == Some(file))
+                .flat_map(|(_, subscriptions)| subscriptions.iter().map(|s| s.entity))
+                .collect()
+        }
+        #[cfg(not(feature = "hot_reload"))]
+        {
+            vec![]
+        }
+    }
+
     /// Cleans up despawned entities.
     #[cfg(feature = "hot_reload")]
     pub(super) fn remove_entity(&mut self, scene_loader: &mut SceneLoader, dead_entity: Entity)
@@ -2,15 +2,13 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use smallvec::SmallVec;
 use smol_str::SmolStr;
 
 use super::*;
 use crate::loading::{
-    CobFile, CobFill, CobLoadableIdentifier, CobSceneLayer, CobSceneLayerEntry, CobSceneMacroCall,
-    CobSceneMacroCommandType, CobSceneMacroValue, CobSceneNodeName,
+    CobConstantValue, CobFile, CobFill, CobLoadableIdentifier, CobSceneLayer, CobSceneLayerEntry,
+    CobSceneMacroCall, CobSceneMacroCommandType, CobSceneMacroParams, CobSceneMacroValue, CobSceneNodeName,
 };
-use crate::prelude::CobImportAlias;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -25,7 +23,8 @@ fn set_canonical_id(canonical: &str, id: &mut CobLoadableIdentifier)
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn canonicalize_loadable_names(mut id_scratch: String, entries: &mut Vec<CobSceneLayerEntry>) -> String
+pub(super) fn canonicalize_loadable_names(mut id_scratch: String, entries: &mut Vec<CobSceneLayerEntry>)
+    -> String
 {
     for entry in entries.iter_mut() {
         match entry {
@@ -45,7 +44,39 @@ fn canonicalize_loadable_names(mut id_scratch: String, entries: &mut Vec<CobScen
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn expand_macro_recursive(
+/// Replaces parameter placeholders in macro content with argument values.
+///
+/// Macro content is fully resolved when the macro is defined, so macro calls and `#for` loops in the content are
+/// already expanded. Parameters passed to nested macro calls and used inside loops end up in the expanded
+/// loadables. Scene references (e.g. `=> widgets.card::card`) and scene macro commands don't contain values.
+fn substitute_params(entries: &mut [CobSceneLayerEntry], args: &CobLoadableResolver) -> Result<(), String>
+{
+    for entry in entries.iter_mut() {
+        match entry {
+            CobSceneLayerEntry::Loadable(loadable) => {
+                loadable.resolve(args)?;
+            }
+            CobSceneLayerEntry::Layer(layer) => {
+                substitute_params(&mut layer.entries, args)?;
+            }
+            CobSceneLayerEntry::SceneMacroCommand(_) => (),
+            CobSceneLayerEntry::LoadableMacroCall(_)
+            | CobSceneLayerEntry::SceneMacroCall(_)
+            | CobSceneLayerEntry::For(_)
+            | CobSceneLayerEntry::Condition(_) => {
+                return Err(String::from(
+                    "failed substituting scene macro parameters; macro content is unexpectedly unresolved",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) fn expand_macro_recursive(
     mut id_scratch: String,
     result_entries: &mut Vec<CobSceneLayerEntry>,
     call_entries: &[CobSceneLayerEntry],
@@ -79,15 +110,21 @@ fn expand_macro_recursive(
                 }
             }
             CobSceneLayerEntry::SceneMacroCommand(command) => {
-                // Find the targeted loadable.
-                id_scratch = command.id.to_canonical(Some(id_scratch));
-
-                let Some(pos) = result_entries.iter().position(|layer| {
-                    let CobSceneLayerEntry::Loadable(loadable) = layer else { return false };
-                    loadable.id.name == id_scratch
-                }) else {
-                    continue;
+                // Find the targeted loadable or scene node.
+                let target = match &command.node {
+                    Some(node) => result_entries.iter().position(|layer| {
+                        let CobSceneLayerEntry::Layer(layer) = layer else { return false };
+                        layer.name == *node
+                    }),
+                    None => {
+                        id_scratch = command.id.to_canonical(Some(id_scratch));
+                        result_entries.iter().position(|layer| {
+                            let CobSceneLayerEntry::Loadable(loadable) = layer else { return false };
+                            loadable.id.name == id_scratch
+                        })
+                    }
                 };
+                let Some(pos) = target else { continue };
 
                 // Apply the command.
                 let removed = result_entries.remove(pos);
@@ -137,6 +174,8 @@ fn expand_macro_recursive(
                 let new_layer = CobSceneLayer {
                     name_fill: CobFill::default(),
                     name: CobSceneNodeName(SmolStr::from(layer_id)),
+                    location: layer.location.clone(),
+                    instance_ref: None,
                     entries: vec![],
                 };
                 result_entries.push(CobSceneLayerEntry::Layer(new_layer));
@@ -147,6 +186,9 @@ fn expand_macro_recursive(
         // Recurse into the layer.
         let result_idx = prev.expect("index was just set");
         let CobSceneLayerEntry::Layer(result_layer) = &mut result_entries[result_idx] else { unreachable!() };
+        if layer.instance_ref.is_some() {
+            result_layer.instance_ref = layer.instance_ref.clone();
+        }
         id_scratch = expand_macro_recursive(id_scratch, &mut result_layer.entries, &layer.entries);
     }
 
@@ -155,8 +197,37 @@ fn expand_macro_recursive(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// [ identifier : macro value ]
-type SceneMacrosMap = HashMap<SmolStr, CobSceneMacroValue>;
+/// Applies a patch's scene items to a scene node's items. Loadable names in `result_entries` must be canonical.
+///
+/// Child nodes in the patch are only added or rearranged here. Their contents are applied when the child nodes
+/// are patched.
+pub(super) fn apply_scene_patch(
+    id_scratch: String,
+    result_entries: &mut Vec<CobSceneLayerEntry>,
+    patch_entries: &[CobSceneLayerEntry],
+) -> String
+{
+    let shallow_entries: Vec<CobSceneLayerEntry> = patch_entries
+        .iter()
+        .map(|entry| match entry {
+            CobSceneLayerEntry::Layer(layer) => CobSceneLayerEntry::Layer(CobSceneLayer {
+                name_fill: CobFill::default(),
+                name: layer.name.clone(),
+                location: layer.location.clone(),
+                instance_ref: layer.instance_ref.clone(),
+                entries: vec![],
+            }),
+            entry => entry.clone(),
+        })
+        .collect();
+
+    expand_macro_recursive(id_scratch, result_entries, &shallow_entries)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// [ identifier : (macro params, macro value) ]
+type SceneMacrosMap = HashMap<SmolStr, (CobSceneMacroParams, CobSceneMacroValue)>;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -166,7 +237,7 @@ type SceneMacrosMap = HashMap<SmolStr, CobSceneMacroValue>;
 #[derive(Default, Debug)]
 pub struct SceneMacrosResolver
 {
-    stack: SmallVec<[(SmolStr, Arc<SceneMacrosMap>); 5]>,
+    stack: DefsStack<(CobSceneMacroParams, CobSceneMacroValue)>,
     new_file: SceneMacrosMap,
     id_scratch: String,
 }
@@ -181,48 +252,97 @@ impl SceneMacrosResolver
     pub(crate) fn end_new_file(&mut self)
     {
         let map = std::mem::take(&mut self.new_file);
-        self.stack.push((SmolStr::default(), Arc::new(map)));
+        self.stack.push_file(map);
     }
 
     /// Adds an entry to the new file being collected.
-    pub(crate) fn insert(&mut self, file: &CobFile, name: SmolStr, mut value: CobSceneMacroValue)
+    ///
+    /// The value should already be resolved, except for references to the macro's own parameters. Parameter
+    /// defaults should be fully resolved.
+    pub(crate) fn insert(
+        &mut self,
+        file: &CobFile,
+        name: SmolStr,
+        params: CobSceneMacroParams,
+        mut value: CobSceneMacroValue,
+    )
     {
         // Canonicalize all loadable names in the macro value.
         self.id_scratch = canonicalize_loadable_names(std::mem::take(&mut self.id_scratch), &mut value.entries);
 
+        if self.stack.get(name.as_str()).is_some() {
+            record_def_collision(
+                file,
+                format!("scene macro +{} is also imported; local definitions can't shadow imported definitions",
+                    name.as_str()),
+            );
+        }
+
         match self.new_file.entry(name) {
             Entry::Vacant(vacant) => {
-                vacant.insert(value);
+                vacant.insert((params, value));
             }
             Entry::Occupied(mut occupied) => {
                 tracing::warn!("overwriting scene macro definition +{} in {:?}", occupied.key().as_str(), file);
-                occupied.insert(value);
+                occupied.insert((params, value));
             }
         }
     }
 
     /// Searches backward through the stack until a match is found.
-    pub fn get(&self, path: impl AsRef<str>) -> Option<&CobSceneMacroValue>
+    pub fn get(&self, path: impl AsRef<str>) -> Option<(&CobSceneMacroParams, &CobSceneMacroValue)>
     {
         let path = path.as_ref();
-        self.new_file.get(path).or_else(|| {
-            self.stack.iter().rev().find_map(|(prefix, m)| {
-                let stripped = path.strip_prefix(prefix.as_str())?;
-                let cleaned = stripped.strip_prefix(DEFS_SEPARATOR).unwrap_or(stripped);
-                m.get(cleaned)
-            })
-        })
+        self.new_file
+            .get(path)
+            .or_else(|| self.stack.get(path))
+            .map(|(params, value)| (params, value))
     }
 
     /// Expands a scene macro invocation into scene layer entries.
+    ///
+    /// The invocation's arguments should already be resolved. Parameters without a matching argument use their
+    /// default values.
     pub fn expand(&mut self, call: &CobSceneMacroCall) -> Result<Vec<CobSceneLayerEntry>, String>
     {
         let path = call.path.as_str();
-        let mut result_entries = self
+        let (params, value) = self
             .get(path)
-            .ok_or_else(|| format!("no scene macro definition at '{path}'"))?
-            .entries
-            .clone();
+            .ok_or_else(|| format!("no scene macro definition at '{path}'"))?;
+
+        // Collect arguments.
+        let call_args = call
+            .args
+            .as_ref()
+            .map(|a| a.entries.as_slice())
+            .unwrap_or(&[]);
+        if let Some(unknown) = call_args
+            .iter()
+            .find(|arg| !params.iter().any(|param| param == arg.as_str()))
+        {
+            return Err(format!("scene macro +{path} has no parameter {:?}", unknown.as_str()));
+        }
+
+        let mut args = Vec::with_capacity(params.len());
+        for param in params.entries.iter() {
+            let value = match call_args.iter().find(|arg| arg.as_str() == param.as_str()) {
+                Some(arg) => arg.value.clone(),
+                None => param
+                    .default
+                    .clone()
+                    .ok_or_else(|| format!("scene macro +{path} is missing argument {:?}", param.as_str()))?,
+            };
+            args.push((param.name.clone(), CobConstantValue::Value(value)));
+        }
+
+        // Substitute arguments for parameters. Since the macro value is already resolved, the only remaining
+        // constants are parameter placeholders.
+        let mut result_entries = value.entries.clone();
+        if !args.is_empty() {
+            let mut args_resolver = CobLoadableResolver::default();
+            args_resolver.constants.set_params(args);
+            substitute_params(&mut result_entries, &args_resolver)?;
+        }
 
         self.id_scratch = expand_macro_recursive(
             std::mem::take(&mut self.id_scratch),
@@ -233,28 +353,17 @@ impl SceneMacrosResolver
         Ok(result_entries)
     }
 
-    pub(crate) fn append(&mut self, alias: &CobImportAlias, to_append: &Self)
+    pub(crate) fn append(&mut self, file: &CobFile, alias: &str, filters: &[Arc<[SmolStr]>], to_append: &Self)
     {
-        let alias = alias.as_str();
-
-        // Remove duplicate maps in self.
-        for (to_append_prefix, to_append) in to_append.stack.iter() {
-            let new_to_append_prefix = path_to_string(DEFS_SEPARATOR, &[alias, &*to_append_prefix]);
-            let Some(existing) = self.stack.iter().position(|(prefix, m)| {
-                *prefix == new_to_append_prefix && Arc::as_ptr(m) == Arc::as_ptr(to_append)
-            }) else {
-                continue;
-            };
-            self.stack.remove(existing);
+        for path in self.stack.append(alias, filters, &to_append.stack) {
+            record_def_collision(file, format!("scene macro +{path} is provided by more than one import"));
         }
+    }
 
-        // Append.
-        self.stack.reserve(to_append.stack.len());
-        self.stack
-            .extend(to_append.stack.iter().map(|(old_prefix, map)| {
-                let new_prefix = path_to_string(DEFS_SEPARATOR, &[alias, &*old_prefix]);
-                (new_prefix, map.clone())
-            }));
+    /// Checks if `name` selects any def in the stack that is also selected by `exports`.
+    pub(crate) fn selects_any(&self, name: &str, exports: Option<&[SmolStr]>) -> bool
+    {
+        self.stack.selects_any(name, exports)
     }
 }
 
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn unload_cob_file(
+    In(manifest_key): In<ManifestKey>,
+    mut c: Commands,
+    mut cob_files: ResMut<LoadedCobAssetFiles>,
+    mut cob_cache: ResMut<CobAssetCache>,
+    commands_buffer: Option<ResMut<CommandsBuffer>>,
+    mut scene_buffer: ResMut<SceneBuffer>,
+    mut scene_loader: ResMut<SceneLoader>,
+)
+{
+    let file = match cob_cache.unload_file(&manifest_key) {
+        Ok(file) => file,
+        Err(err) => {
+            tracing::warn!("failed unloading COB file {:?}; {}", manifest_key.as_str(), err);
+            return;
+        }
+    };
+
+    if let Some(mut commands_buffer) = commands_buffer {
+        commands_buffer.unload_file(&file);
+    }
+
+    let mut live_entities = scene_loader.unload_file(&file);
+    live_entities.extend(scene_buffer.unload_file(&file));
+    live_entities.sort_unstable();
+    live_entities.dedup();
+
+    cob_files.release_handle(&file);
+
+    if live_entities.is_empty() {
+        tracing::info!("unloaded COB file {:?}", file.as_str());
+    } else {
+        tracing::warn!("unloaded COB file {:?} while {} entities are still bound to its scenes: {:?}",
+            file.as_str(), live_entities.len(), live_entities);
+    }
+
+    c.react().broadcast(CobFileUnloaded { file, live_entities });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for unloading a COB file that was registered in a manifest.
+///
+/// Removes the file's scenes, cached commands, and asset handle (or contents, for files loaded with
+/// [`load_cob_from_str`](LoadedCobAssetFilesAppExt::load_cob_from_str)). Scenes that were spawned from the file
+/// are not despawned, they are reported in [`CobFileUnloaded`] instead. Files that imported defs from the unloaded
+/// file keep the defs they already imported, and commands that were already applied are not reverted.
+///
+/// Lazy files can be loaded again by requesting one of their scenes (see [`SceneLoader::request_scene`]). Files
+/// that are still loading can't be unloaded.
+#[derive(Debug, Clone)]
+pub struct UnloadCobFile(pub ManifestKey);
+
+impl UnloadCobFile
+{
+    pub fn new(manifest_key: impl AsRef<str>) -> Self
+    {
+        Self(ManifestKey::new(manifest_key))
+    }
+}
+
+impl Command for UnloadCobFile
+{
+    fn apply(self, world: &mut World)
+    {
+        world.syscall(self.0, unload_cob_file);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reactive event broadcasted when a COB file is unloaded with [`UnloadCobFile`].
+#[derive(Debug, Clone)]
+pub struct CobFileUnloaded
+{
+    pub file: CobFile,
+    /// Root entities of scene instances that were spawned from the file and are still alive.
+    ///
+    /// With the `hot_reload` feature, live scene nodes that have loadables from the file are included too.
+    pub live_entities: Vec<Entity>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -40,9 +40,14 @@ pub enum CobSection
 {
     Manifest(CobManifest),
     Import(CobImport),
+    Export(CobExport),
+    Assets(CobAssets),
+    /// A condition that applies to the next `#defs`, `#commands`, or `#scenes` section.
+    Condition(CobCondition),
     Defs(CobDefs),
     Commands(CobCommands),
     Scenes(CobScenes),
+    Patches(CobPatches),
 }
 
 impl CobSection
@@ -52,15 +57,40 @@ impl CobSection
         match self {
             Self::Manifest(section) => section.write_to(first_section, writer),
             Self::Import(section) => section.write_to(first_section, writer),
+            Self::Export(section) => section.write_to(first_section, writer),
+            Self::Assets(section) => section.write_to(first_section, writer),
+            Self::Condition(condition) => {
+                let space = if first_section { "" } else { "\n\n" };
+                condition.write_to_with_space(writer, space)
+            }
             Self::Defs(section) => section.write_to(first_section, writer),
             Self::Commands(section) => section.write_to(first_section, writer),
             Self::Scenes(section) => section.write_to(first_section, writer),
+            Self::Patches(section) => section.write_to(first_section, writer),
         }
     }
 
     /// Tries to parse a section from the available content.
     pub fn try_parse(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
     {
+        let fill = match rc(content, move |c| CobCondition::try_parse(fill, c))? {
+            (Some(condition), fill, remaining) => {
+                let target = CobCondition::skip_conditions(remaining)?;
+                if !["#defs", "#commands", "#scenes"]
+                    .iter()
+                    .any(|k| target.fragment().starts_with(k))
+                {
+                    return Err(span_diagnostic_error(
+                        content,
+                        "condition isn't followed by a section or item it can apply to; conditions can be applied to \
+                        #defs, #commands, and #scenes sections and their items, but not to #manifest, #import, \
+                        #export, #assets, #patches, or scene roots",
+                    ));
+                }
+                return Ok((Some(Self::Condition(condition)), fill, remaining));
+            }
+            (None, fill, _) => fill,
+        };
         let fill = match CobManifest::try_parse(fill, content)? {
             (Some(section), fill, remaining) => return Ok((Some(Self::Manifest(section)), fill, remaining)),
             (None, fill, _) => fill,
@@ -69,6 +99,14 @@ impl CobSection
             (Some(section), fill, remaining) => return Ok((Some(Self::Import(section)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match CobExport::try_parse(fill, content)? {
+            (Some(section), fill, remaining) => return Ok((Some(Self::Export(section)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobAssets::try_parse(fill, c))? {
+            (Some(section), fill, remaining) => return Ok((Some(Self::Assets(section)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
         let fill = match rc(content, move |c| CobDefs::try_parse(fill, c))? {
             (Some(section), fill, remaining) => return Ok((Some(Self::Defs(section)), fill, remaining)),
             (None, fill, _) => fill,
@@ -81,6 +119,10 @@ impl CobSection
             (Some(section), fill, remaining) => return Ok((Some(Self::Scenes(section)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobPatches::try_parse(fill, c))? {
+            (Some(section), fill, remaining) => return Ok((Some(Self::Patches(section)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
 
         Ok((None, fill, content))
     }
@@ -116,12 +158,11 @@ impl Cob
         Ok(())
     }
 
+    /// Parses a COB file, stopping at the first error.
     pub fn parse(span: Span) -> Result<Self, SpanError>
     {
         let Some(file) = CobFile::try_new(span.extra.file) else {
-            tracing::warn!("failed parsing COB file at {}; file name doesn't end with '.cob'",
-                get_location(span).as_str());
-            return Err(span_verify_error(span));
+            return Err(span_diagnostic_error(span, "COB file name doesn't end with '.cob'"));
         };
 
         debug_assert_eq!(get_local_recursion_count(), 0);
@@ -130,20 +171,33 @@ impl Cob
         let (mut fill, mut remaining) = CobFill::parse(span);
 
         let end_fill = loop {
-            match rc(remaining, move |rm| CobSection::try_parse(fill, rm))? {
-                (Some(section), next_fill, after_section) => {
+            let result =
+                rc(remaining, move |rm| CobSection::try_parse(fill, rm)).and_then(|result| match result {
+                    (None, _, end_of_file) if end_of_file.len() != 0 => Err(span_diagnostic_error(
+                        end_of_file,
+                        "unexpected content; expected a section (e.g. #scenes) or an item in the current section",
+                    )),
+                    result => Ok(result),
+                });
+
+            match result {
+                Ok((Some(section), next_fill, after_section)) => {
                     sections.push(section);
                     fill = next_fill;
                     remaining = after_section;
                 }
-                (None, end_fill, end_of_file) => {
-                    if end_of_file.len() != 0 {
-                        tracing::warn!("incomplete COB file parsing, error at {}", get_location(end_of_file).as_str());
-                        return Err(span_verify_error(end_of_file));
-                    }
-
+                Ok((None, end_fill, _)) => {
                     break end_fill;
                 }
+                Err(err) => {
+                    // Resync at the next section.
+                    let error_at = try_recover(err)?;
+                    let next_section = skip_to_line(remaining, error_at, |indent, line| {
+                        indent == 0
+                            && (starts_with_section_keyword(line) || CobCondition::starts_with_condition(line))
+                    });
+                    (fill, remaining) = CobFill::parse(next_section);
+                }
             }
         };
 
@@ -152,6 +206,18 @@ impl Cob
         Ok(Self { file, sections, end_fill })
     }
 
+    /// Parses a COB file, recovering from errors where possible.
+    ///
+    /// The parser resyncs at section headers (e.g. `#scenes`) and at scene node boundaries. Content that fails to
+    /// parse is skipped, and a diagnostic is returned for each error that was recovered from.
+    ///
+    /// Returns an error if parsing fails in a way that can't be recovered from (e.g. an invalid file name).
+    pub fn parse_with_recovery(span: Span) -> Result<(Self, Vec<CobDiagnostic>), SpanError>
+    {
+        let (result, diagnostics) = with_error_recovery(|| Self::parse(span));
+        result.map(|cob| (cob, diagnostics))
+    }
+
     // TODO: This allocates a string to do loadable name checks.
     pub fn get_command_loadable_mut(&mut self, target_name: &str) -> Option<&mut CobLoadable>
     {
@@ -160,7 +226,7 @@ impl Cob
             match s {
                 CobSection::Commands(commands) => {
                     for entry in commands.entries.iter_mut() {
-                        let CobCommandEntry(loadable) = entry;
+                        let CobCommandEntry::Loadable(loadable) = entry else { continue };
                         id_scratch = loadable.id.to_canonical(Some(id_scratch));
                         if id_scratch != target_name {
                             continue;
@@ -0,0 +1,716 @@
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Token in a fill sequence that the formatter cares about.
+enum FillToken<'a>
+{
+    Newline,
+    /// Line or block comment. Line comments exclude the terminating newline and trailing whitespace.
+    Comment(&'a str),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extracts newlines and comments from a fill sequence. Spaces, carriage returns, and ignored characters are
+/// discarded.
+fn fill_tokens(fill: &str) -> Vec<FillToken<'_>>
+{
+    let mut tokens = vec![];
+    let mut rest = fill;
+
+    while !rest.is_empty() {
+        if rest.starts_with("//") {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            tokens.push(FillToken::Comment(rest[..end].trim_end()));
+            rest = &rest[end..];
+            continue;
+        }
+        if rest.starts_with("/*") {
+            let end = rest.find("*/").map(|e| e + 2).unwrap_or(rest.len());
+            tokens.push(FillToken::Comment(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let Some(c) = rest.chars().next() else { break };
+        rest = &rest[c.len_utf8()..];
+        if c == '\n' {
+            tokens.push(FillToken::Newline);
+        }
+    }
+
+    tokens
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn push_line_break(string: &mut String, num_newlines: usize, indent: usize)
+{
+    string.extend(std::iter::repeat('\n').take(num_newlines));
+    string.extend(std::iter::repeat(' ').take(indent));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Where a fill that ends in a line break appears.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum LineStart
+{
+    /// Start of the file.
+    FileStart,
+    /// Before a section header.
+    Section,
+    /// Before an item.
+    Item,
+    /// Before a section or item that a condition is attached to.
+    Attached,
+    /// Before a closing delimiter, or at the end of the file.
+    Close,
+}
+
+impl LineStart
+{
+    fn num_newlines(self, first_break: bool, last_break: bool, original: usize) -> usize
+    {
+        match self {
+            Self::FileStart if first_break => 0,
+            Self::Section if first_break => 2,
+            Self::Attached if first_break => 1,
+            Self::Close if last_break => 1,
+            _ => original.clamp(1, 2),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the line start for an item, which may be attached to a preceding condition.
+fn item_start(attached: bool) -> LineStart
+{
+    if attached {
+        LineStart::Attached
+    } else {
+        LineStart::Item
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Formats [`Cob`] data in a consistent style.
+///
+/// The formatter rewrites the fill sequences in a `Cob` value. Syntax is never changed.
+/// - Sections are separated by one blank line. Up to one blank line is preserved between items.
+/// - Each definition, command, loadable, and scene node starts on its own line, indented by
+///   [`indent_width`](Self::indent_width) per scene layer.
+/// - Values (and operators in `calc(...)` expressions) on the same line are separated by one space. Line breaks
+///   inside values are preserved, with indentation normalized.
+/// - Conditions (e.g. `#[if(wasm)]`) are placed on the line directly before the section or item they apply to.
+/// - Comments are preserved. Commas and semicolons are removed.
+/// - Fills between tokens that are normally placed on one line (e.g. macro parameters, or before `=` in a
+///   definition) are left unchanged if they contain `//` line comments.
+///
+/// Example:
+/// ```rust
+/// let formatted = CobFormatter::new().indent_width(2).format_to_string(&cob);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CobFormatter
+{
+    indent_width: usize,
+    map_colon_space: bool,
+    map_brace_padding: bool,
+    sort_imports: bool,
+}
+
+impl CobFormatter
+{
+    /// Makes a new formatter with default options.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Sets the number of spaces per indentation level.
+    ///
+    /// Defaults to 4.
+    pub fn indent_width(mut self, width: usize) -> Self
+    {
+        self.indent_width = width.max(1);
+        self
+    }
+
+    /// Controls whether map values are separated from their keys by a space (`key: value`).
+    ///
+    /// Off by default (`key:value`).
+    pub fn map_colon_space(mut self, space: bool) -> Self
+    {
+        self.map_colon_space = space;
+        self
+    }
+
+    /// Controls whether non-empty maps have spaces inside their braces (`{ key:value }`).
+    ///
+    /// Off by default (`{key:value}`).
+    pub fn map_brace_padding(mut self, padding: bool) -> Self
+    {
+        self.map_brace_padding = padding;
+        self
+    }
+
+    /// Controls whether `#import` entries are sorted by manifest key.
+    ///
+    /// On by default. Comments before an entry move with the entry.
+    pub fn sort_imports(mut self, sort: bool) -> Self
+    {
+        self.sort_imports = sort;
+        self
+    }
+
+    /// Formats a `Cob` value in-place.
+    pub fn format(&self, cob: &mut Cob)
+    {
+        let mut attached = false;
+        for (idx, section) in cob.sections.iter_mut().enumerate() {
+            let start = if idx == 0 {
+                LineStart::FileStart
+            } else if attached {
+                LineStart::Attached
+            } else {
+                LineStart::Section
+            };
+            attached = matches!(section, CobSection::Condition(_));
+            self.format_section(section, start);
+        }
+
+        let end = if cob.sections.is_empty() {
+            LineStart::FileStart
+        } else {
+            LineStart::Close
+        };
+        cob.end_fill = self.line_fill(&cob.end_fill, 0, end);
+    }
+
+    /// Formats a copy of a `Cob` value and serializes it to a string.
+    pub fn format_to_string(&self, cob: &Cob) -> String
+    {
+        let mut cob = cob.clone();
+        self.format(&mut cob);
+
+        let mut bytes = Vec::default();
+        cob.write_to(&mut DefaultRawSerializer::new(&mut bytes))
+            .expect("writing to a byte vector should not fail");
+        String::from_utf8(bytes).expect("COB serialization should produce valid UTF-8")
+    }
+
+    /// Makes a fill that ends in a line break followed by `indent` spaces.
+    ///
+    /// Comments are placed on their own lines at the same indentation, except for comments at the end of the
+    /// previous line.
+    fn line_fill(&self, fill: &CobFill, indent: usize, start: LineStart) -> CobFill
+    {
+        let mut string = String::default();
+        let mut num_newlines = 0;
+        let mut first_break = true;
+
+        for token in fill_tokens(fill.string.as_str()) {
+            match token {
+                FillToken::Newline => num_newlines += 1,
+                FillToken::Comment(comment) => {
+                    if string.is_empty() && num_newlines == 0 && start != LineStart::FileStart {
+                        // The comment is at the end of the previous line.
+                        string.push(' ');
+                    } else {
+                        push_line_break(
+                            &mut string,
+                            start.num_newlines(first_break, false, num_newlines),
+                            indent,
+                        );
+                        first_break = false;
+                    }
+                    string.push_str(comment);
+                    num_newlines = 0;
+                }
+            }
+        }
+
+        push_line_break(&mut string, start.num_newlines(first_break, true, num_newlines), indent);
+        CobFill::new(string)
+    }
+
+    /// Makes a fill that doesn't contain line breaks.
+    ///
+    /// Uses `space` if there are no comments. Closing fills put comments after the preceding item.
+    ///
+    /// Fills with line comments are returned unchanged, since the line break that ends a line comment can't be
+    /// removed.
+    fn inline_fill(&self, fill: &CobFill, space: &str, closing: bool) -> CobFill
+    {
+        let comments: Vec<&str> = fill_tokens(fill.string.as_str())
+            .into_iter()
+            .filter_map(|t| match t {
+                FillToken::Comment(comment) => Some(comment),
+                FillToken::Newline => None,
+            })
+            .collect();
+        if comments.is_empty() {
+            return CobFill::new(space);
+        }
+        if comments.iter().any(|c| c.starts_with("//")) {
+            return fill.clone();
+        }
+
+        let comments = comments.join(" ");
+        match closing {
+            true => CobFill::new(format!(" {comments}{space}")),
+            false => CobFill::new(format!("{space}{comments} ")),
+        }
+    }
+
+    /// Formats the fill before an item inside a value.
+    ///
+    /// If the original fill has a line break then the item is placed on a new line at the `line_break` indent.
+    /// Returns the indent of the line the item is on.
+    fn leading_fill(&self, fill: &mut CobFill, line: usize, line_break: usize, space: &str) -> usize
+    {
+        if fill.string.contains('\n') {
+            *fill = self.line_fill(fill, line_break, LineStart::Item);
+            line_break
+        } else {
+            *fill = self.inline_fill(fill, space, false);
+            line
+        }
+    }
+
+    /// Formats the fill before a closing delimiter.
+    ///
+    /// If the original fill has a line break then the delimiter is placed on a new line at the `line` indent.
+    fn closing_fill(&self, fill: &mut CobFill, line: usize, space: &str)
+    {
+        if fill.string.contains('\n') {
+            *fill = self.line_fill(fill, line, LineStart::Close);
+        } else {
+            *fill = self.inline_fill(fill, space, true);
+        }
+    }
+
+    fn format_section(&self, section: &mut CobSection, start: LineStart)
+    {
+        match section {
+            CobSection::Manifest(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                for entry in section.entries.iter_mut() {
+                    entry.entry_fill = self.line_fill(&entry.entry_fill, 0, LineStart::Item);
+                    if let Some(lazy_fill) = &mut entry.lazy_fill {
+                        *lazy_fill = self.inline_fill(lazy_fill, " ", false);
+                    }
+                    entry.as_fill = self.inline_fill(&entry.as_fill, " ", true);
+                    entry.key_fill = self.inline_fill(&entry.key_fill, " ", false);
+                }
+            }
+            CobSection::Import(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                if self.sort_imports {
+                    section
+                        .entries
+                        .sort_by(|a, b| a.key.as_str().cmp(b.key.as_str()));
+                }
+                for entry in section.entries.iter_mut() {
+                    entry.entry_fill = self.line_fill(&entry.entry_fill, 0, LineStart::Item);
+                    if let Some(selection) = &mut entry.selection {
+                        for (idx, name) in selection.names.iter_mut().enumerate() {
+                            let space = if idx == 0 { "" } else { " " };
+                            name.name_fill = self.inline_fill(&name.name_fill, space, false);
+                        }
+                        selection.end_fill = self.inline_fill(&selection.end_fill, "", true);
+                    }
+                    entry.as_fill = self.inline_fill(&entry.as_fill, " ", true);
+                    entry.alias_fill = self.inline_fill(&entry.alias_fill, " ", false);
+                }
+            }
+            CobSection::Export(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                for entry in section.entries.iter_mut() {
+                    entry.entry_fill = self.line_fill(&entry.entry_fill, 0, LineStart::Item);
+                }
+            }
+            CobSection::Assets(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                for entry in section.entries.iter_mut() {
+                    entry.entry_fill = self.line_fill(&entry.entry_fill, 0, LineStart::Item);
+                    self.format_value(&mut entry.value, 0, self.indent_width, " ");
+                }
+            }
+            CobSection::Condition(condition) => {
+                self.format_condition(condition, 0, start);
+            }
+            CobSection::Defs(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                let mut attached = false;
+                for entry in section.entries.iter_mut() {
+                    self.format_def(entry, item_start(attached));
+                    attached = matches!(entry, CobDefEntry::Condition(_));
+                }
+            }
+            CobSection::Commands(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                let mut attached = false;
+                for entry in section.entries.iter_mut() {
+                    match entry {
+                        CobCommandEntry::Loadable(loadable) => {
+                            self.format_loadable(loadable, 0, item_start(attached));
+                        }
+                        CobCommandEntry::Condition(condition) => {
+                            self.format_condition(condition, 0, item_start(attached));
+                        }
+                    }
+                    attached = matches!(entry, CobCommandEntry::Condition(_));
+                }
+            }
+            CobSection::Scenes(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                for layer in section.scenes.iter_mut() {
+                    self.format_scene_layer(layer, 0, LineStart::Item);
+                }
+            }
+            CobSection::Patches(section) => {
+                section.start_fill = self.line_fill(&section.start_fill, 0, start);
+                for patch in section.patches.iter_mut() {
+                    patch.start_fill = self.line_fill(&patch.start_fill, 0, LineStart::Item);
+                    self.format_scene_entries(&mut patch.entries, self.indent_width);
+                }
+            }
+        }
+    }
+
+    /// Formats a condition that starts on its own line at `indent`.
+    fn format_condition(&self, condition: &mut CobCondition, indent: usize, start: LineStart)
+    {
+        condition.start_fill = self.line_fill(&condition.start_fill, indent, start);
+        self.format_condition_expr(&mut condition.expr, "");
+        condition.end_fill = self.inline_fill(&condition.end_fill, "", true);
+    }
+
+    fn format_condition_expr(&self, expr: &mut CobConditionExpr, space: &str)
+    {
+        match expr {
+            CobConditionExpr::Flag { fill, .. } => {
+                *fill = self.inline_fill(fill, space, false);
+            }
+            CobConditionExpr::Op { fill, args, end_fill, .. } => {
+                *fill = self.inline_fill(fill, space, false);
+                for (idx, arg) in args.iter_mut().enumerate() {
+                    let space = if idx == 0 { "" } else { " " };
+                    self.format_condition_expr(arg, space);
+                }
+                *end_fill = self.inline_fill(end_fill, "", true);
+            }
+        }
+    }
+
+    fn format_def(&self, entry: &mut CobDefEntry, start: LineStart)
+    {
+        let width = self.indent_width;
+
+        match entry {
+            CobDefEntry::Constant(def) => {
+                def.start_fill = self.line_fill(&def.start_fill, 0, start);
+                def.pre_eq_fill = self.inline_fill(&def.pre_eq_fill, " ", true);
+                match &mut def.value {
+                    CobConstantValue::Value(value) => {
+                        self.format_value(value, 0, width, " ");
+                    }
+                    CobConstantValue::ValueGroup(group) => {
+                        group.start_fill = self.inline_fill(&group.start_fill, " ", false);
+                        let mut line = 0;
+                        for entry in group.entries.iter_mut() {
+                            line = match entry {
+                                CobValueGroupEntry::KeyValue(kv) => self.format_key_value(kv, line, width, " "),
+                                CobValueGroupEntry::Value(value) => self.format_value(value, line, width, " "),
+                            };
+                        }
+                        self.closing_fill(&mut group.end_fill, 0, " ");
+                    }
+                }
+            }
+            CobDefEntry::DataMacro(def) => {
+                def.start_fill = self.line_fill(&def.start_fill, 0, start);
+                for (idx, param) in def.params.entries.iter_mut().enumerate() {
+                    let space = if idx == 0 { "" } else { " " };
+                    param.fill = self.inline_fill(&param.fill, space, false);
+                }
+                def.params.end_fill = self.inline_fill(&def.params.end_fill, "", true);
+                def.pre_eq_fill = self.inline_fill(&def.pre_eq_fill, " ", true);
+                self.format_value(&mut def.value, 0, width, " ");
+            }
+            CobDefEntry::LoadableMacro(def) => {
+                def.start_fill = self.line_fill(&def.start_fill, 0, start);
+                def.pre_eq_fill = self.inline_fill(&def.pre_eq_fill, " ", true);
+                def.value.start_fill = self.inline_fill(&def.value.start_fill, " ", false);
+                self.format_scene_entries(&mut def.value.entries, width);
+                self.closing_fill(&mut def.value.end_fill, 0, "");
+            }
+            CobDefEntry::SceneMacro(def) => {
+                def.start_fill = self.line_fill(&def.start_fill, 0, start);
+                if let Some(params) = &mut def.params {
+                    for (idx, param) in params.entries.iter_mut().enumerate() {
+                        let space = if idx == 0 { "" } else { " " };
+                        param.fill = self.inline_fill(&param.fill, space, false);
+                        if let Some(default) = &mut param.default {
+                            self.format_value(default, 0, width, "");
+                        }
+                    }
+                    params.end_fill = self.inline_fill(&params.end_fill, "", true);
+                }
+                def.pre_eq_fill = self.inline_fill(&def.pre_eq_fill, " ", true);
+                def.value.start_fill = self.inline_fill(&def.value.start_fill, " ", false);
+                self.format_scene_entries(&mut def.value.entries, width);
+                self.closing_fill(&mut def.value.end_fill, 0, "");
+            }
+            CobDefEntry::Condition(condition) => {
+                self.format_condition(condition, 0, start);
+            }
+        }
+    }
+
+    fn format_scene_layer(&self, layer: &mut CobSceneLayer, indent: usize, start: LineStart)
+    {
+        layer.name_fill = self.line_fill(&layer.name_fill, indent, start);
+        if let Some(instance_ref) = &mut layer.instance_ref {
+            instance_ref.arrow_fill = self.inline_fill(&instance_ref.arrow_fill, " ", false);
+            instance_ref.file_fill = self.inline_fill(&instance_ref.file_fill, " ", false);
+        }
+        self.format_scene_entries(&mut layer.entries, indent + self.indent_width);
+    }
+
+    /// Formats scene items that start on their own lines at `indent`.
+    fn format_scene_entries(&self, entries: &mut [CobSceneLayerEntry], indent: usize)
+    {
+        let mut attached = false;
+        for entry in entries.iter_mut() {
+            let start = item_start(attached);
+            match entry {
+                CobSceneLayerEntry::Loadable(loadable) => {
+                    self.format_loadable(loadable, indent, start);
+                }
+                CobSceneLayerEntry::LoadableMacroCall(call) => {
+                    call.start_fill = self.line_fill(&call.start_fill, indent, start);
+                    self.format_scene_entries(&mut call.container.entries, indent + self.indent_width);
+                    self.closing_fill(&mut call.container.end_fill, indent, "");
+                }
+                CobSceneLayerEntry::SceneMacroCall(call) => {
+                    call.start_fill = self.line_fill(&call.start_fill, indent, start);
+                    if let Some(args) = &mut call.args {
+                        for (idx, arg) in args.entries.iter_mut().enumerate() {
+                            let space = if idx == 0 { "" } else { " " };
+                            arg.fill = self.inline_fill(&arg.fill, space, false);
+                            self.format_value(&mut arg.value, indent, indent + self.indent_width, "");
+                        }
+                        args.end_fill = self.inline_fill(&args.end_fill, "", true);
+                    }
+                    self.format_scene_entries(&mut call.container.entries, indent + self.indent_width);
+                    self.closing_fill(&mut call.container.end_fill, indent, "");
+                }
+                CobSceneLayerEntry::SceneMacroCommand(command) => {
+                    command.start_fill = self.line_fill(&command.start_fill, indent, start);
+                }
+                CobSceneLayerEntry::Layer(layer) => {
+                    self.format_scene_layer(layer, indent, start);
+                }
+                CobSceneLayerEntry::For(for_entry) => {
+                    for_entry.start_fill = self.line_fill(&for_entry.start_fill, indent, start);
+                    for_entry.binding_fill = self.inline_fill(&for_entry.binding_fill, " ", false);
+                    for_entry.in_fill = self.inline_fill(&for_entry.in_fill, " ", false);
+                    self.format_value(&mut for_entry.iterable, indent, indent + self.indent_width, " ");
+                    self.format_scene_entries(&mut for_entry.entries, indent + self.indent_width);
+                }
+                CobSceneLayerEntry::Condition(condition) => {
+                    self.format_condition(condition, indent, start);
+                }
+            }
+            attached = matches!(entry, CobSceneLayerEntry::Condition(_));
+        }
+    }
+
+    /// Formats a loadable that starts on its own line at `indent`.
+    fn format_loadable(&self, loadable: &mut CobLoadable, indent: usize, start: LineStart)
+    {
+        loadable.fill = self.line_fill(&loadable.fill, indent, start);
+        match &mut loadable.variant {
+            CobLoadableVariant::Unit => (),
+            CobLoadableVariant::Tuple(tuple) => self.format_tuple_content(tuple, indent),
+            CobLoadableVariant::Array(array) => self.format_array_content(array, indent),
+            CobLoadableVariant::Map(map) => self.format_map_content(map, indent),
+            CobLoadableVariant::Enum(variant) => {
+                variant.fill = self.inline_fill(&variant.fill, "", false);
+                self.format_enum_variant(&mut variant.variant, indent);
+            }
+        }
+    }
+
+    /// Formats a value whose first line is indented by `line`. If the value should start on a new line, it will be
+    /// indented by `line_break`.
+    ///
+    /// Returns the indent of the line the value ends on.
+    fn format_value(&self, value: &mut CobValue, line: usize, line_break: usize, space: &str) -> usize
+    {
+        match value {
+            CobValue::Enum(val) => {
+                let line = self.leading_fill(&mut val.fill, line, line_break, space);
+                self.format_enum_variant(&mut val.variant, line);
+                line
+            }
+            CobValue::Builtin(CobBuiltin::Color(val)) => self.leading_fill(&mut val.fill, line, line_break, space),
+            CobValue::Builtin(CobBuiltin::ColorLiteral(val)) => {
+                let line = self.leading_fill(&mut val.fill, line, line_break, space);
+                for (idx, component) in val.components.iter_mut().enumerate() {
+                    let space = if idx == 0 { "" } else { " " };
+                    component.fill = self.inline_fill(&component.fill, space, false);
+                }
+                val.end_fill = self.inline_fill(&val.end_fill, "", true);
+                line
+            }
+            CobValue::Builtin(CobBuiltin::NamedColor(val)) => {
+                self.leading_fill(&mut val.fill, line, line_break, space)
+            }
+            CobValue::Builtin(CobBuiltin::Val { fill, .. }) => self.leading_fill(fill, line, line_break, space),
+            CobValue::Array(val) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_array_content(val, line);
+                line
+            }
+            CobValue::Tuple(val) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_tuple_content(val, line);
+                line
+            }
+            CobValue::Map(val) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_map_content(val, line);
+                line
+            }
+            CobValue::Number(val) => self.leading_fill(&mut val.fill, line, line_break, space),
+            CobValue::Bool(val) => self.leading_fill(&mut val.fill, line, line_break, space),
+            CobValue::None(val) => self.leading_fill(&mut val.fill, line, line_break, space),
+            CobValue::String(val) => self.leading_fill(&mut val.fill, line, line_break, space),
+            CobValue::Constant(val) => self.leading_fill(&mut val.start_fill, line, line_break, space),
+            CobValue::DataMacro(val) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_tuple_content(&mut val.args, line);
+                line
+            }
+            CobValue::Expr(CobExpr::Calc(val)) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_calc_entries(&mut val.entries, line);
+                self.closing_fill(&mut val.end_fill, line, "");
+                line
+            }
+            CobValue::Expr(CobExpr::Function(val)) => {
+                let line = self.leading_fill(&mut val.start_fill, line, line_break, space);
+                self.format_tuple_content(&mut val.args, line);
+                line
+            }
+        }
+    }
+
+    fn format_enum_variant(&self, variant: &mut CobEnumVariant, line: usize)
+    {
+        match variant {
+            CobEnumVariant::Unit => (),
+            CobEnumVariant::Tuple(tuple) => self.format_tuple_content(tuple, line),
+            CobEnumVariant::Array(array) => self.format_array_content(array, line),
+            CobEnumVariant::Map(map) => self.format_map_content(map, line),
+        }
+    }
+
+    fn format_value_sequence(&self, values: &mut [CobValue], line: usize)
+    {
+        let inner = line + self.indent_width;
+        let mut current = line;
+        for (idx, value) in values.iter_mut().enumerate() {
+            let space = if idx == 0 { "" } else { " " };
+            current = self.format_value(value, current, inner, space);
+        }
+    }
+
+    fn format_calc_entries(&self, entries: &mut [CobCalcEntry], line: usize)
+    {
+        let inner = line + self.indent_width;
+        let mut current = line;
+        for (idx, entry) in entries.iter_mut().enumerate() {
+            let space = if idx == 0 { "" } else { " " };
+            current = match entry {
+                CobCalcEntry::Operand(value) => self.format_value(value, current, inner, space),
+                CobCalcEntry::Op { fill, .. } => self.leading_fill(fill, current, inner, space),
+                CobCalcEntry::Group { fill, entries, end_fill } => {
+                    let group_line = self.leading_fill(fill, current, inner, space);
+                    self.format_calc_entries(entries, group_line);
+                    self.closing_fill(end_fill, group_line, "");
+                    group_line
+                }
+            };
+        }
+    }
+
+    fn format_tuple_content(&self, tuple: &mut CobTuple, line: usize)
+    {
+        self.format_value_sequence(&mut tuple.entries, line);
+        self.closing_fill(&mut tuple.end_fill, line, "");
+    }
+
+    fn format_array_content(&self, array: &mut CobArray, line: usize)
+    {
+        self.format_value_sequence(&mut array.entries, line);
+        self.closing_fill(&mut array.end_fill, line, "");
+    }
+
+    fn format_map_content(&self, map: &mut CobMap, line: usize)
+    {
+        let padding = if self.map_brace_padding && !map.entries.is_empty() {
+            " "
+        } else {
+            ""
+        };
+        let inner = line + self.indent_width;
+        let mut current = line;
+
+        for (idx, entry) in map.entries.iter_mut().enumerate() {
+            let space = if idx == 0 { padding } else { " " };
+            current = match entry {
+                CobMapEntry::KeyValue(kv) => self.format_key_value(kv, current, inner, space),
+                CobMapEntry::Constant(constant) => {
+                    self.leading_fill(&mut constant.start_fill, current, inner, space)
+                }
+            };
+        }
+
+        self.closing_fill(&mut map.end_fill, line, padding);
+    }
+
+    fn format_key_value(&self, kv: &mut CobMapKeyValue, line: usize, line_break: usize, space: &str) -> usize
+    {
+        let line = match &mut kv.key {
+            CobMapKey::Value(key) => self.format_value(key, line, line_break, space),
+            CobMapKey::FieldName { fill, .. } => self.leading_fill(fill, line, line_break, space),
+        };
+        kv.semicolon_fill = self.inline_fill(&kv.semicolon_fill, "", true);
+
+        let colon_space = if self.map_colon_space { " " } else { "" };
+        self.format_value(&mut kv.value, line, line + self.indent_width, colon_space)
+    }
+}
+
+impl Default for CobFormatter
+{
+    fn default() -> Self
+    {
+        Self {
+            indent_width: 4,
+            map_colon_space: false,
+            map_brace_padding: false,
+            sort_imports: true,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,309 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Operator in a [`CobConditionExpr`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CobConditionOp
+{
+    /// `not(a)`: true if `a` is false.
+    Not,
+    /// `any(a b ..)`: true if any argument is true. `any()` is false.
+    Any,
+    /// `all(a b ..)`: true if all arguments are true. `all()` is true.
+    All,
+}
+
+impl CobConditionOp
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Not => "not",
+            Self::Any => "any",
+            Self::All => "all",
+        }
+    }
+
+    fn from_name(op: &str) -> Option<Self>
+    {
+        match op {
+            "not" => Some(Self::Not),
+            "any" => Some(Self::Any),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Expression in a [`CobCondition`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CobConditionExpr
+{
+    /// A flag in [`CobFlags`], e.g. `wasm`.
+    Flag
+    {
+        fill: CobFill, name: SmolStr
+    },
+    /// An operator applied to other expressions, e.g. `not(wasm)`.
+    Op
+    {
+        fill: CobFill,
+        op: CobConditionOp,
+        args: Vec<CobConditionExpr>,
+        /// Fill before the closing `)`.
+        end_fill: CobFill,
+    },
+}
+
+impl CobConditionExpr
+{
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        match self {
+            Self::Flag { fill, name } => {
+                fill.write_to_or_else(writer, space)?;
+                writer.write_bytes(name.as_bytes())?;
+            }
+            Self::Op { fill, op, args, end_fill } => {
+                fill.write_to_or_else(writer, space)?;
+                writer.write_bytes(op.as_str().as_bytes())?;
+                writer.write_bytes("(".as_bytes())?;
+                for (idx, arg) in args.iter().enumerate() {
+                    let space = if idx == 0 { "" } else { " " };
+                    arg.write_to_with_space(writer, space)?;
+                }
+                end_fill.write_to(writer)?;
+                writer.write_bytes(")".as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn parse(fill: CobFill, content: Span) -> Result<(Self, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, name)) = snake_identifier(content) else {
+            return Err(span_diagnostic_error(
+                content,
+                "expected a condition flag (e.g. wasm) or a condition operator (not, any, all)",
+            ));
+        };
+
+        // Flag
+        let Some(op) = CobConditionOp::from_name(name.fragment()) else {
+            let (post_fill, remaining) = CobFill::parse(remaining);
+            return Ok((
+                Self::Flag { fill, name: SmolStr::from(*name.fragment()) },
+                post_fill,
+                remaining,
+            ));
+        };
+
+        // Operator
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("expected '(' after condition operator '{}'", op.as_str()),
+            ));
+        };
+
+        let (mut arg_fill, mut remaining) = CobFill::parse(remaining);
+        let mut args = vec![];
+        let end_fill = loop {
+            if let Ok((after_close, _)) = char::<_, ()>(')').parse(remaining) {
+                remaining = after_close;
+                break arg_fill;
+            }
+            let (arg, next_fill, after_arg) = rc(remaining, move |rm| Self::parse(arg_fill, rm))?;
+            args.push(arg);
+            arg_fill = next_fill;
+            remaining = after_arg;
+        };
+
+        if op == CobConditionOp::Not && args.len() != 1 {
+            return Err(span_diagnostic_error(
+                content,
+                "condition operator 'not' must have exactly one argument",
+            ));
+        }
+
+        let (post_fill, remaining) = CobFill::parse(remaining);
+        Ok((Self::Op { fill, op, args, end_fill }, post_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        match (self, other) {
+            (Self::Flag { fill, .. }, Self::Flag { fill: other_fill, .. }) => {
+                fill.recover(other_fill);
+            }
+            (
+                Self::Op { fill, args, end_fill, .. },
+                Self::Op {
+                    fill: other_fill, args: other_args, end_fill: other_end_fill, ..
+                },
+            ) => {
+                fill.recover(other_fill);
+                for (arg, other_arg) in args.iter_mut().zip(other_args.iter()) {
+                    arg.recover_fill(other_arg);
+                }
+                end_fill.recover(other_end_fill);
+            }
+            _ => (),
+        }
+    }
+
+    /// Evaluates the expression.
+    ///
+    /// Returns an error if the expression contains a flag that isn't in `flags`.
+    pub fn evaluate(&self, flags: &CobFlags) -> Result<bool, String>
+    {
+        match self {
+            Self::Flag { name, .. } => flags
+                .get(name.as_str())
+                .ok_or_else(|| format!("unknown condition flag {:?}", name.as_str())),
+            Self::Op { op: CobConditionOp::Not, args, .. } => {
+                let Some(arg) = args.first() else { return Ok(true) };
+                Ok(!arg.evaluate(flags)?)
+            }
+            Self::Op { op: CobConditionOp::Any, args, .. } => {
+                for arg in args.iter() {
+                    if arg.evaluate(flags)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Self::Op { op: CobConditionOp::All, args, .. } => {
+                for arg in args.iter() {
+                    if !arg.evaluate(flags)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A condition attribute that includes or excludes the item after it, e.g. `#[if(not(wasm))]`.
+///
+/// Conditions can be placed before `#defs`, `#commands`, and `#scenes` sections, and before items in those
+/// sections (definitions, commands, loadables, macro calls, and scene nodes other than scene roots). Multiple
+/// conditions before the same item must all be true for the item to be included.
+///
+/// Conditions are evaluated against [`CobFlags`] when a file is extracted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobCondition
+{
+    pub start_fill: CobFill,
+    pub expr: CobConditionExpr,
+    /// Fill before the closing `)]`.
+    pub end_fill: CobFill,
+}
+
+impl CobCondition
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "\n")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("#[if(".as_bytes())?;
+        self.expr.write_to_with_space(writer, "")?;
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")]".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("#[if(").parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+
+        if start_fill.len() != 0 && start_fill.ends_newline_then_num_spaces().is_none() {
+            return Err(span_diagnostic_error(content, "condition doesn't start on a new line"));
+        }
+
+        let (expr_fill, remaining) = CobFill::parse(remaining);
+        let (expr, end_fill, remaining) = rc(remaining, move |rm| CobConditionExpr::parse(expr_fill, rm))?;
+
+        let Ok((remaining, _)) = tag::<_, _, ()>(")]").parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected ')]' at the end of the condition",
+            ));
+        };
+
+        let (post_fill, remaining) = CobFill::parse(remaining);
+        if post_fill.ends_newline_then_num_spaces().is_none() && !remaining.fragment().is_empty() {
+            return Err(span_diagnostic_error(
+                remaining,
+                "the item after a condition must start on a new line",
+            ));
+        }
+
+        Ok((Some(Self { start_fill, expr, end_fill }), post_fill, remaining))
+    }
+
+    /// Tries to parse a condition that applies to an item in a section.
+    ///
+    /// Returns `None` if the condition (or a stack of conditions it starts) applies to a section header, so the
+    /// condition can be parsed as a section instead.
+    pub fn try_parse_item(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let (condition, next_fill, remaining) = match Self::try_parse(fill, content)? {
+            (Some(condition), next_fill, remaining) => (condition, next_fill, remaining),
+            (None, fill, _) => return Ok((None, fill, content)),
+        };
+        if starts_with_section_keyword(Self::skip_conditions(remaining)?.fragment()) {
+            return Ok((None, condition.start_fill, content));
+        }
+        Ok((Some(condition), next_fill, remaining))
+    }
+
+    /// Skips past a stack of conditions, returning the content after them.
+    pub(crate) fn skip_conditions(mut content: Span) -> Result<Span, SpanError>
+    {
+        while let (Some(_), _, remaining) = Self::try_parse(CobFill::default(), content)? {
+            content = remaining;
+        }
+        Ok(content)
+    }
+
+    /// Returns `true` if the content starts with a condition.
+    pub(crate) fn starts_with_condition(content: &str) -> bool
+    {
+        content.starts_with("#[if(")
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        self.expr.recover_fill(&other.expr);
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    /// Evaluates the condition.
+    ///
+    /// Returns an error if the condition contains a flag that isn't in `flags`.
+    pub fn evaluate(&self, flags: &CobFlags) -> Result<bool, String>
+    {
+        self.expr.evaluate(flags)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
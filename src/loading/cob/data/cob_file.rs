@@ -48,14 +48,10 @@ impl CobFile
 
         // Validate
         if let Err(err) = AssetPath::try_parse(*path.fragment()) {
-            tracing::warn!("failed parsing COB file path at {}; path is invalid {:?}",
-                get_location(content).as_str(), err);
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, format!("invalid COB file path: {err}")));
         }
         if !path.ends_with(".cob") {
-            tracing::warn!("failed parsing COB file path at {}; file does not end with '.cob' extension",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "COB file path doesn't end with '.cob'"));
         }
 
         Ok((Self(Arc::from(*path.fragment())), remaining))
@@ -17,7 +17,7 @@ fn is_allowed_special_char(c: char) -> bool
 {
     match c {
         '"' | ':' | '#' | '@' | '+' | '-' | '=' | '$' | '?' | '{' | '}' | '(' | ')' | '[' | ']' | '<' | '>'
-        | '.' | '\'' | '\\' | '_' | '^' | '!' => true,
+        | '.' | '\'' | '\\' | '_' | '^' | '!' | '*' | '/' => true,
         _ => false,
     }
 }
@@ -47,7 +47,7 @@ impl CobFill
 {
     pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
     {
-        writer.write_bytes(self.string.as_bytes())?;
+        writer.write_fill(self.string.as_str())?;
         Ok(())
     }
 
@@ -58,7 +58,7 @@ impl CobFill
     ) -> Result<(), std::io::Error>
     {
         if self.string.len() == 0 {
-            writer.write_bytes(fallback.as_ref().as_bytes())?;
+            writer.write_fill(fallback.as_ref())?;
         } else {
             self.write_to(writer)?;
         }
@@ -131,6 +131,72 @@ impl CobFill
         self.string.as_str().ends_with('\n')
     }
 
+    /// Normalizes the whitespace in a fill sequence.
+    ///
+    /// - Line endings are converted to `\n`.
+    /// - Trailing spaces at the end of lines are removed.
+    /// - Runs of blank lines are collapsed to one blank line.
+    /// - Runs of spaces in the middle of a line are collapsed to one space.
+    ///
+    /// Comments, ignored characters, and line indentation are preserved.
+    pub fn normalize_whitespace(fill: &str) -> String
+    {
+        let mut normalized = String::with_capacity(fill.len());
+        let mut rest = fill;
+        let mut num_newlines = 0;
+        let mut indenting = false;
+
+        while !rest.is_empty() {
+            // Line comments: trim trailing whitespace.
+            if rest.starts_with("//") {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                normalized.push_str(rest[..end].trim_end());
+                rest = &rest[end..];
+                num_newlines = 0;
+                indenting = false;
+                continue;
+            }
+
+            // Block comments: preserve exactly.
+            if rest.starts_with("/*") {
+                let end = rest.find("*/").map(|e| e + 2).unwrap_or(rest.len());
+                normalized.push_str(&rest[..end]);
+                rest = &rest[end..];
+                num_newlines = 0;
+                indenting = false;
+                continue;
+            }
+
+            let Some(c) = rest.chars().next() else { break };
+            rest = &rest[c.len_utf8()..];
+
+            match c {
+                '\r' => (),
+                '\n' => {
+                    let trimmed_len = normalized.trim_end_matches(' ').len();
+                    normalized.truncate(trimmed_len);
+                    if num_newlines < 2 {
+                        normalized.push('\n');
+                    }
+                    num_newlines += 1;
+                    indenting = true;
+                }
+                ' ' => {
+                    if indenting || !normalized.ends_with(' ') {
+                        normalized.push(' ');
+                    }
+                }
+                _ => {
+                    normalized.push(c);
+                    num_newlines = 0;
+                    indenting = false;
+                }
+            }
+        }
+
+        normalized
+    }
+
     /// If `self.len() == 0` then clone the other's fill value.
     pub fn recover(&mut self, other: &Self)
     {
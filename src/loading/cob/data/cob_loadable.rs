@@ -14,6 +14,8 @@ pub struct CobLoadableIdentifier
 {
     pub name: SmolStr,
     pub generics: Option<CobGenerics>,
+    /// Location of the identifier in its source file.
+    pub location: CobItemLocation,
 }
 
 impl CobLoadableIdentifier
@@ -45,7 +47,11 @@ impl CobLoadableIdentifier
     {
         let (remaining, id) = camel_identifier(content)?;
         let (generics, remaining) = rc(remaining, |rm| CobGenerics::try_parse(rm))?;
-        Ok((Self { name: SmolStr::from(*id.fragment()), generics }, remaining))
+        let location = CobItemLocation::from_span_with_len(content, id.fragment().len());
+        Ok((
+            Self { name: SmolStr::from(*id.fragment()), generics, location },
+            remaining,
+        ))
     }
 
     pub fn recover_fill(&mut self, other: &Self)
@@ -143,9 +149,10 @@ impl CobLoadableVariant
             match rc(remaining, |rm| CobEnum::try_parse(CobFill::default(), rm))? {
                 (Some(variant), next_fill, remaining) => return Ok((Self::Enum(variant), next_fill, remaining)),
                 _ => {
-                    tracing::warn!("failed parsing loadable enum at {}; no valid variant name",
-                        get_location(content));
-                    return Err(span_verify_error(content));
+                    return Err(span_diagnostic_error(
+                        remaining,
+                        "expected an enum variant name after '::'",
+                    ));
                 }
             }
         }
@@ -12,42 +12,74 @@ where
     V: Visitor<'de>,
 {
     match builtin {
-        CobBuiltin::Color(CobHexColor { color, .. }) => visitor.visit_enum(ColorSrgbaAccess { color: *color }),
+        CobBuiltin::Color(CobHexColor { color, .. }) => visitor.visit_enum(ColorAccess::srgba(*color)),
+        CobBuiltin::ColorLiteral(color) => visitor.visit_enum(ColorAccess::literal(color)),
+        CobBuiltin::NamedColor(CobNamedColor { color, .. }) => visitor.visit_enum(ColorAccess::srgba(*color)),
         CobBuiltin::Val { val, .. } => visitor.visit_enum(ValAccess { val: *val }),
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-// TODO: is there an easier way to do this by leveraging the Serialize implementation of Srgba?
-struct ColorSrgbaAccess
+/// Deserializes a color as a `Color` newtype variant containing a map of the color's fields.
+// TODO: is there an easier way to do this by leveraging the Serialize implementation of Color?
+struct ColorAccess
 {
-    color: Srgba,
+    variant: &'static str,
+    fields: [(&'static str, f32); 4],
 }
 
-impl<'de> EnumAccess<'de> for ColorSrgbaAccess
+impl ColorAccess
+{
+    fn srgba(color: Srgba) -> Self
+    {
+        Self {
+            variant: "Srgba",
+            fields: [("red", color.red), ("green", color.green), ("blue", color.blue), ("alpha", color.alpha)],
+        }
+    }
+
+    fn literal(color: &CobColorLiteral) -> Self
+    {
+        // Use the color space with alpha so all fields are available.
+        let space = match color.space {
+            CobColorSpace::Rgb | CobColorSpace::Rgba => CobColorSpace::Rgba,
+            CobColorSpace::Hsl | CobColorSpace::Hsla => CobColorSpace::Hsla,
+            CobColorSpace::Oklch | CobColorSpace::Oklcha => CobColorSpace::Oklcha,
+        };
+        let params = space.params();
+        let components = color.components_f32();
+        let field = |idx: usize| (params[idx], components.get(idx).copied().unwrap_or(1.));
+        Self {
+            variant: space.variant(),
+            fields: [field(0), field(1), field(2), field(3)],
+        }
+    }
+}
+
+impl<'de> EnumAccess<'de> for ColorAccess
 {
     type Error = CobError;
-    type Variant = ColorSrgbaVariantAccess;
+    type Variant = ColorVariantAccess;
 
     fn variant_seed<V>(self, seed: V) -> CobResult<(V::Value, Self::Variant)>
     where
         V: DeserializeSeed<'de>,
     {
-        let variant = "Srgba".into_deserializer();
-        let visitor = ColorSrgbaVariantAccess { color: self.color };
+        let variant = self.variant.into_deserializer();
+        let visitor = ColorVariantAccess { fields: self.fields };
         seed.deserialize(variant).map(|v| (v, visitor))
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-struct ColorSrgbaVariantAccess
+struct ColorVariantAccess
 {
-    color: Srgba,
+    fields: [(&'static str, f32); 4],
 }
 
-impl<'de> VariantAccess<'de> for ColorSrgbaVariantAccess
+impl<'de> VariantAccess<'de> for ColorVariantAccess
 {
     type Error = CobError;
 
@@ -55,7 +87,7 @@ impl<'de> VariantAccess<'de> for ColorSrgbaVariantAccess
     {
         Err(serde::de::Error::invalid_type(
             Unexpected::UnitVariant,
-            &"newtype variant Srgba",
+            &"newtype variant",
         ))
     }
 
@@ -63,7 +95,7 @@ impl<'de> VariantAccess<'de> for ColorSrgbaVariantAccess
     where
         T: DeserializeSeed<'de>,
     {
-        seed.deserialize(SrgbaDeserializer { color: self.color })
+        seed.deserialize(ColorFieldsDeserializer { fields: self.fields })
     }
 
     fn tuple_variant<V>(self, _len: usize, _visitor: V) -> CobResult<V::Value>
@@ -72,7 +104,7 @@ impl<'de> VariantAccess<'de> for ColorSrgbaVariantAccess
     {
         Err(serde::de::Error::invalid_type(
             Unexpected::TupleVariant,
-            &"newtype variant Srgba",
+            &"newtype variant",
         ))
     }
 
@@ -82,19 +114,19 @@ impl<'de> VariantAccess<'de> for ColorSrgbaVariantAccess
     {
         Err(serde::de::Error::invalid_type(
             Unexpected::StructVariant,
-            &"newtype variant Srgba",
+            &"newtype variant",
         ))
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-struct SrgbaDeserializer
+struct ColorFieldsDeserializer
 {
-    color: Srgba,
+    fields: [(&'static str, f32); 4],
 }
 
-impl<'de> serde::Deserializer<'de> for SrgbaDeserializer
+impl<'de> serde::Deserializer<'de> for ColorFieldsDeserializer
 {
     type Error = CobError;
 
@@ -102,7 +134,7 @@ impl<'de> serde::Deserializer<'de> for SrgbaDeserializer
     where
         V: Visitor<'de>,
     {
-        visitor.visit_map(SrgbaAccess::new(self.color))
+        visitor.visit_map(ColorFieldsAccess { next: 0, fields: self.fields, value: None })
     }
 
     forward_to_deserialize_any! {
@@ -115,22 +147,14 @@ impl<'de> serde::Deserializer<'de> for SrgbaDeserializer
 
 //-------------------------------------------------------------------------------------------------------------------
 
-struct SrgbaAccess
+struct ColorFieldsAccess
 {
     next: usize,
-    color: Srgba,
+    fields: [(&'static str, f32); 4],
     value: Option<f32>,
 }
 
-impl SrgbaAccess
-{
-    fn new(color: Srgba) -> Self
-    {
-        SrgbaAccess { next: 0, color, value: None }
-    }
-}
-
-impl<'de> MapAccess<'de> for SrgbaAccess
+impl<'de> MapAccess<'de> for ColorFieldsAccess
 {
     type Error = CobError;
 
@@ -138,33 +162,10 @@ impl<'de> MapAccess<'de> for SrgbaAccess
     where
         T: DeserializeSeed<'de>,
     {
-        match self.next {
-            0 => {
-                self.value = Some(self.color.red);
-                self.next += 1;
-                let name = "red".into_deserializer();
-                seed.deserialize(name).map(Some)
-            }
-            1 => {
-                self.value = Some(self.color.green);
-                self.next += 1;
-                let name = "green".into_deserializer();
-                seed.deserialize(name).map(Some)
-            }
-            2 => {
-                self.value = Some(self.color.blue);
-                self.next += 1;
-                let name = "blue".into_deserializer();
-                seed.deserialize(name).map(Some)
-            }
-            3 => {
-                self.value = Some(self.color.alpha);
-                self.next += 1;
-                let name = "alpha".into_deserializer();
-                seed.deserialize(name).map(Some)
-            }
-            _ => Ok(None),
-        }
+        let Some((name, value)) = self.fields.get(self.next).copied() else { return Ok(None) };
+        self.value = Some(value);
+        self.next += 1;
+        seed.deserialize(name.into_deserializer()).map(Some)
     }
 
     fn next_value_seed<T>(&mut self, seed: T) -> CobResult<T::Value>
@@ -173,13 +174,13 @@ impl<'de> MapAccess<'de> for SrgbaAccess
     {
         match self.value.take() {
             Some(value) => seed.deserialize(value.into_deserializer()),
-            None => Err(serde::de::Error::custom("Srgba field is missing")),
+            None => Err(serde::de::Error::custom("color field is missing")),
         }
     }
 
     fn size_hint(&self) -> Option<usize>
     {
-        Some(4usize.saturating_sub(self.next))
+        Some(self.fields.len().saturating_sub(self.next))
     }
 }
 
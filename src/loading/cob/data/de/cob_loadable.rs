@@ -2,8 +2,9 @@ use serde::de::{Expected, Visitor};
 use serde::forward_to_deserialize_any;
 
 use super::{
-    visit_array_ref, visit_map_ref, visit_tuple_ref, visit_wrapped_array_ref, visit_wrapped_erased_ref,
-    visit_wrapped_map_ref, visit_wrapped_tuple_ref, EnumRefDeserializer, ErasedNewtypeStruct, MapRefDeserializer,
+    rect_shorthand_ref, visit_array_ref, visit_map_ref, visit_rect_shorthand_ref, visit_tuple_ref,
+    visit_wrapped_array_ref, visit_wrapped_erased_ref, visit_wrapped_map_ref, visit_wrapped_tuple_ref,
+    EnumRefDeserializer, ErasedNewtypeStruct, MapRefDeserializer,
 };
 use crate::prelude::*;
 
@@ -116,12 +117,24 @@ impl<'de> serde::Deserializer<'de> for &'de CobLoadable
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> CobResult<V::Value>
     where
         V: Visitor<'de>,
     {
+        // Rect shorthand, e.g. `Margin(4px 8px)` for a struct with rect fields.
+        if is_rect_shorthand_struct(fields.iter().copied()) {
+            let values = match &self.variant {
+                CobLoadableVariant::Tuple(tuple) => rect_shorthand_ref(&tuple.entries),
+                CobLoadableVariant::Array(array) => rect_shorthand_ref(&array.entries),
+                _ => None,
+            };
+            if let Some(values) = values {
+                return visit_rect_shorthand_ref(values, visitor);
+            }
+        }
+
         match &self.variant {
             CobLoadableVariant::Unit => {
                 // Use this instead of `visitor.visit_unit()` because some visitor implementations don't handle it
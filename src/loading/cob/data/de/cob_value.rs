@@ -1,8 +1,8 @@
 use serde::de::{Expected, Visitor};
 
 use super::{
-    deserialize_builtin, visit_array_ref, visit_map_ref, visit_tuple_ref, visit_wrapped_value_ref,
-    EnumRefDeserializer,
+    deserialize_builtin, rect_shorthand_ref, visit_array_ref, visit_map_ref, visit_rect_shorthand_ref,
+    visit_tuple_ref, visit_wrapped_value_ref, EnumRefDeserializer,
 };
 use crate::prelude::*;
 
@@ -44,6 +44,8 @@ impl<'de> serde::Deserializer<'de> for &'de CobValue
             CobValue::None(_) => visitor.visit_none(),
             CobValue::String(s) => visitor.visit_borrowed_str(s.as_str()),
             CobValue::Constant(_) => Err(self.invalid_type(&visitor)),
+            CobValue::DataMacro(_) => Err(self.invalid_type(&visitor)),
+            CobValue::Expr(_) => Err(self.invalid_type(&visitor)),
         }
     }
 
@@ -217,12 +219,25 @@ impl<'de> serde::Deserializer<'de> for &'de CobValue
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> CobResult<V::Value>
     where
         V: Visitor<'de>,
     {
+        // Rect shorthand, e.g. `margin:(4px 8px)` or `margin:4px` for a `UiRect`.
+        if is_rect_shorthand_struct(fields.iter().copied()) {
+            let values = match self {
+                CobValue::Builtin(CobBuiltin::Val { .. }) => rect_shorthand_ref(std::slice::from_ref(self)),
+                CobValue::Tuple(tuple) => rect_shorthand_ref(&tuple.entries),
+                CobValue::Array(array) => rect_shorthand_ref(&array.entries),
+                _ => None,
+            };
+            if let Some(values) = values {
+                return visit_rect_shorthand_ref(values, visitor);
+            }
+        }
+
         match self {
             // Allow empty tuples to be treated as unit structs.
             CobValue::Tuple(tuple) => {
@@ -289,6 +304,13 @@ impl CobValue
             CobValue::None(_) => format!("None"),
             CobValue::String(s) => format!("string \"{}\"", s.as_str()),
             CobValue::Constant(constant) => format!("constant ${}", constant.path.as_str()),
+            CobValue::DataMacro(call) => format!("data macro *{}", call.path.as_str()),
+            CobValue::Expr(expr) => {
+                let mut buff = Vec::<u8>::default();
+                let mut serializer = DefaultRawSerializer::new(&mut buff);
+                let _ = expr.write_to(&mut serializer);
+                format!("unevaluated expression {}", String::from_utf8_lossy(&buff))
+            }
         }
     }
 }
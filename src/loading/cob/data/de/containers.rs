@@ -16,11 +16,28 @@ impl<'de> serde::Deserializer<'de> for &'de CobArray
         visit_array_ref(&self.entries, visitor)
     }
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> CobResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if is_rect_shorthand_struct(fields.iter().copied()) {
+            if let Some(values) = rect_shorthand_ref(&self.entries) {
+                return visit_rect_shorthand_ref(values, visitor);
+            }
+        }
+        visit_array_ref(&self.entries, visitor)
+    }
+
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf option unit seq tuple
         map identifier ignored_any
-        enum newtype_struct unit_struct tuple_struct struct
+        enum newtype_struct unit_struct tuple_struct
     }
 }
 
@@ -37,11 +54,28 @@ impl<'de> serde::Deserializer<'de> for &'de CobTuple
         visit_tuple_ref(&self.entries, visitor)
     }
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> CobResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if is_rect_shorthand_struct(fields.iter().copied()) {
+            if let Some(values) = rect_shorthand_ref(&self.entries) {
+                return visit_rect_shorthand_ref(values, visitor);
+            }
+        }
+        visit_tuple_ref(&self.entries, visitor)
+    }
+
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf option unit seq tuple
         map identifier ignored_any
-        enum newtype_struct unit_struct tuple_struct struct
+        enum newtype_struct unit_struct tuple_struct
     }
 }
 
@@ -559,3 +593,71 @@ impl<'de> SeqAccess<'de> for WrappedValueRefDeserializer<'de>
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the rect fields written in rect shorthand (e.g. `(4px 8px)` for a `UiRect`), in the order of
+/// [`RECT_SHORTHAND_FIELDS`].
+///
+/// Returns `None` if the values are not one, two, or four `Val`s.
+pub(super) fn rect_shorthand_ref<'de>(values: &'de [CobValue]) -> Option<[&'de CobValue; 4]>
+{
+    if !values
+        .iter()
+        .all(|value| matches!(value, CobValue::Builtin(CobBuiltin::Val { .. })))
+    {
+        return None;
+    }
+    match values {
+        [all] => Some([all, all, all, all]),
+        [vertical, horizontal] => Some([vertical, horizontal, vertical, horizontal]),
+        [top, right, bottom, left] => Some([top, right, bottom, left]),
+        _ => None,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) fn visit_rect_shorthand_ref<'de, V>(values: [&'de CobValue; 4], visitor: V) -> CobResult<V::Value>
+where
+    V: Visitor<'de>,
+{
+    visitor.visit_map(RectShorthandAccess { next: 0, values })
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct RectShorthandAccess<'de>
+{
+    next: usize,
+    values: [&'de CobValue; 4],
+}
+
+impl<'de> MapAccess<'de> for RectShorthandAccess<'de>
+{
+    type Error = CobError;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> CobResult<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let Some(name) = RECT_SHORTHAND_FIELDS.get(self.next).copied() else { return Ok(None) };
+        seed.deserialize(name.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> CobResult<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let Some(value) = self.values.get(self.next) else {
+            return Err(serde::de::Error::custom("rect field is missing"));
+        };
+        self.next += 1;
+        seed.deserialize(*value)
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        Some(RECT_SHORTHAND_FIELDS.len().saturating_sub(self.next))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -15,6 +15,8 @@ use crate::prelude::*;
 pub struct CobConstantName
 {
     pub name: SmolStr,
+    /// Location of the name in its source file.
+    pub location: CobItemLocation,
 }
 
 impl CobConstantName
@@ -31,7 +33,10 @@ impl CobConstantName
         let (post_symbol, _) = char('$').parse(content)?;
         recognize(anything_identifier)
             .parse(post_symbol)
-            .map(|(r, k)| (Self { name: SmolStr::from(*k.fragment()) }, r))
+            .map(|(r, k)| {
+                let location = CobItemLocation::from_span_with_len(content, k.fragment().len() + 1);
+                (Self { name: SmolStr::from(*k.fragment()), location }, r)
+            })
     }
 
     pub fn as_str(&self) -> &str
@@ -188,11 +193,15 @@ impl CobConstantDef
             return Ok((None, start_fill, content));
         };
         let (pre_eq_fill, remaining) = CobFill::parse(remaining);
-        let (remaining, _) = char('=').parse(remaining)?;
+        let Ok((remaining, _)) = char::<_, ()>('=').parse(remaining) else {
+            return Err(span_diagnostic_error(remaining, "expected '=' after constant name"));
+        };
         let (value_fill, remaining) = CobFill::parse(remaining);
         let (Some(value), end_fill, remaining) = CobConstantValue::try_parse(value_fill, remaining)? else {
-            tracing::warn!("constant definition is invalid at {}", get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a value or value group after '='",
+            ));
         };
 
         let def = Self { start_fill, name, pre_eq_fill, value };
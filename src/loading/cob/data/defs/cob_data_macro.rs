@@ -0,0 +1,322 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::recognize;
+use nom::multi::many0_count;
+use nom::sequence::{terminated, tuple};
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Data macro name must be `*` followed by an identifier. Names do not include `a::b::` path segments.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobDataMacroName
+{
+    pub name: SmolStr,
+    /// Location of the name in its source file.
+    pub location: CobItemLocation,
+}
+
+impl CobDataMacroName
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("*".as_bytes())?;
+        writer.write_bytes(self.name.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn parse(content: Span) -> Result<(Self, Span), SpanError>
+    {
+        let (post_symbol, _) = char('*').parse(content)?;
+        recognize(anything_identifier)
+            .parse(post_symbol)
+            .map(|(r, k)| {
+                let location = CobItemLocation::from_span_with_len(content, k.fragment().len() + 1);
+                (Self { name: SmolStr::from(*k.fragment()), location }, r)
+            })
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Data macro paths must be a series of snake-case identifiers separated by `::`. E.g. `*a::b::my_macro`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobDataMacroPath
+{
+    pub path: SmolStr,
+}
+
+impl CobDataMacroPath
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("*".as_bytes())?;
+        writer.write_bytes(self.path.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn parse(content: Span) -> Result<(Self, Span), SpanError>
+    {
+        let (post_symbol, _) = char('*').parse(content)?;
+        recognize(tuple((
+            // Extensions
+            many0_count(terminated(snake_identifier, tag("::"))),
+            // Macro name
+            anything_identifier,
+        )))
+        .parse(post_symbol)
+        .map(|(r, k)| (Self { path: SmolStr::from(*k.fragment()) }, r))
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.path.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A named parameter in a data macro definition. Parameters are snake-case identifiers.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobDataMacroParam
+{
+    pub fill: CobFill,
+    pub name: SmolStr,
+}
+
+impl CobDataMacroParam
+{
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.name.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Parameter list of a data macro definition, e.g. `(color width)`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobDataMacroParams
+{
+    pub entries: Vec<CobDataMacroParam>,
+    /// Fill before ending `)`.
+    pub end_fill: CobFill,
+}
+
+impl CobDataMacroParams
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("(".as_bytes())?;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let space = if idx == 0 { "" } else { " " };
+            entry.write_to_with_space(writer, space)?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(content: Span) -> Result<(Option<Self>, Span), SpanError>
+    {
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(content) else { return Ok((None, content)) };
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut entries: Vec<CobDataMacroParam> = vec![];
+
+        let end_fill = loop {
+            let Ok((after_param, name)) = snake_identifier(remaining) else { break item_fill };
+            if entries.len() > 0 && item_fill.len() == 0 {
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("data macro parameter #{} is not preceded by whitespace", entries.len() + 1),
+                ));
+            }
+            if entries.iter().any(|p| p.as_str() == *name.fragment()) {
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("data macro parameter {:?} is duplicated", *name.fragment()),
+                ));
+            }
+            entries.push(CobDataMacroParam { fill: item_fill, name: SmolStr::from(*name.fragment()) });
+            (item_fill, remaining) = CobFill::parse(after_param);
+        };
+
+        let Ok((remaining, _)) = char::<_, ()>(')').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected ')'; data macro parameters must be snake-case identifiers",
+            ));
+        };
+        Ok((Some(Self { entries, end_fill }), remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.fill.recover(&other_entry.fill);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_
+    {
+        self.entries.iter().map(|p| p.as_str())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Definition of a data macro, e.g. `*card_border(color width) = Animated<BorderColor>{ idle:$color }`.
+///
+/// Parameters are referenced in the macro value like constants (`$color`), and shadow constants with the same
+/// name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobDataMacroDef
+{
+    pub start_fill: CobFill,
+    pub name: CobDataMacroName,
+    // No fill between name and params.
+    pub params: CobDataMacroParams,
+    pub pre_eq_fill: CobFill,
+    /// The value is expected to handle its own fill.
+    pub value: CobValue,
+}
+
+impl CobDataMacroDef
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        self.name.write_to(writer)?;
+        self.params.write_to(writer)?;
+        self.pre_eq_fill.write_to(writer)?;
+        writer.write_bytes("=".as_bytes())?;
+        self.value.write_to(writer)?;
+
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((name, remaining)) = rc(content, |c| CobDataMacroName::parse(c)) else {
+            return Ok((None, start_fill, content));
+        };
+        let (Some(params), remaining) = CobDataMacroParams::try_parse(remaining)? else {
+            return Err(span_diagnostic_error(
+                content,
+                format!("data macro definition is missing a parameter list (use `*{}() = ...` for a macro without \
+                    parameters)", name.as_str()),
+            ));
+        };
+        let (pre_eq_fill, remaining) = CobFill::parse(remaining);
+        let Ok((remaining, _)) = char::<_, ()>('=').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected '=' after data macro parameter list name",
+            ));
+        };
+        let (value_fill, remaining) = CobFill::parse(remaining);
+        let (Some(value), end_fill, remaining) = rc(remaining, move |rm| CobValue::try_parse(value_fill, rm))?
+        else {
+            return Err(span_diagnostic_error(remaining, "expected a value after '='"));
+        };
+
+        let def = Self { start_fill, name, params, pre_eq_fill, value };
+        Ok((Some(def), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        // Name has no fill
+        self.params.recover_fill(&other.params);
+        self.pre_eq_fill.recover(&other.pre_eq_fill);
+        self.value.recover_fill(&other.value);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Invocation of a data macro, e.g. `*card_border(#333 2px)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobDataMacroCall
+{
+    pub start_fill: CobFill,
+    pub path: CobDataMacroPath,
+    /// Arguments are written like a tuple, and must abut the macro path.
+    pub args: CobTuple,
+}
+
+impl CobDataMacroCall
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        self.path.write_to(writer)?;
+        self.args.write_to(writer)?;
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((path, remaining)) = rc(content, |c| CobDataMacroPath::parse(c)) else {
+            return Ok((None, start_fill, content));
+        };
+        let (Some(args), end_fill, remaining) = rc(remaining, |rm| CobTuple::try_parse(CobFill::default(), rm))?
+        else {
+            return Err(span_diagnostic_error(
+                content,
+                "expected data macro arguments `(...)` immediately after the macro name",
+            ));
+        };
+
+        Ok((Some(Self { start_fill, path, args }), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        // Path has no fill
+        self.args.recover_fill(&other.args);
+    }
+
+    /// Resolves the arguments then expands the macro into a plain value.
+    pub fn resolve(&mut self, resolver: &CobLoadableResolver) -> Result<CobValue, String>
+    {
+        self.args.resolve(resolver)?;
+        resolver.data_macros.expand(self)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,373 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::recognize;
+use nom::multi::many0_count;
+use nom::sequence::{terminated, tuple};
+use nom::Parser;
+use smol_str::SmolStr;
+
+use super::cob_scene_macro::try_parse_scene_group;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable macros can only contain loadables (and conditions on them). Definitions may also contain other
+/// loadable macro invocations, and invocations may also contain scene macro commands.
+fn verify_loadable_macro_entries<'a>(
+    entries: &[CobSceneLayerEntry],
+    is_definition: bool,
+    content: Span<'a>,
+) -> Result<(), SpanError<'a>>
+{
+    for entry in entries.iter() {
+        let err_msg = match entry {
+            CobSceneLayerEntry::Loadable(_) | CobSceneLayerEntry::Condition(_) => continue,
+            CobSceneLayerEntry::LoadableMacroCall(_) if is_definition => continue,
+            CobSceneLayerEntry::SceneMacroCommand(_) if !is_definition => continue,
+            CobSceneLayerEntry::LoadableMacroCall(_) => "loadable macro invocations",
+            CobSceneLayerEntry::SceneMacroCommand(_) => "scene macro commands",
+            CobSceneLayerEntry::SceneMacroCall(_) => "scene macro invocations",
+            CobSceneLayerEntry::Layer(_) => "scene nodes",
+            CobSceneLayerEntry::For(_) => "#for loops",
+        };
+        let kind = if is_definition {
+            "definition"
+        } else {
+            "invocation"
+        };
+        return Err(span_diagnostic_error(
+            content,
+            format!("loadable macro {kind}s cannot contain {err_msg}"),
+        ));
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable macro name must be `@` followed by an identifier. Names do not include `a::b::` path segments.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobLoadableMacroName
+{
+    pub name: SmolStr,
+    /// Location of the name in its source file.
+    pub location: CobItemLocation,
+}
+
+impl CobLoadableMacroName
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("@".as_bytes())?;
+        writer.write_bytes(self.name.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn parse(content: Span) -> Result<(Self, Span), SpanError>
+    {
+        let (post_symbol, _) = char('@').parse(content)?;
+        recognize(anything_identifier)
+            .parse(post_symbol)
+            .map(|(r, k)| {
+                let location = CobItemLocation::from_span_with_len(content, k.fragment().len() + 1);
+                (Self { name: SmolStr::from(*k.fragment()), location }, r)
+            })
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable macro paths must be a series of snake-case identifiers separated by `::`. E.g. `@a::b::my_macro`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobLoadableMacroPath
+{
+    pub path: SmolStr,
+}
+
+impl CobLoadableMacroPath
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("@".as_bytes())?;
+        writer.write_bytes(self.path.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn parse(content: Span) -> Result<(Self, Span), SpanError>
+    {
+        let (post_symbol, _) = char('@').parse(content)?;
+        recognize(tuple((
+            // Extensions
+            many0_count(terminated(snake_identifier, tag("::"))),
+            // Macro name
+            anything_identifier,
+        )))
+        .parse(post_symbol)
+        .map(|(r, k)| (Self { path: SmolStr::from(*k.fragment()) }, r))
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.path.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable group for loadable macro definitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobLoadableMacroValue
+{
+    /// Fill before opening `\`.
+    pub start_fill: CobFill,
+    pub entries: Vec<CobSceneLayerEntry>,
+    /// Fill before ending `\`.
+    pub end_fill: CobFill,
+}
+
+impl CobLoadableMacroValue
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("\\".as_bytes())?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes("\\".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let (result, post_fill, remaining) = try_parse_scene_group('\\', '\\', 0, start_fill, content)?;
+        let Some((start_fill, entries, end_fill)) = result else { return Ok((None, post_fill, remaining)) };
+        verify_loadable_macro_entries(&entries, true, content)?;
+
+        Ok((Some(Self { start_fill, entries, end_fill }), post_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.recover_fill(other_entry);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    pub fn resolve(&mut self, resolver: &mut CobResolver, resolve_mode: SceneResolveMode) -> Result<(), String>
+    {
+        CobSceneLayer::resolve_entries_impl("", &mut self.entries, resolver, resolve_mode)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loadable group for loadable macro invocations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobLoadableMacroContainer
+{
+    pub entries: Vec<CobSceneLayerEntry>,
+    /// Fill before ending `}`.
+    pub end_fill: CobFill,
+}
+
+impl CobLoadableMacroContainer
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("{".as_bytes())?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes("}".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(layer_indent: usize, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let (result, post_fill, remaining) =
+            try_parse_scene_group('{', '}', layer_indent, CobFill::default(), content)?;
+        let Some((_, entries, end_fill)) = result else { return Ok((None, post_fill, remaining)) };
+        verify_loadable_macro_entries(&entries, false, content)?;
+
+        Ok((Some(Self { entries, end_fill }), post_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.recover_fill(other_entry);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    pub fn resolve(&mut self, resolver: &mut CobResolver, resolve_mode: SceneResolveMode) -> Result<(), String>
+    {
+        CobSceneLayer::resolve_entries_impl("", &mut self.entries, resolver, resolve_mode)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Definition of a loadable macro, e.g. `@button_colors = \ ... \`.
+///
+/// Loadable macros expand to a list of loadables at the scene node where they are invoked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobLoadableMacroDef
+{
+    pub start_fill: CobFill,
+    pub name: CobLoadableMacroName,
+    pub pre_eq_fill: CobFill,
+    /// The value is expected to handle its own fill.
+    pub value: CobLoadableMacroValue,
+}
+
+impl CobLoadableMacroDef
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        self.name.write_to(writer)?;
+        self.pre_eq_fill.write_to(writer)?;
+        writer.write_bytes("=".as_bytes())?;
+        self.value.write_to(writer)?;
+
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((name, remaining)) = rc(content, |c| CobLoadableMacroName::parse(c)) else {
+            return Ok((None, start_fill, content));
+        };
+        let (pre_eq_fill, remaining) = CobFill::parse(remaining);
+        let Ok((remaining, _)) = char::<_, ()>('=').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected '=' after loadable macro name",
+            ));
+        };
+        let (value_fill, remaining) = CobFill::parse(remaining);
+        let (Some(value), end_fill, remaining) = CobLoadableMacroValue::try_parse(value_fill, remaining)? else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a loadable group `\\ ... \\` after '='",
+            ));
+        };
+
+        let def = Self { start_fill, name, pre_eq_fill, value };
+        Ok((Some(def), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        // Name has no fill
+        self.pre_eq_fill.recover(&other.pre_eq_fill);
+        self.value.recover_fill(&other.value);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Invocation of a loadable macro, e.g. `@button_colors{}`.
+///
+/// The container can overwrite loadables in the macro, add new loadables, and rearrange or remove loadables with
+/// scene macro commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobLoadableMacroCall
+{
+    pub start_fill: CobFill,
+    pub path: CobLoadableMacroPath,
+    // No fill between path and container.
+    pub container: CobLoadableMacroContainer,
+}
+
+impl CobLoadableMacroCall
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        self.path.write_to(writer)?;
+        self.container.write_to(writer)?;
+
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((path, remaining)) = rc(content, |c| CobLoadableMacroPath::parse(c)) else {
+            return Ok((None, start_fill, content));
+        };
+        let (pre_container_fill, remaining) = CobFill::parse(remaining);
+
+        // Loadable macro invocations may not have fill before the opening brace.
+        if pre_container_fill.len() != 0 {
+            return Err(span_diagnostic_error(
+                remaining,
+                "unexpected whitespace between loadable macro name and its container",
+            ));
+        }
+
+        let layer_indent = start_fill.ends_newline_then_num_spaces().unwrap_or(0);
+
+        let (Some(container), end_fill, remaining) =
+            CobLoadableMacroContainer::try_parse(layer_indent, remaining)?
+        else {
+            return Err(span_diagnostic_error(
+                content,
+                "expected a container `{ ... }` after loadable macro name",
+            ));
+        };
+
+        let def = Self { start_fill, path, container };
+        Ok((Some(def), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        // Name has no fill.
+        self.container.recover_fill(&other.container);
+    }
+
+    pub fn resolve(
+        &mut self,
+        resolver: &mut CobResolver,
+        resolve_mode: SceneResolveMode,
+    ) -> Result<Vec<CobSceneLayerEntry>, String>
+    {
+        // Resolve the content.
+        self.container.resolve(resolver, resolve_mode)?;
+
+        // Expand the macro.
+        resolver.scenes.loadable_macros.expand(self)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
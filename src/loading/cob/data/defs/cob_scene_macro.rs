@@ -11,7 +11,7 @@ use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn try_parse_scene_group(
+pub(super) fn try_parse_scene_group(
     opener: char,
     closer: char,
     layer_indent: usize,
@@ -26,17 +26,16 @@ fn try_parse_scene_group(
     let Some(content_indent) = item_fill.ends_newline_then_num_spaces() else {
         // Check if there is in fact no content.
         let Ok((remaining, _)) = char::<_, ()>(closer).parse(remaining) else {
-            tracing::warn!("failed parsing scene group at {}; group opener '{opener}' not on a separate line from the first item",
-                get_location(remaining));
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("first item in a scene group isn't on a separate line from the group opener '{opener}'"),
+            ));
         };
         let (post_fill, remaining) = CobFill::parse(remaining);
         return Ok((Some((start_fill, vec![], item_fill)), post_fill, remaining));
     };
     if content_indent == 0 {
-        tracing::warn!("failed parsing scene group at {}; content indent is zero",
-            get_location(remaining));
-        return Err(span_verify_error(remaining));
+        return Err(span_diagnostic_error(remaining, "scene group content must be indented"));
     }
 
     // Collect entries.
@@ -58,14 +57,20 @@ fn try_parse_scene_group(
         }
     };
 
-    let (remaining, _) = char(closer).parse(remaining)?;
+    let Ok((remaining, _)) = char::<_, ()>(closer).parse(remaining) else {
+        return Err(span_diagnostic_error(
+            remaining,
+            format!("expected a scene group item or closing '{closer}'"),
+        ));
+    };
     let (post_fill, remaining) = CobFill::parse(remaining);
     Ok((Some((start_fill, entries, end_fill)), post_fill, remaining))
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Command that can be used in scene macro invocations to rearrange loadables in the macro's scene content.
+/// Command that can be used in scene macro invocations to rearrange loadables and scene nodes in the macro's scene
+/// content.
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub enum CobSceneMacroCommandType
 {
@@ -114,7 +119,10 @@ pub struct CobSceneMacroCommand
 {
     pub start_fill: CobFill,
     pub command_type: CobSceneMacroCommandType,
+    /// The targeted loadable. Ignored if `node` is set.
     pub id: CobLoadableIdentifier,
+    /// The targeted scene node, e.g. `-"footer"`.
+    pub node: Option<CobSceneNodeName>,
 }
 
 impl CobSceneMacroCommand
@@ -123,7 +131,10 @@ impl CobSceneMacroCommand
     {
         self.start_fill.write_to(writer)?;
         self.command_type.write_to(writer)?;
-        self.id.write_to(writer)?;
+        match &self.node {
+            Some(node) => node.write_to(writer)?,
+            None => self.id.write_to(writer)?,
+        }
         Ok(())
     }
 
@@ -132,16 +143,31 @@ impl CobSceneMacroCommand
         let Ok((remaining, command_type)) = CobSceneMacroCommandType::parse_nomlike(content) else {
             return Ok((None, start_fill, content));
         };
+        if let (Some(node), remaining) = CobSceneNodeName::try_parse(remaining)? {
+            let (post_fill, remaining) = CobFill::parse(remaining);
+            let command = Self {
+                start_fill,
+                command_type,
+                id: CobLoadableIdentifier::default(),
+                node: Some(node),
+            };
+            return Ok((Some(command), post_fill, remaining));
+        }
         let (id, remaining) = match CobLoadableIdentifier::parse(remaining) {
             Ok((id, remaining)) => (id, remaining),
             Err(err) => {
-                tracing::warn!("failed parsing cob scene macro command at {}; id is invalid: {err:?}",
-                    get_location(content).as_str());
-                return Err(span_verify_error(content));
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("expected a loadable name after scene macro command: {err:?}"),
+                ));
             }
         };
         let (post_fill, remaining) = CobFill::parse(remaining);
-        Ok((Some(Self { start_fill, command_type, id }), post_fill, remaining))
+        Ok((
+            Some(Self { start_fill, command_type, id, node: None }),
+            post_fill,
+            remaining,
+        ))
     }
 
     pub fn recover_fill(&mut self, other: &Self)
@@ -159,6 +185,8 @@ impl CobSceneMacroCommand
 pub struct CobSceneMacroName
 {
     pub name: SmolStr,
+    /// Location of the name in its source file.
+    pub location: CobItemLocation,
 }
 
 impl CobSceneMacroName
@@ -175,7 +203,10 @@ impl CobSceneMacroName
         let (post_symbol, _) = char('+').parse(content)?;
         recognize(anything_identifier)
             .parse(post_symbol)
-            .map(|(r, k)| (Self { name: SmolStr::from(*k.fragment()) }, r))
+            .map(|(r, k)| {
+                let location = CobItemLocation::from_span_with_len(content, k.fragment().len() + 1);
+                (Self { name: SmolStr::from(*k.fragment()), location }, r)
+            })
     }
 
     pub fn as_str(&self) -> &str
@@ -223,6 +254,283 @@ impl CobSceneMacroPath
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// A named parameter in a scene macro definition, with an optional default value. E.g. `label` or `accent=#444`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobSceneMacroParam
+{
+    pub fill: CobFill,
+    pub name: SmolStr,
+    /// No fill between the name and `=`. The value is expected to handle its own fill.
+    pub default: Option<CobValue>,
+}
+
+impl CobSceneMacroParam
+{
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.name.as_bytes())?;
+        if let Some(default) = &self.default {
+            writer.write_bytes("=".as_bytes())?;
+            default.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Parameter list of a scene macro definition, e.g. `(label accent=#444)`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobSceneMacroParams
+{
+    pub entries: Vec<CobSceneMacroParam>,
+    /// Fill before ending `)`.
+    pub end_fill: CobFill,
+}
+
+impl CobSceneMacroParams
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("(".as_bytes())?;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let space = if idx == 0 { "" } else { " " };
+            entry.write_to_with_space(writer, space)?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(content: Span) -> Result<(Option<Self>, Span), SpanError>
+    {
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(content) else { return Ok((None, content)) };
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut entries: Vec<CobSceneMacroParam> = vec![];
+
+        let end_fill = loop {
+            let Ok((after_param, name)) = snake_identifier(remaining) else { break item_fill };
+            if entries.len() > 0 && item_fill.len() == 0 {
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("scene macro parameter #{} is not preceded by whitespace", entries.len() + 1),
+                ));
+            }
+            if entries.iter().any(|p| p.as_str() == *name.fragment()) {
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("scene macro parameter {:?} is duplicated", *name.fragment()),
+                ));
+            }
+
+            // Default value.
+            let mut param = CobSceneMacroParam {
+                fill: item_fill,
+                name: SmolStr::from(*name.fragment()),
+                default: None,
+            };
+            if let Ok((after_eq, _)) = char::<_, ()>('=').parse(after_param) {
+                let (value_fill, after_eq) = CobFill::parse(after_eq);
+                let (Some(value), next_fill, after_value) =
+                    rc(after_eq, move |rm| CobValue::try_parse(value_fill, rm))?
+                else {
+                    return Err(span_diagnostic_error(
+                        after_eq,
+                        format!("expected a default value for scene macro parameter {:?}", param.as_str()),
+                    ));
+                };
+                param.default = Some(value);
+                (item_fill, remaining) = (next_fill, after_value);
+            } else {
+                (item_fill, remaining) = CobFill::parse(after_param);
+            }
+            entries.push(param);
+        };
+
+        let Ok((remaining, _)) = char::<_, ()>(')').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected ')'; scene macro parameters must be snake-case identifiers with optional default values \
+                (e.g. `label` or `accent=#444`)",
+            ));
+        };
+        Ok((Some(Self { entries, end_fill }), remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.fill.recover(&other_entry.fill);
+            if let (Some(default), Some(other_default)) = (&mut entry.default, &other_entry.default) {
+                default.recover_fill(other_default);
+            }
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    /// Resolves default values.
+    pub fn resolve(&mut self, resolver: &CobLoadableResolver) -> Result<(), String>
+    {
+        for param in self.entries.iter_mut() {
+            let Some(default) = &mut param.default else { continue };
+            if default.resolve(resolver)?.is_some() {
+                return Err(
+                    format!("default value of scene macro parameter {:?} cannot be a value group",
+                    param.name.as_str()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_
+    {
+        self.entries.iter().map(|p| p.as_str())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A named argument in a scene macro invocation, e.g. `label="Submit"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobSceneMacroArg
+{
+    pub fill: CobFill,
+    pub name: SmolStr,
+    /// No fill between the name and `=`. The value is expected to handle its own fill.
+    pub value: CobValue,
+}
+
+impl CobSceneMacroArg
+{
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.name.as_bytes())?;
+        writer.write_bytes("=".as_bytes())?;
+        self.value.write_to(writer)?;
+        Ok(())
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        self.name.as_str()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Argument list of a scene macro invocation, e.g. `(label="Submit" accent=#F00)`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CobSceneMacroArgs
+{
+    pub entries: Vec<CobSceneMacroArg>,
+    /// Fill before ending `)`.
+    pub end_fill: CobFill,
+}
+
+impl CobSceneMacroArgs
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("(".as_bytes())?;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let space = if idx == 0 { "" } else { " " };
+            entry.write_to_with_space(writer, space)?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(content: Span) -> Result<(Option<Self>, Span), SpanError>
+    {
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(content) else { return Ok((None, content)) };
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut entries: Vec<CobSceneMacroArg> = vec![];
+
+        let end_fill = loop {
+            let Ok((after_name, name)) = snake_identifier(remaining) else { break item_fill };
+            let Ok((after_eq, _)) = char::<_, ()>('=').parse(after_name) else {
+                return Err(span_diagnostic_error(
+                    remaining,
+                    "expected '=' after scene macro argument name; arguments must be named (e.g. `label=\"Submit\"`)",
+                ));
+            };
+            if entries.len() > 0 && item_fill.len() == 0 {
+                return Err(span_diagnostic_error(
+                    remaining,
+                    format!("scene macro argument #{} is not preceded by whitespace", entries.len() + 1),
+                ));
+            }
+            if entries.iter().any(|a| a.as_str() == *name.fragment()) {
+                return Err(span_diagnostic_error(
+                    remaining,
+                    format!("scene macro argument {:?} is duplicated", *name.fragment()),
+                ));
+            }
+
+            let (value_fill, after_eq) = CobFill::parse(after_eq);
+            let (Some(value), next_fill, after_value) =
+                rc(after_eq, move |rm| CobValue::try_parse(value_fill, rm))?
+            else {
+                return Err(span_diagnostic_error(
+                    after_eq,
+                    format!("expected a value for scene macro argument {:?}", *name.fragment()),
+                ));
+            };
+            entries.push(CobSceneMacroArg {
+                fill: item_fill,
+                name: SmolStr::from(*name.fragment()),
+                value,
+            });
+            (item_fill, remaining) = (next_fill, after_value);
+        };
+
+        let Ok((remaining, _)) = char::<_, ()>(')').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected ')'; scene macro arguments must be written as `name=value`",
+            ));
+        };
+        Ok((Some(Self { entries, end_fill }), remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.fill.recover(&other_entry.fill);
+            entry.value.recover_fill(&other_entry.value);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    pub fn resolve(&mut self, resolver: &CobLoadableResolver) -> Result<(), String>
+    {
+        for arg in self.entries.iter_mut() {
+            if arg.value.resolve(resolver)?.is_some() {
+                return Err(format!("scene macro argument {:?} cannot be a value group", arg.name.as_str()));
+            }
+        }
+        Ok(())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Scene group for scene macro definitions.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CobSceneMacroValue
@@ -332,11 +640,17 @@ impl CobSceneMacroContainer
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Definition of a scene macro, e.g. `+button(label accent=#444) = \ ... \`.
+///
+/// Parameters are optional. They are referenced in the macro content like constants (`$label`), and shadow
+/// constants with the same name.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CobSceneMacroDef
 {
     pub start_fill: CobFill,
     pub name: CobSceneMacroName,
+    // No fill between name and params.
+    pub params: Option<CobSceneMacroParams>,
     pub pre_eq_fill: CobFill,
     /// The value is expected to handle its own fill.
     pub value: CobSceneMacroValue,
@@ -353,6 +667,9 @@ impl CobSceneMacroDef
     {
         self.start_fill.write_to_or_else(writer, space)?;
         self.name.write_to(writer)?;
+        if let Some(params) = &self.params {
+            params.write_to(writer)?;
+        }
         self.pre_eq_fill.write_to(writer)?;
         writer.write_bytes("=".as_bytes())?;
         self.value.write_to(writer)?;
@@ -365,15 +682,20 @@ impl CobSceneMacroDef
         let Ok((name, remaining)) = rc(content, |c| CobSceneMacroName::parse(c)) else {
             return Ok((None, start_fill, content));
         };
+        let (params, remaining) = CobSceneMacroParams::try_parse(remaining)?;
         let (pre_eq_fill, remaining) = CobFill::parse(remaining);
-        let (remaining, _) = char('=').parse(remaining)?;
+        let Ok((remaining, _)) = char::<_, ()>('=').parse(remaining) else {
+            return Err(span_diagnostic_error(remaining, "expected '=' after scene macro name"));
+        };
         let (value_fill, remaining) = CobFill::parse(remaining);
         let (Some(value), end_fill, remaining) = CobSceneMacroValue::try_parse(value_fill, remaining)? else {
-            tracing::warn!("scene macro definition is invalid at {}", get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a scene group `\\ ... \\` after '='",
+            ));
         };
 
-        let def = Self { start_fill, name, pre_eq_fill, value };
+        let def = Self { start_fill, name, params, pre_eq_fill, value };
         Ok((Some(def), end_fill, remaining))
     }
 
@@ -381,6 +703,9 @@ impl CobSceneMacroDef
     {
         self.start_fill.recover(&other.start_fill);
         // Name has no fill
+        if let (Some(params), Some(other_params)) = (&mut self.params, &other.params) {
+            params.recover_fill(other_params);
+        }
         self.pre_eq_fill.recover(&other.pre_eq_fill);
         self.value.recover_fill(&other.value);
     }
@@ -393,7 +718,9 @@ pub struct CobSceneMacroCall
 {
     pub start_fill: CobFill,
     pub path: CobSceneMacroPath,
-    // No fill between path and container.
+    /// Named arguments, e.g. `(label="Submit")`. No fill between path and args.
+    pub args: Option<CobSceneMacroArgs>,
+    // No fill between path/args and container.
     pub container: CobSceneMacroContainer,
 }
 
@@ -408,6 +735,9 @@ impl CobSceneMacroCall
     {
         self.start_fill.write_to_or_else(writer, space)?;
         self.path.write_to(writer)?;
+        if let Some(args) = &self.args {
+            args.write_to(writer)?;
+        }
         self.container.write_to(writer)?;
 
         Ok(())
@@ -418,25 +748,28 @@ impl CobSceneMacroCall
         let Ok((path, remaining)) = rc(content, |c| CobSceneMacroPath::parse(c)) else {
             return Ok((None, start_fill, content));
         };
+        let (args, remaining) = CobSceneMacroArgs::try_parse(remaining)?;
         let (pre_container_fill, remaining) = CobFill::parse(remaining);
 
         // Scene macro invocations may not have fill before the opening brace.
         if pre_container_fill.len() != 0 {
-            tracing::warn!("failed parsing scene macro invocation at {}; there is whitespace between the macro name and \
-                its container",
-                get_location(remaining));
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(
+                remaining,
+                "unexpected whitespace between scene macro name and its container",
+            ));
         }
 
         let layer_indent = start_fill.ends_newline_then_num_spaces().unwrap_or(0);
 
         let (Some(container), end_fill, remaining) = CobSceneMacroContainer::try_parse(layer_indent, remaining)?
         else {
-            tracing::warn!("scene macro invocation is invalid at {}", get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                content,
+                "expected a container `{ ... }` after scene macro name",
+            ));
         };
 
-        let def = Self { start_fill, path, container };
+        let def = Self { start_fill, path, args, container };
         Ok((Some(def), end_fill, remaining))
     }
 
@@ -444,6 +777,9 @@ impl CobSceneMacroCall
     {
         self.start_fill.recover(&other.start_fill);
         // Name has no fill.
+        if let (Some(args), Some(other_args)) = (&mut self.args, &other.args) {
+            args.recover_fill(other_args);
+        }
         self.container.recover_fill(&other.container);
     }
 
@@ -453,7 +789,10 @@ impl CobSceneMacroCall
         resolve_mode: SceneResolveMode,
     ) -> Result<Vec<CobSceneLayerEntry>, String>
     {
-        // Resolve the content.
+        // Resolve the arguments and content.
+        if let Some(args) = &mut self.args {
+            args.resolve(&resolver.loadables)?;
+        }
         self.container.resolve(resolver, resolve_mode)?;
 
         // Expand the macro.
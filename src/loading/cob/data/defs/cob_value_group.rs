@@ -38,10 +38,10 @@ impl CobValueGroupEntry
                 return Ok((Some(Self::Value(value)), next_fill, remaining));
             }
             (CobMapKVParseResult::KeyNoValue(CobMapKey::FieldName { name, .. }), _, _) => {
-                tracing::warn!("failed parsing value group entry at {}; found field name without value: {} (use \
-                    key:value syntax)",
-                    get_location(content).as_str(), name.as_str());
-                return Err(span_verify_error(content));
+                return Err(span_diagnostic_error(
+                    content,
+                    format!("expected ':' after map key \"{}\" (use key:value syntax)", name.as_str()),
+                ));
             }
             (CobMapKVParseResult::Failure, fill, _) => fill,
         };
@@ -120,9 +120,10 @@ impl CobValueGroup
                 (Some(entry), next_fill, after_entry) => {
                     if entries.len() > 0 {
                         if fill_len == 0 {
-                            tracing::warn!("failed parsing value group at {}; entry #{} is not preceded by fill/whitespace",
-                                get_location(content), entries.len() + 1);
-                            return Err(span_verify_error(content));
+                            return Err(span_diagnostic_error(
+                                remaining,
+                                format!("value group entry #{} is not preceded by whitespace", entries.len() + 1),
+                            ));
                         }
                     }
                     entries.push(entry);
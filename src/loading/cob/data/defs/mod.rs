@@ -1,7 +1,11 @@
 mod cob_constant;
+mod cob_data_macro;
+mod cob_loadable_macro;
 mod cob_scene_macro;
 mod cob_value_group;
 
 pub use cob_constant::*;
+pub use cob_data_macro::*;
+pub use cob_loadable_macro::*;
 pub use cob_scene_macro::*;
 pub use cob_value_group::*;
@@ -1,3 +1,4 @@
+mod cob_condition;
 mod cob_file;
 mod cob_fill;
 mod cob_generics;
@@ -7,6 +8,7 @@ mod defs;
 mod ser;
 mod value;
 
+pub use cob_condition::*;
 pub use cob_file::*;
 pub use cob_fill::*;
 pub use cob_generics::*;
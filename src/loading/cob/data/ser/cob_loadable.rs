@@ -1,6 +1,7 @@
 use serde::ser::Impossible;
 use serde::Serialize;
 
+use super::cob_value::try_rect_shorthand;
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -491,6 +492,12 @@ impl serde::ser::SerializeStruct for SerializeStruct
                 id: self.name.try_into()?,
                 variant: CobLoadableVariant::Unit,
             })
+        } else if let Some(shorthand) = try_rect_shorthand(&self.vec) {
+            Ok(CobLoadable {
+                fill: CobFill::default(),
+                id: self.name.try_into()?,
+                variant: CobLoadableVariant::Tuple(CobTuple::from(shorthand)),
+            })
         } else {
             Ok(CobLoadable {
                 fill: CobFill::default(),
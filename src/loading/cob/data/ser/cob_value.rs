@@ -402,6 +402,39 @@ impl serde::ser::SerializeMap for SerializeMap
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Converts the fields of a rect type (e.g. `UiRect`) to the values of the shortest CSS-style shorthand: `4px` if
+/// all sides are equal, `4px 8px` if vertical and horizontal sides are equal, and `1px 2px 3px 4px` otherwise.
+///
+/// Applies to any struct whose fields match [`RECT_SHORTHAND_FIELDS`], since any such struct can be parsed from
+/// the shorthand. Returns `None` if the fields aren't all `Val`s or don't match [`RECT_SHORTHAND_FIELDS`].
+pub(crate) fn try_rect_shorthand(entries: &[CobMapEntry]) -> Option<Vec<CobValue>>
+{
+    if entries.len() != RECT_SHORTHAND_FIELDS.len() {
+        return None;
+    }
+    let mut sides: [Option<&CobValue>; 4] = [None; 4];
+    for entry in entries.iter() {
+        let CobMapEntry::KeyValue(keyval) = entry else { return None };
+        let CobMapKey::FieldName { name, .. } = &keyval.key else { return None };
+        let idx = RECT_SHORTHAND_FIELDS
+            .iter()
+            .position(|field| name == *field)?;
+        let CobValue::Builtin(CobBuiltin::Val { .. }) = &keyval.value else { return None };
+        sides[idx] = Some(&keyval.value);
+    }
+    let [Some(top), Some(right), Some(bottom), Some(left)] = sides else { return None };
+
+    if top == right && top == bottom && top == left {
+        return Some(vec![top.clone()]);
+    }
+    if top == bottom && right == left {
+        return Some(vec![top.clone(), right.clone()]);
+    }
+    Some(vec![top.clone(), right.clone(), bottom.clone(), left.clone()])
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub struct SerializeStruct
 {
     vec: Vec<CobMapEntry>,
@@ -430,6 +463,12 @@ impl serde::ser::SerializeStruct for SerializeStruct
         // all structs with no members are unit structs...
         if self.vec.len() == 0 {
             Ok(CobValue::Tuple(CobTuple::from(vec![])))
+        } else if let Some(mut shorthand) = try_rect_shorthand(&self.vec) {
+            if shorthand.len() == 1 {
+                Ok(shorthand.pop().unwrap())
+            } else {
+                Ok(CobValue::Tuple(CobTuple::from(shorthand)))
+            }
         } else {
             Ok(CobValue::Map(CobMap::from(self.vec)))
         }
@@ -51,9 +51,10 @@ impl CobArray
                 (Some(entry), next_fill, after_entry) => {
                     if entries.len() > 0 {
                         if fill_len == 0 {
-                            tracing::warn!("failed parsing array at {}; entry #{} is not preceded by fill/whitespace",
-                                get_location(content), entries.len() + 1);
-                            return Err(span_verify_error(content));
+                            return Err(span_diagnostic_error(
+                                remaining,
+                                format!("array entry #{} is not preceded by whitespace", entries.len() + 1),
+                            ));
                         }
                     }
                     entries.push(entry);
@@ -105,9 +105,10 @@ impl CobHexColor
 
         let len = start_len.saturating_sub(end_len);
         if len != 8 && len != 6 {
-            tracing::warn!("failed parsing hex color at {}; hex length is {} but expected 6 or 8",
-                get_location(content), len);
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                content,
+                format!("invalid hex color; expected 6 or 8 hex digits but found {len}"),
+            ));
         }
 
         let mut color = Srgba::default();
@@ -148,10 +149,256 @@ impl TryFrom<Srgba> for CobHexColor
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Color spaces that can be written as color functions, e.g. `rgb(1 0.5 0)`.
+///
+/// Components use the same ranges as bevy's color constructors (e.g. [`Color::hsla`]), so most are in the range
+/// `0.0` to `1.0` and hues are in degrees.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CobColorSpace
+{
+    /// `rgb(red green blue)`: Maps to [`Color::Srgba`].
+    Rgb,
+    /// `rgba(red green blue alpha)`: Maps to [`Color::Srgba`].
+    Rgba,
+    /// `hsl(hue saturation lightness)`: Maps to [`Color::Hsla`].
+    Hsl,
+    /// `hsla(hue saturation lightness alpha)`: Maps to [`Color::Hsla`].
+    Hsla,
+    /// `oklch(lightness chroma hue)`: Maps to [`Color::Oklcha`].
+    Oklch,
+    /// `oklcha(lightness chroma hue alpha)`: Maps to [`Color::Oklcha`].
+    Oklcha,
+}
+
+impl CobColorSpace
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Rgb => "rgb",
+            Self::Rgba => "rgba",
+            Self::Hsl => "hsl",
+            Self::Hsla => "hsla",
+            Self::Oklch => "oklch",
+            Self::Oklcha => "oklcha",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name {
+            "rgb" => Some(Self::Rgb),
+            "rgba" => Some(Self::Rgba),
+            "hsl" => Some(Self::Hsl),
+            "hsla" => Some(Self::Hsla),
+            "oklch" => Some(Self::Oklch),
+            "oklcha" => Some(Self::Oklcha),
+            _ => None,
+        }
+    }
+
+    /// Gets the names of this color space's components, in the order they are written.
+    pub fn params(&self) -> &'static [&'static str]
+    {
+        match self {
+            Self::Rgb => &["red", "green", "blue"],
+            Self::Rgba => &["red", "green", "blue", "alpha"],
+            Self::Hsl => &["hue", "saturation", "lightness"],
+            Self::Hsla => &["hue", "saturation", "lightness", "alpha"],
+            Self::Oklch => &["lightness", "chroma", "hue"],
+            Self::Oklcha => &["lightness", "chroma", "hue", "alpha"],
+        }
+    }
+
+    /// Gets the [`Color`] variant this color space maps to.
+    pub fn variant(&self) -> &'static str
+    {
+        match self {
+            Self::Rgb | Self::Rgba => "Srgba",
+            Self::Hsl | Self::Hsla => "Hsla",
+            Self::Oklch | Self::Oklcha => "Oklcha",
+        }
+    }
+
+    /// Makes a color from components. The alpha defaults to `1.0` if there are only three components.
+    pub fn to_color(&self, components: &[f32]) -> Color
+    {
+        let get = |idx: usize, default: f32| components.get(idx).copied().unwrap_or(default);
+        let (a, b, c, alpha) = (get(0, 0.), get(1, 0.), get(2, 0.), get(3, 1.));
+        match self {
+            Self::Rgb | Self::Rgba => Color::srgba(a, b, c, alpha),
+            Self::Hsl | Self::Hsla => Color::hsla(a, b, c, alpha),
+            Self::Oklch | Self::Oklcha => Color::oklcha(a, b, c, alpha),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A color written as a color function, e.g. `rgb(1 0.5 0)` or `hsla(210 0.5 0.4 0.8)`.
+///
+/// Only number literals are accepted as components. Color functions with other arguments (e.g. constants) are
+/// parsed as expressions and evaluated to hex colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobColorLiteral
+{
+    pub fill: CobFill,
+    pub space: CobColorSpace,
+    pub components: Vec<CobNumber>,
+    /// Fill before ending `)`.
+    pub end_fill: CobFill,
+}
+
+impl CobColorLiteral
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.space.as_str().as_bytes())?;
+        writer.write_bytes("(".as_bytes())?;
+        for (idx, component) in self.components.iter().enumerate() {
+            if idx == 0 {
+                component.write_to(writer)?;
+            } else {
+                component.write_to_with_space(writer, " ")?;
+            }
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, name)) = snake_identifier(content) else { return Ok((None, fill, content)) };
+        let Some(space) = CobColorSpace::from_name(name.fragment()) else { return Ok((None, fill, content)) };
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(remaining) else { return Ok((None, fill, content)) };
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut components = vec![];
+
+        let end_fill = loop {
+            if let Ok((after_end, _)) = char::<_, ()>(')').parse(remaining) {
+                remaining = after_end;
+                break item_fill;
+            }
+
+            // Fall back to parsing an expression if a component isn't a number literal.
+            let (Some(component), next_fill, after_component) = CobNumber::try_parse(item_fill, remaining)? else {
+                return Ok((None, fill, content));
+            };
+            if next_fill.len() == 0 && char::<_, ()>(')').parse(after_component).is_err() {
+                return Ok((None, fill, content));
+            }
+            components.push(component);
+            item_fill = next_fill;
+            remaining = after_component;
+        };
+
+        let params = space.params();
+        if components.len() != params.len() {
+            return Err(span_diagnostic_error(
+                content,
+                format!(
+                    "{}() expects {} components ({}) but found {}",
+                    space.as_str(),
+                    params.len(),
+                    params.join(" "),
+                    components.len()
+                ),
+            ));
+        }
+
+        let (next_fill, remaining) = CobFill::parse(remaining);
+        Ok((Some(Self { fill, space, components, end_fill }), next_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.fill.recover(&other.fill);
+        for (component, other_component) in self.components.iter_mut().zip(other.components.iter()) {
+            component.recover_fill(other_component);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    /// Gets the components as `f32`s. Components that can't be converted are set to `0.0`.
+    pub fn components_f32(&self) -> Vec<f32>
+    {
+        self.components
+            .iter()
+            .map(|c| c.number.as_f32_lossy().unwrap_or_default())
+            .collect()
+    }
+
+    pub fn color(&self) -> Color
+    {
+        self.space.to_color(&self.components_f32())
+    }
+
+    /// Makes a color literal from the components of a [`Color`] variant's serialized map.
+    ///
+    /// The alpha is omitted if it equals `1.0`.
+    fn from_components(space: CobColorSpace, components: Vec<CobNumberValue>) -> Self
+    {
+        let mut components: Vec<CobNumber> = components
+            .into_iter()
+            .map(|number| CobNumber { fill: CobFill::default(), number })
+            .collect();
+        let space = match (space, components.pop()) {
+            (CobColorSpace::Rgba, Some(alpha)) if alpha.number.as_f64() == Some(1.) => CobColorSpace::Rgb,
+            (CobColorSpace::Hsla, Some(alpha)) if alpha.number.as_f64() == Some(1.) => CobColorSpace::Hsl,
+            (CobColorSpace::Oklcha, Some(alpha)) if alpha.number.as_f64() == Some(1.) => CobColorSpace::Oklch,
+            (space, alpha) => {
+                components.extend(alpha);
+                space
+            }
+        };
+        Self {
+            fill: CobFill::default(),
+            space,
+            components,
+            end_fill: CobFill::default(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Field names of rect types like [`UiRect`], in the order they are written in CSS-style shorthand.
+///
+/// Structs with exactly these fields can be written as one, two, or four [`Val`]s: `(4px)` for all sides,
+/// `(4px 8px)` for vertical/horizontal, and `(1px 2px 3px 4px)` for top/right/bottom/left.
+pub(crate) const RECT_SHORTHAND_FIELDS: [&str; 4] = ["top", "right", "bottom", "left"];
+
+/// Checks if a struct's fields match [`RECT_SHORTHAND_FIELDS`] (in any order).
+pub(crate) fn is_rect_shorthand_struct<'a>(fields: impl IntoIterator<Item = &'a str>) -> bool
+{
+    let mut count = 0;
+    for field in fields {
+        if !RECT_SHORTHAND_FIELDS.contains(&field) {
+            return false;
+        }
+        count += 1;
+    }
+    count == RECT_SHORTHAND_FIELDS.len()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CobBuiltin
 {
     Color(CobHexColor),
+    /// Color functions like `rgb(1 0.5 0)` and `hsl(210 0.5 0.4)`.
+    ColorLiteral(CobColorLiteral),
+    /// Palette colors like `css::alice_blue`.
+    NamedColor(CobNamedColor),
     Val
     {
         fill: CobFill,
@@ -174,6 +421,12 @@ impl CobBuiltin
             Self::Color(color) => {
                 color.write_to_with_space(writer, space)?;
             }
+            Self::ColorLiteral(color) => {
+                color.write_to_with_space(writer, space)?;
+            }
+            Self::NamedColor(color) => {
+                color.write_to_with_space(writer, space)?;
+            }
             Self::Val { fill, number, val } => {
                 fill.write_to_or_else(writer, space)?;
                 if let Some(number) = number {
@@ -217,6 +470,22 @@ impl CobBuiltin
             (None, fill, _) => fill,
         };
 
+        // Color function
+        let fill = match CobColorLiteral::try_parse(fill, content)? {
+            (Some(color), next_fill, remaining) => {
+                return Ok((Some(Self::ColorLiteral(color)), next_fill, remaining))
+            }
+            (None, fill, _) => fill,
+        };
+
+        // Palette color
+        let fill = match CobNamedColor::try_parse(fill, content)? {
+            (Some(color), next_fill, remaining) => {
+                return Ok((Some(Self::NamedColor(color)), next_fill, remaining))
+            }
+            (None, fill, _) => fill,
+        };
+
         // Val::Auto
         if let Ok((remaining, val)) =
             value(Val::Auto, verify(snake_identifier, |i| *i.fragment() == "auto")).parse(content)
@@ -261,6 +530,17 @@ impl CobBuiltin
         ))
     }
 
+    /// Gets the color if this is a color builtin.
+    pub fn color(&self) -> Option<Color>
+    {
+        match self {
+            Self::Color(color) => Some(color.color.into()),
+            Self::ColorLiteral(color) => Some(color.color()),
+            Self::NamedColor(color) => Some(color.color.into()),
+            Self::Val { .. } => None,
+        }
+    }
+
     pub fn try_from_unit_variant(typename: &str, variant: &str) -> CobResult<Option<Self>>
     {
         if typename == "Val" && variant == "Auto" {
@@ -277,32 +557,47 @@ impl CobBuiltin
     /// The value should not contain any macros/constants.
     pub fn try_from_newtype_variant(typename: &str, variant: &str, value: &CobValue) -> CobResult<Option<Self>>
     {
-        if typename == "Color" && variant == "Srgba" {
+        if typename == "Color" {
+            let space = match variant {
+                "Srgba" => CobColorSpace::Rgba,
+                "Hsla" => CobColorSpace::Hsla,
+                "Oklcha" => CobColorSpace::Oklcha,
+                _ => return Ok(None),
+            };
+
+            // Collect components in the order they are written.
             let CobValue::Map(CobMap { entries, .. }) = value else { return Ok(None) };
-            let mut color = Srgba::default();
+            let params = space.params();
+            let mut components: Vec<Option<CobNumberValue>> = vec![None; params.len()];
             for entry in entries.iter() {
                 let CobMapEntry::KeyValue(keyval) = entry else { return Err(CobError::MalformedBuiltin) };
                 let CobMapKey::FieldName { fill: _, name } = &keyval.key else {
                     return Err(CobError::MalformedBuiltin);
                 };
                 let CobValue::Number(num) = &keyval.value else { return Ok(None) };
-                let Some(float) = num.number.as_f64() else { return Ok(None) };
-                let value = float as f32;
-
-                if name == "red" {
-                    color.red = value;
-                } else if name == "green" {
-                    color.green = value;
-                } else if name == "blue" {
-                    color.blue = value;
-                } else if name == "alpha" {
-                    color.alpha = value;
-                } else {
-                    return Ok(None);
+                let Some(idx) = params.iter().position(|p| name == *p) else { return Ok(None) };
+                components[idx] = Some(num.number);
+            }
+            let Some(components) = components.into_iter().collect::<Option<Vec<_>>>() else { return Ok(None) };
+
+            // Srgba colors are written as hex when possible.
+            if space == CobColorSpace::Rgba {
+                let mut color = Srgba::default();
+                for (component, number) in [&mut color.red, &mut color.green, &mut color.blue, &mut color.alpha]
+                    .into_iter()
+                    .zip(components.iter())
+                {
+                    let Some(float) = number.as_f64() else { return Ok(None) };
+                    *component = float as f32;
+                }
+                if let Ok(color) = CobHexColor::try_from(color) {
+                    return Ok(Some(Self::Color(color)));
                 }
             }
 
-            return Ok(CobHexColor::try_from(color).map(|c| Self::Color(c)).ok());
+            return Ok(Some(Self::ColorLiteral(CobColorLiteral::from_components(
+                space, components,
+            ))));
         }
 
         if typename == "Val" {
@@ -336,6 +631,12 @@ impl CobBuiltin
             (Self::Color(color), Self::Color(other_color)) => {
                 color.recover_fill(other_color);
             }
+            (Self::ColorLiteral(color), Self::ColorLiteral(other_color)) => {
+                color.recover_fill(other_color);
+            }
+            (Self::NamedColor(color), Self::NamedColor(other_color)) => {
+                color.recover_fill(other_color);
+            }
             (Self::Val { fill, .. }, Self::Val { fill: other_fill, .. }) => {
                 fill.recover(&other_fill);
             }
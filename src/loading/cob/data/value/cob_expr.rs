@@ -0,0 +1,725 @@
+use bevy::prelude::*;
+use nom::character::complete::{char, one_of};
+use nom::Parser;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the number in a `Val` along with a constructor for the `Val`'s unit.
+fn val_parts(val: Val) -> Option<(f32, fn(f32) -> Val)>
+{
+    match val {
+        Val::Auto => None,
+        Val::Px(num) => Some((num, Val::Px)),
+        Val::Percent(num) => Some((num, Val::Percent)),
+        Val::Vw(num) => Some((num, Val::Vw)),
+        Val::Vh(num) => Some((num, Val::Vh)),
+        Val::VMin(num) => Some((num, Val::VMin)),
+        Val::VMax(num) => Some((num, Val::VMax)),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Intermediate value used while evaluating expressions.
+#[derive(Debug, Copy, Clone)]
+enum ExprValue
+{
+    Number(CobNumberValue),
+    Val(Val),
+    Color(Srgba),
+}
+
+impl ExprValue
+{
+    fn from_value(value: &CobValue) -> Result<Self, String>
+    {
+        match value {
+            CobValue::Number(num) => Ok(Self::Number(num.number)),
+            CobValue::Builtin(CobBuiltin::Val { val, .. }) => Ok(Self::Val(*val)),
+            CobValue::Builtin(
+                builtin @ (CobBuiltin::Color(_) | CobBuiltin::ColorLiteral(_) | CobBuiltin::NamedColor(_)),
+            ) => Ok(Self::Color(builtin.color().map(Srgba::from).unwrap_or_default())),
+            _ => {
+                let mut buff = Vec::<u8>::default();
+                let _ = value.write_to(&mut DefaultRawSerializer::new(&mut buff));
+                Err(format!(
+                    "{:?} can't be used in an expression; only numbers, vals (e.g. 10px), and colors are supported",
+                    String::from_utf8_lossy(&buff).trim()
+                ))
+            }
+        }
+    }
+
+    fn into_value(self, fill: CobFill) -> CobValue
+    {
+        match self {
+            Self::Number(number) => CobValue::Number(CobNumber { fill, number }),
+            Self::Val(val) => CobValue::Builtin(CobBuiltin::Val {
+                fill,
+                number: val_parts(val).map(|(num, _)| CobNumberValue::from(num)),
+                val,
+            }),
+            Self::Color(color) => CobValue::Builtin(CobBuiltin::Color(CobHexColor { fill, color })),
+        }
+    }
+
+    fn describe(&self) -> String
+    {
+        let mut buff = Vec::<u8>::default();
+        let _ = self
+            .into_value(CobFill::default())
+            .write_to(&mut DefaultRawSerializer::new(&mut buff));
+        String::from_utf8_lossy(&buff).into()
+    }
+
+    fn as_f64(&self, function: CobFunction) -> Result<f64, String>
+    {
+        match self {
+            Self::Number(num) => num.as_f64().ok_or_else(|| {
+                format!("{}() argument {} can't be converted to f64", function.as_str(), self.describe())
+            }),
+            _ => Err(format!("{}() expected a number but found {}", function.as_str(), self.describe())),
+        }
+    }
+
+    fn as_color(&self, function: CobFunction) -> Result<Srgba, String>
+    {
+        match self {
+            Self::Color(color) => Ok(*color),
+            _ => Err(format!("{}() expected a color but found {}", function.as_str(), self.describe())),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_number_op(lhs: CobNumberValue, op: CobCalcOp, rhs: CobNumberValue) -> Result<CobNumberValue, String>
+{
+    // Integer math is used when possible so integers stay integers.
+    let is_int = |num: &CobNumberValue| matches!(num, CobNumberValue::Uint(_) | CobNumberValue::Int(_));
+    if is_int(&lhs) && is_int(&rhs) {
+        if let (Some(a), Some(b)) = (lhs.as_i128(), rhs.as_i128()) {
+            let result = match op {
+                CobCalcOp::Add => a.checked_add(b),
+                CobCalcOp::Sub => a.checked_sub(b),
+                CobCalcOp::Mul => a.checked_mul(b),
+                CobCalcOp::Div => a
+                    .checked_rem(b)
+                    .filter(|rem| *rem == 0)
+                    .and_then(|_| a.checked_div(b)),
+            };
+            if let Some(result) = result {
+                return Ok(match result >= 0 {
+                    true => CobNumberValue::Uint(result as u128),
+                    false => CobNumberValue::Int(result),
+                });
+            }
+        }
+    }
+
+    let (Some(a), Some(b)) = (lhs.as_f64(), rhs.as_f64()) else {
+        return Err(String::from("number in expression can't be converted to f64"));
+    };
+    if op == CobCalcOp::Div && b == 0. {
+        return Err(String::from("division by zero"));
+    }
+    Ok(CobNumberValue::Float64(op.apply(a, b)))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_op(lhs: ExprValue, op: CobCalcOp, rhs: ExprValue) -> Result<ExprValue, String>
+{
+    let err = || {
+        Err(format!(
+            "can't evaluate {} {} {}",
+            lhs.describe(),
+            op.as_str(),
+            rhs.describe()
+        ))
+    };
+
+    match (lhs, rhs) {
+        (ExprValue::Number(a), ExprValue::Number(b)) => Ok(ExprValue::Number(apply_number_op(a, op, b)?)),
+        (ExprValue::Val(a), ExprValue::Val(b)) => {
+            let (Some((a_num, a_unit)), Some((b_num, _))) = (val_parts(a), val_parts(b)) else { return err() };
+            if std::mem::discriminant(&a) != std::mem::discriminant(&b) {
+                return Err(format!(
+                    "can't evaluate {} {} {}; mixed-unit calc is not supported because Val only stores one \
+                    unit (use a single unit, or set the value in code)",
+                    lhs.describe(),
+                    op.as_str(),
+                    rhs.describe()
+                ));
+            }
+            match op {
+                CobCalcOp::Add | CobCalcOp::Sub => Ok(ExprValue::Val(a_unit(op.apply(a_num, b_num)))),
+                CobCalcOp::Div if b_num != 0. => {
+                    Ok(ExprValue::Number(CobNumberValue::Float64((a_num / b_num) as f64)))
+                }
+                CobCalcOp::Div => Err(String::from("division by zero")),
+                CobCalcOp::Mul => err(),
+            }
+        }
+        (ExprValue::Val(a), ExprValue::Number(b)) => {
+            let (Some((a_num, a_unit)), Some(b_num)) = (val_parts(a), b.as_f32_lossy()) else { return err() };
+            if op == CobCalcOp::Div && b_num == 0. {
+                return Err(String::from("division by zero"));
+            }
+            Ok(ExprValue::Val(a_unit(op.apply(a_num, b_num))))
+        }
+        (ExprValue::Number(a), ExprValue::Val(b)) => {
+            let (Some(a_num), Some((b_num, b_unit))) = (a.as_f32_lossy(), val_parts(b)) else { return err() };
+            if op == CobCalcOp::Div {
+                return err();
+            }
+            Ok(ExprValue::Val(b_unit(op.apply(a_num, b_num))))
+        }
+        (ExprValue::Color(_), _) | (_, ExprValue::Color(_)) => Err(format!(
+            "can't evaluate {} {} {}; use color functions like lighten() or mix() to modify colors",
+            lhs.describe(),
+            op.as_str(),
+            rhs.describe()
+        )),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Arithmetic operator in a [`CobCalc`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CobCalcOp
+{
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl CobCalcOp
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+        }
+    }
+
+    fn from_char(op: char) -> Self
+    {
+        match op {
+            '+' => Self::Add,
+            '-' => Self::Sub,
+            '*' => Self::Mul,
+            _ => Self::Div,
+        }
+    }
+
+    /// Multiplication and division are applied before addition and subtraction.
+    fn precedence(&self) -> u8
+    {
+        match self {
+            Self::Add | Self::Sub => 0,
+            Self::Mul | Self::Div => 1,
+        }
+    }
+
+    fn apply<T>(&self, a: T, b: T) -> T
+    where
+        T: std::ops::Add<Output = T>
+            + std::ops::Sub<Output = T>
+            + std::ops::Mul<Output = T>
+            + std::ops::Div<Output = T>,
+    {
+        match self {
+            Self::Add => a + b,
+            Self::Sub => a - b,
+            Self::Mul => a * b,
+            Self::Div => a / b,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An entry in a [`CobCalc`]. Operands and operators alternate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CobCalcEntry
+{
+    /// A number, val, or color, or something that resolves to one (e.g. a constant).
+    Operand(CobValue),
+    Op
+    {
+        fill: CobFill, op: CobCalcOp
+    },
+    /// Parenthesized sub-expression, e.g. `($a + $b)`.
+    Group
+    {
+        fill: CobFill,
+        entries: Vec<CobCalcEntry>,
+        /// Fill before the closing `)`.
+        end_fill: CobFill,
+    },
+}
+
+impl CobCalcEntry
+{
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        match self {
+            Self::Operand(value) => {
+                value.write_to_with_space(writer, space)?;
+            }
+            Self::Op { fill, op } => {
+                fill.write_to_or_else(writer, space)?;
+                writer.write_bytes(op.as_str().as_bytes())?;
+            }
+            Self::Group { fill, entries, end_fill } => {
+                fill.write_to_or_else(writer, space)?;
+                writer.write_bytes("(".as_bytes())?;
+                CobCalc::write_entries(entries, writer)?;
+                end_fill.write_to(writer)?;
+                writer.write_bytes(")".as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        match (self, other) {
+            (Self::Operand(value), Self::Operand(other_value)) => {
+                value.recover_fill(other_value);
+            }
+            (Self::Op { fill, .. }, Self::Op { fill: other_fill, .. }) => {
+                fill.recover(other_fill);
+            }
+            (
+                Self::Group { fill, entries, end_fill },
+                Self::Group {
+                    fill: other_fill,
+                    entries: other_entries,
+                    end_fill: other_end_fill,
+                },
+            ) => {
+                fill.recover(other_fill);
+                for (entry, other_entry) in entries.iter_mut().zip(other_entries.iter()) {
+                    entry.recover_fill(other_entry);
+                }
+                end_fill.recover(other_end_fill);
+            }
+            _ => (),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Arithmetic expression, e.g. `calc($gap * 2)` or `calc(($width - 10px) / 2)`.
+///
+/// Operands can be numbers and vals (e.g. `10px`, `50%`). Unitless numbers can be combined with vals, in which
+/// case the result has the val's unit (e.g. `calc(10px + 2)` is `12px`). Mixed-unit calc (e.g. `calc(100% - 10px)`)
+/// is not supported, since `Val` only stores one unit. It is reported as an error when the expression is evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobCalc
+{
+    pub start_fill: CobFill,
+    pub entries: Vec<CobCalcEntry>,
+    /// Fill before the closing `)`.
+    pub end_fill: CobFill,
+}
+
+impl CobCalc
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("calc(".as_bytes())?;
+        Self::write_entries(&self.entries, writer)?;
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes(")".as_bytes())?;
+        Ok(())
+    }
+
+    fn write_entries(entries: &[CobCalcEntry], writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        // Operands and operators don't need to be separated by whitespace, so no fallback space is used.
+        for entry in entries.iter() {
+            entry.write_to_with_space(writer, "")?;
+        }
+        Ok(())
+    }
+
+    /// Parses entries until a closing `)`, which is consumed.
+    fn parse_entries(mut fill: CobFill, mut content: Span)
+        -> Result<(Vec<CobCalcEntry>, CobFill, Span), SpanError>
+    {
+        let mut entries = vec![];
+
+        loop {
+            // Operand
+            if let Ok((remaining, _)) = char::<_, ()>('(').parse(content) {
+                let (inner_fill, remaining) = CobFill::parse(remaining);
+                let (inner, end_fill, remaining) = rc(remaining, move |rm| Self::parse_entries(inner_fill, rm))?;
+                entries.push(CobCalcEntry::Group { fill, entries: inner, end_fill });
+                (fill, content) = CobFill::parse(remaining);
+            } else {
+                match rc(content, move |c| CobValue::try_parse(fill, c))? {
+                    (Some(value), next_fill, remaining) => {
+                        entries.push(CobCalcEntry::Operand(value));
+                        (fill, content) = (next_fill, remaining);
+                    }
+                    (None, _, _) => {
+                        return Err(span_diagnostic_error(
+                            content,
+                            "expected a number, val (e.g. 10px), color, or constant in calc expression",
+                        ));
+                    }
+                }
+            }
+
+            // End
+            if let Ok((remaining, _)) = char::<_, ()>(')').parse(content) {
+                return Ok((entries, fill, remaining));
+            }
+
+            // Operator
+            let Ok((remaining, op)) = one_of::<_, _, ()>("+-*/").parse(content) else {
+                return Err(span_diagnostic_error(
+                    content,
+                    "expected an operator (+, -, *, /) or ')' in calc expression",
+                ));
+            };
+            entries.push(CobCalcEntry::Op { fill, op: CobCalcOp::from_char(op) });
+            (fill, content) = CobFill::parse(remaining);
+        }
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, name)) = snake_identifier(content) else { return Ok((None, start_fill, content)) };
+        if *name.fragment() != "calc" {
+            return Ok((None, start_fill, content));
+        }
+        let Ok((remaining, _)) = char::<_, ()>('(').parse(remaining) else {
+            return Ok((None, start_fill, content));
+        };
+
+        let (fill, remaining) = CobFill::parse(remaining);
+        let (entries, end_fill, remaining) = rc(remaining, move |rm| Self::parse_entries(fill, rm))?;
+        let (post_fill, remaining) = CobFill::parse(remaining);
+        Ok((Some(Self { start_fill, entries, end_fill }), post_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.recover_fill(other_entry);
+        }
+        self.end_fill.recover(&other.end_fill);
+    }
+
+    fn resolve_entries(entries: &mut [CobCalcEntry], resolver: &CobLoadableResolver) -> Result<(), String>
+    {
+        for entry in entries.iter_mut() {
+            match entry {
+                CobCalcEntry::Operand(value) => {
+                    if value.resolve(resolver)?.is_some() {
+                        return Err(String::from("value groups can't be used in calc expressions"));
+                    }
+                }
+                CobCalcEntry::Op { .. } => (),
+                CobCalcEntry::Group { entries, .. } => Self::resolve_entries(entries, resolver)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_entries(entries: &[CobCalcEntry]) -> Result<Option<ExprValue>, String>
+    {
+        // Operator-precedence evaluation with a value stack and an operator stack.
+        let mut values: Vec<ExprValue> = vec![];
+        let mut ops: Vec<CobCalcOp> = vec![];
+
+        let reduce = |values: &mut Vec<ExprValue>, ops: &mut Vec<CobCalcOp>| -> Result<(), String> {
+            let (Some(op), Some(rhs), Some(lhs)) = (ops.pop(), values.pop(), values.pop()) else {
+                return Err(String::from("malformed calc expression"));
+            };
+            values.push(apply_op(lhs, op, rhs)?);
+            Ok(())
+        };
+
+        for entry in entries.iter() {
+            match entry {
+                CobCalcEntry::Operand(value) => match value {
+                    // Unresolved data macro parameters and expressions that contain them.
+                    CobValue::Constant(_) | CobValue::Expr(_) => return Ok(None),
+                    _ => values.push(ExprValue::from_value(value)?),
+                },
+                CobCalcEntry::Op { op, .. } => {
+                    while ops
+                        .last()
+                        .is_some_and(|prev| prev.precedence() >= op.precedence())
+                    {
+                        reduce(&mut values, &mut ops)?;
+                    }
+                    ops.push(*op);
+                }
+                CobCalcEntry::Group { entries, .. } => {
+                    let Some(value) = Self::evaluate_entries(entries)? else { return Ok(None) };
+                    values.push(value);
+                }
+            }
+        }
+        while !ops.is_empty() {
+            reduce(&mut values, &mut ops)?;
+        }
+
+        match (values.pop(), values.is_empty()) {
+            (Some(value), true) => Ok(Some(value)),
+            _ => Err(String::from("malformed calc expression")),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Built-in function that can be used in a [`CobFunctionCall`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CobFunction
+{
+    /// `lighten(color amount)`: Increases a color's lightness by `amount` (0.0 to 1.0).
+    Lighten,
+    /// `darken(color amount)`: Decreases a color's lightness by `amount` (0.0 to 1.0).
+    Darken,
+    /// `alpha(color alpha)`: Replaces a color's alpha.
+    Alpha,
+    /// `mix(a b t)`: Interpolates between two colors, where `t = 0.0` is `a` and `t = 1.0` is `b`.
+    Mix,
+    /// Color functions like `rgb(r g b)` with non-literal arguments (e.g. `rgb($r 0 0)`).
+    Color(CobColorSpace),
+}
+
+impl CobFunction
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Lighten => "lighten",
+            Self::Darken => "darken",
+            Self::Alpha => "alpha",
+            Self::Mix => "mix",
+            Self::Color(space) => space.as_str(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self>
+    {
+        match name {
+            "lighten" => Some(Self::Lighten),
+            "darken" => Some(Self::Darken),
+            "alpha" => Some(Self::Alpha),
+            "mix" => Some(Self::Mix),
+            _ => CobColorSpace::from_name(name).map(Self::Color),
+        }
+    }
+
+    fn params(&self) -> &'static [&'static str]
+    {
+        match self {
+            Self::Lighten | Self::Darken => &["color", "amount"],
+            Self::Alpha => &["color", "alpha"],
+            Self::Mix => &["a", "b", "t"],
+            Self::Color(space) => space.params(),
+        }
+    }
+
+    fn evaluate(&self, args: &[ExprValue]) -> Result<ExprValue, String>
+    {
+        let params = self.params();
+        if args.len() != params.len() {
+            return Err(format!(
+                "{}() expects {} argument(s) ({}) but {} were provided",
+                self.as_str(),
+                params.len(),
+                params.join(" "),
+                args.len()
+            ));
+        }
+
+        let color = match self {
+            Self::Lighten => args[0]
+                .as_color(*self)?
+                .lighter(args[1].as_f64(*self)? as f32),
+            Self::Darken => args[0]
+                .as_color(*self)?
+                .darker(args[1].as_f64(*self)? as f32),
+            Self::Alpha => args[0]
+                .as_color(*self)?
+                .with_alpha(args[1].as_f64(*self)? as f32),
+            Self::Mix => args[0]
+                .as_color(*self)?
+                .mix(&args[1].as_color(*self)?, args[2].as_f64(*self)? as f32),
+            Self::Color(space) => {
+                let components = args
+                    .iter()
+                    .map(|arg| arg.as_f64(*self).map(|num| num as f32))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Srgba::from(space.to_color(&components))
+            }
+        };
+        Ok(ExprValue::Color(color))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Invocation of a built-in function, e.g. `lighten($primary 0.1)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobFunctionCall
+{
+    pub start_fill: CobFill,
+    pub function: CobFunction,
+    /// Arguments are written like a tuple, and must abut the function name.
+    pub args: CobTuple,
+}
+
+impl CobFunctionCall
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.function.as_str().as_bytes())?;
+        self.args.write_to(writer)?;
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, name)) = snake_identifier(content) else { return Ok((None, start_fill, content)) };
+        let Some(function) = CobFunction::from_name(name.fragment()) else {
+            return Ok((None, start_fill, content));
+        };
+        let (Some(args), end_fill, remaining) = rc(remaining, |rm| CobTuple::try_parse(CobFill::default(), rm))?
+        else {
+            return Ok((None, start_fill, content));
+        };
+
+        Ok((Some(Self { start_fill, function, args }), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        self.args.recover_fill(&other.args);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An expression that is evaluated to a plain value when resolved.
+///
+/// Expressions are evaluated after constants and data macro calls inside them are resolved, so they can be used to
+/// derive values from constants (e.g. `$hover = lighten($primary 0.1)`). Expressions that contain data macro
+/// parameters are evaluated when the macro is expanded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CobExpr
+{
+    Calc(CobCalc),
+    Function(CobFunctionCall),
+}
+
+impl CobExpr
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        match self {
+            Self::Calc(calc) => calc.write_to_with_space(writer, space),
+            Self::Function(call) => call.write_to_with_space(writer, space),
+        }
+    }
+
+    pub fn try_parse(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let fill = match rc(content, move |c| CobCalc::try_parse(fill, c))? {
+            (Some(calc), next_fill, remaining) => return Ok((Some(Self::Calc(calc)), next_fill, remaining)),
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobFunctionCall::try_parse(fill, c))? {
+            (Some(call), next_fill, remaining) => return Ok((Some(Self::Function(call)), next_fill, remaining)),
+            (None, fill, _) => fill,
+        };
+
+        Ok((None, fill, content))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        match (self, other) {
+            (Self::Calc(calc), Self::Calc(other_calc)) => {
+                calc.recover_fill(other_calc);
+            }
+            (Self::Function(call), Self::Function(other_call)) => {
+                call.recover_fill(other_call);
+            }
+            _ => (),
+        }
+    }
+
+    pub fn start_fill(&self) -> &CobFill
+    {
+        match self {
+            Self::Calc(calc) => &calc.start_fill,
+            Self::Function(call) => &call.start_fill,
+        }
+    }
+
+    /// Resolves values inside the expression, then evaluates it.
+    ///
+    /// Returns `None` if the expression can't be evaluated yet because it contains data macro parameters.
+    pub fn resolve(&mut self, resolver: &CobLoadableResolver) -> Result<Option<CobValue>, String>
+    {
+        let result = match self {
+            Self::Calc(calc) => {
+                CobCalc::resolve_entries(&mut calc.entries, resolver)?;
+                CobCalc::evaluate_entries(&calc.entries)?
+            }
+            Self::Function(call) => {
+                call.args.resolve(resolver)?;
+                let mut args = Vec::with_capacity(call.args.entries.len());
+                for arg in call.args.entries.iter() {
+                    match arg {
+                        CobValue::Constant(_) | CobValue::Expr(_) => return Ok(None),
+                        _ => args.push(ExprValue::from_value(arg)?),
+                    }
+                }
+                Some(call.function.evaluate(&args)?)
+            }
+        };
+
+        Ok(result.map(|value| value.into_value(self.start_fill().clone())))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -151,8 +151,7 @@ impl CobMapKeyValue
         };
         let (value_fill, remaining) = CobFill::parse(remaining);
         let (Some(value), next_fill, remaining) = rc(remaining, |rm| CobValue::try_parse(value_fill, rm))? else {
-            tracing::warn!("failed parsing value for map entry at {}; no valid value found", get_location(remaining));
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(remaining, "expected a value after ':' in map entry"));
         };
         Ok((
             CobMapKVParseResult::Success(Self { key, semicolon_fill, value }),
@@ -375,9 +374,10 @@ impl CobMap
                 (CobMapEntryResult::Success(entry), next_fill, after_entry) => {
                     if entries.len() > 0 {
                         if fill_len == 0 {
-                            tracing::warn!("failed parsing map at {}; entry #{} is not preceded by fill/whitespace",
-                                get_location(content).as_str(), entries.len() + 1);
-                            return Err(span_verify_error(content));
+                            return Err(span_diagnostic_error(
+                                remaining,
+                                format!("map entry #{} is not preceded by whitespace", entries.len() + 1),
+                            ));
                         }
                     }
                     entries.push(entry);
@@ -385,16 +385,16 @@ impl CobMap
                     remaining = after_entry;
                 }
                 (CobMapEntryResult::UnusedValue(_), _, _) => {
-                    tracing::warn!("failed parsing map at {}; entry #{} is a value-like key without a value (use \
-                        key:value syntax)",
-                        get_location(content).as_str(), entries.len() + 1);
-                    return Err(span_verify_error(content));
+                    return Err(span_diagnostic_error(
+                        remaining,
+                        format!("expected ':' after map key in entry #{} (use key:value syntax)", entries.len() + 1),
+                    ));
                 }
-                (CobMapEntryResult::FieldFailure(name), _, _) => {
-                    tracing::warn!("failed parsing map at {}; entry #{} is a struct-field key \"{}\" without a value (use \
-                        key:value syntax)",
-                        get_location(content).as_str(), entries.len() + 1, name.as_str());
-                    return Err(span_verify_error(content));
+                (CobMapEntryResult::FieldFailure(name), _, key_start) => {
+                    return Err(span_diagnostic_error(
+                        key_start,
+                        format!("expected ':' after map key \"{}\" (use key:value syntax)", name.as_str()),
+                    ));
                 }
                 (CobMapEntryResult::Failure, end_fill, after_end) => {
                     remaining = after_end;
@@ -403,7 +403,9 @@ impl CobMap
             }
         };
 
-        let (remaining, _) = char('}').parse(remaining)?;
+        let Ok((remaining, _)) = char::<_, ()>('}').parse(remaining) else {
+            return Err(span_diagnostic_error(remaining, "expected a map entry or '}'"));
+        };
         let (post_fill, remaining) = CobFill::parse(remaining);
         Ok((Some(Self { start_fill, entries, end_fill }), post_fill, remaining))
     }
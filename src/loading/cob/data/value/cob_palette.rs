@@ -0,0 +1,538 @@
+use bevy::color::palettes::{basic, css, tailwind};
+use bevy::prelude::*;
+use nom::bytes::complete::tag;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Color palettes from [`bevy::color::palettes`] that can be referenced by name in COB files.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CobColorPalette
+{
+    /// See [`bevy::color::palettes::basic`].
+    Basic,
+    /// See [`bevy::color::palettes::css`].
+    Css,
+    /// See [`bevy::color::palettes::tailwind`].
+    Tailwind,
+}
+
+impl CobColorPalette
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Basic => "basic",
+            Self::Css => "css",
+            Self::Tailwind => "tailwind",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name {
+            "basic" => Some(Self::Basic),
+            "css" => Some(Self::Css),
+            "tailwind" => Some(Self::Tailwind),
+            _ => None,
+        }
+    }
+
+    /// Looks up a color by its snake-case name (e.g. `alice_blue` for [`css::ALICE_BLUE`]).
+    pub fn get(&self, name: &str) -> Option<Srgba>
+    {
+        match self {
+            Self::Basic => basic_color(name),
+            Self::Css => css_color(name),
+            Self::Tailwind => tailwind_color(name),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A named color from one of the built-in palettes, e.g. `css::alice_blue` or `tailwind::amber_500`.
+///
+/// Named colors are replaced with hex colors when re-serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobNamedColor
+{
+    pub fill: CobFill,
+    pub palette: CobColorPalette,
+    pub name: SmolStr,
+    pub color: Srgba,
+}
+
+impl CobNamedColor
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.write_to_with_space(writer, "")
+    }
+
+    pub fn write_to_with_space(&self, writer: &mut impl RawSerializer, space: &str) -> Result<(), std::io::Error>
+    {
+        self.fill.write_to_or_else(writer, space)?;
+        writer.write_bytes(self.palette.as_str().as_bytes())?;
+        writer.write_bytes("::".as_bytes())?;
+        writer.write_bytes(self.name.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, palette)) = snake_identifier(content) else { return Ok((None, fill, content)) };
+        let Some(palette) = CobColorPalette::from_name(palette.fragment()) else {
+            return Ok((None, fill, content));
+        };
+        let Ok((remaining, _)) = tag::<_, _, ()>("::").parse(remaining) else { return Ok((None, fill, content)) };
+        let Ok((after_name, name)) = snake_identifier(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("expected a color name after {}::", palette.as_str()),
+            ));
+        };
+        let Some(color) = palette.get(name.fragment()) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("unknown {} color {:?}", palette.as_str(), *name.fragment()),
+            ));
+        };
+
+        let (next_fill, remaining) = CobFill::parse(after_name);
+        Ok((
+            Some(Self { fill, palette, name: SmolStr::from(*name.fragment()), color }),
+            next_fill,
+            remaining,
+        ))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.fill.recover(&other.fill);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn basic_color(name: &str) -> Option<Srgba>
+{
+    let color = match name {
+        "aqua" => basic::AQUA,
+        "black" => basic::BLACK,
+        "blue" => basic::BLUE,
+        "fuchsia" => basic::FUCHSIA,
+        "gray" => basic::GRAY,
+        "green" => basic::GREEN,
+        "lime" => basic::LIME,
+        "maroon" => basic::MAROON,
+        "navy" => basic::NAVY,
+        "olive" => basic::OLIVE,
+        "purple" => basic::PURPLE,
+        "red" => basic::RED,
+        "silver" => basic::SILVER,
+        "teal" => basic::TEAL,
+        "white" => basic::WHITE,
+        "yellow" => basic::YELLOW,
+        _ => return None,
+    };
+    Some(color)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn css_color(name: &str) -> Option<Srgba>
+{
+    let color = match name {
+        "alice_blue" => css::ALICE_BLUE,
+        "antique_white" => css::ANTIQUE_WHITE,
+        "aqua" => css::AQUA,
+        "aquamarine" => css::AQUAMARINE,
+        "azure" => css::AZURE,
+        "beige" => css::BEIGE,
+        "bisque" => css::BISQUE,
+        "blanched_almond" => css::BLANCHED_ALMOND,
+        "blue_violet" => css::BLUE_VIOLET,
+        "brown" => css::BROWN,
+        "burlywood" => css::BURLYWOOD,
+        "cadet_blue" => css::CADET_BLUE,
+        "chartreuse" => css::CHARTREUSE,
+        "chocolate" => css::CHOCOLATE,
+        "coral" => css::CORAL,
+        "cornflower_blue" => css::CORNFLOWER_BLUE,
+        "cornsilk" => css::CORNSILK,
+        "crimson" => css::CRIMSON,
+        "dark_blue" => css::DARK_BLUE,
+        "dark_cyan" => css::DARK_CYAN,
+        "dark_goldenrod" => css::DARK_GOLDENROD,
+        "dark_gray" => css::DARK_GRAY,
+        "dark_green" => css::DARK_GREEN,
+        "dark_grey" => css::DARK_GREY,
+        "dark_khaki" => css::DARK_KHAKI,
+        "dark_magenta" => css::DARK_MAGENTA,
+        "dark_olivegreen" => css::DARK_OLIVEGREEN,
+        "dark_orange" => css::DARK_ORANGE,
+        "dark_orchid" => css::DARK_ORCHID,
+        "dark_red" => css::DARK_RED,
+        "dark_salmon" => css::DARK_SALMON,
+        "dark_sea_green" => css::DARK_SEA_GREEN,
+        "dark_slate_blue" => css::DARK_SLATE_BLUE,
+        "dark_slate_gray" => css::DARK_SLATE_GRAY,
+        "dark_slate_grey" => css::DARK_SLATE_GREY,
+        "dark_turquoise" => css::DARK_TURQUOISE,
+        "dark_violet" => css::DARK_VIOLET,
+        "deep_pink" => css::DEEP_PINK,
+        "deep_sky_blue" => css::DEEP_SKY_BLUE,
+        "dim_gray" => css::DIM_GRAY,
+        "dim_grey" => css::DIM_GREY,
+        "dodger_blue" => css::DODGER_BLUE,
+        "fire_brick" => css::FIRE_BRICK,
+        "floral_white" => css::FLORAL_WHITE,
+        "forest_green" => css::FOREST_GREEN,
+        "gainsboro" => css::GAINSBORO,
+        "ghost_white" => css::GHOST_WHITE,
+        "gold" => css::GOLD,
+        "goldenrod" => css::GOLDENROD,
+        "green_yellow" => css::GREEN_YELLOW,
+        "grey" => css::GREY,
+        "honeydew" => css::HONEYDEW,
+        "hot_pink" => css::HOT_PINK,
+        "indian_red" => css::INDIAN_RED,
+        "indigo" => css::INDIGO,
+        "ivory" => css::IVORY,
+        "khaki" => css::KHAKI,
+        "lavender" => css::LAVENDER,
+        "lavender_blush" => css::LAVENDER_BLUSH,
+        "lawn_green" => css::LAWN_GREEN,
+        "lemon_chiffon" => css::LEMON_CHIFFON,
+        "light_blue" => css::LIGHT_BLUE,
+        "light_coral" => css::LIGHT_CORAL,
+        "light_cyan" => css::LIGHT_CYAN,
+        "light_goldenrod_yellow" => css::LIGHT_GOLDENROD_YELLOW,
+        "light_gray" => css::LIGHT_GRAY,
+        "light_green" => css::LIGHT_GREEN,
+        "light_grey" => css::LIGHT_GREY,
+        "light_pink" => css::LIGHT_PINK,
+        "light_salmon" => css::LIGHT_SALMON,
+        "light_sea_green" => css::LIGHT_SEA_GREEN,
+        "light_sky_blue" => css::LIGHT_SKY_BLUE,
+        "light_slate_gray" => css::LIGHT_SLATE_GRAY,
+        "light_slate_grey" => css::LIGHT_SLATE_GREY,
+        "light_steel_blue" => css::LIGHT_STEEL_BLUE,
+        "light_yellow" => css::LIGHT_YELLOW,
+        "limegreen" => css::LIMEGREEN,
+        "linen" => css::LINEN,
+        "magenta" => css::MAGENTA,
+        "medium_aquamarine" => css::MEDIUM_AQUAMARINE,
+        "medium_blue" => css::MEDIUM_BLUE,
+        "medium_orchid" => css::MEDIUM_ORCHID,
+        "medium_purple" => css::MEDIUM_PURPLE,
+        "medium_sea_green" => css::MEDIUM_SEA_GREEN,
+        "medium_slate_blue" => css::MEDIUM_SLATE_BLUE,
+        "medium_spring_green" => css::MEDIUM_SPRING_GREEN,
+        "medium_turquoise" => css::MEDIUM_TURQUOISE,
+        "medium_violet_red" => css::MEDIUM_VIOLET_RED,
+        "midnight_blue" => css::MIDNIGHT_BLUE,
+        "mint_cream" => css::MINT_CREAM,
+        "misty_rose" => css::MISTY_ROSE,
+        "moccasin" => css::MOCCASIN,
+        "navajo_white" => css::NAVAJO_WHITE,
+        "old_lace" => css::OLD_LACE,
+        "olive_drab" => css::OLIVE_DRAB,
+        "orange" => css::ORANGE,
+        "orange_red" => css::ORANGE_RED,
+        "orchid" => css::ORCHID,
+        "pale_goldenrod" => css::PALE_GOLDENROD,
+        "pale_green" => css::PALE_GREEN,
+        "pale_turquoise" => css::PALE_TURQUOISE,
+        "pale_violetred" => css::PALE_VIOLETRED,
+        "papaya_whip" => css::PAPAYA_WHIP,
+        "peachpuff" => css::PEACHPUFF,
+        "peru" => css::PERU,
+        "pink" => css::PINK,
+        "plum" => css::PLUM,
+        "powder_blue" => css::POWDER_BLUE,
+        "rebecca_purple" => css::REBECCA_PURPLE,
+        "rosy_brown" => css::ROSY_BROWN,
+        "royal_blue" => css::ROYAL_BLUE,
+        "saddle_brown" => css::SADDLE_BROWN,
+        "salmon" => css::SALMON,
+        "sandy_brown" => css::SANDY_BROWN,
+        "sea_green" => css::SEA_GREEN,
+        "seashell" => css::SEASHELL,
+        "sienna" => css::SIENNA,
+        "sky_blue" => css::SKY_BLUE,
+        "slate_blue" => css::SLATE_BLUE,
+        "slate_gray" => css::SLATE_GRAY,
+        "slate_grey" => css::SLATE_GREY,
+        "snow" => css::SNOW,
+        "spring_green" => css::SPRING_GREEN,
+        "steel_blue" => css::STEEL_BLUE,
+        "tan" => css::TAN,
+        "thistle" => css::THISTLE,
+        "tomato" => css::TOMATO,
+        "turquoise" => css::TURQUOISE,
+        "violet" => css::VIOLET,
+        "wheat" => css::WHEAT,
+        "white_smoke" => css::WHITE_SMOKE,
+        "yellow_green" => css::YELLOW_GREEN,
+        // The css palette includes the basic colors.
+        _ => return basic_color(name),
+    };
+    Some(color)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn tailwind_color(name: &str) -> Option<Srgba>
+{
+    let color = match name {
+        "amber_50" => tailwind::AMBER_50,
+        "amber_100" => tailwind::AMBER_100,
+        "amber_200" => tailwind::AMBER_200,
+        "amber_300" => tailwind::AMBER_300,
+        "amber_400" => tailwind::AMBER_400,
+        "amber_500" => tailwind::AMBER_500,
+        "amber_600" => tailwind::AMBER_600,
+        "amber_700" => tailwind::AMBER_700,
+        "amber_800" => tailwind::AMBER_800,
+        "amber_900" => tailwind::AMBER_900,
+        "amber_950" => tailwind::AMBER_950,
+        "blue_50" => tailwind::BLUE_50,
+        "blue_100" => tailwind::BLUE_100,
+        "blue_200" => tailwind::BLUE_200,
+        "blue_300" => tailwind::BLUE_300,
+        "blue_400" => tailwind::BLUE_400,
+        "blue_500" => tailwind::BLUE_500,
+        "blue_600" => tailwind::BLUE_600,
+        "blue_700" => tailwind::BLUE_700,
+        "blue_800" => tailwind::BLUE_800,
+        "blue_900" => tailwind::BLUE_900,
+        "blue_950" => tailwind::BLUE_950,
+        "cyan_50" => tailwind::CYAN_50,
+        "cyan_100" => tailwind::CYAN_100,
+        "cyan_200" => tailwind::CYAN_200,
+        "cyan_300" => tailwind::CYAN_300,
+        "cyan_400" => tailwind::CYAN_400,
+        "cyan_500" => tailwind::CYAN_500,
+        "cyan_600" => tailwind::CYAN_600,
+        "cyan_700" => tailwind::CYAN_700,
+        "cyan_800" => tailwind::CYAN_800,
+        "cyan_900" => tailwind::CYAN_900,
+        "cyan_950" => tailwind::CYAN_950,
+        "emerald_50" => tailwind::EMERALD_50,
+        "emerald_100" => tailwind::EMERALD_100,
+        "emerald_200" => tailwind::EMERALD_200,
+        "emerald_300" => tailwind::EMERALD_300,
+        "emerald_400" => tailwind::EMERALD_400,
+        "emerald_500" => tailwind::EMERALD_500,
+        "emerald_600" => tailwind::EMERALD_600,
+        "emerald_700" => tailwind::EMERALD_700,
+        "emerald_800" => tailwind::EMERALD_800,
+        "emerald_900" => tailwind::EMERALD_900,
+        "emerald_950" => tailwind::EMERALD_950,
+        "fuchsia_50" => tailwind::FUCHSIA_50,
+        "fuchsia_100" => tailwind::FUCHSIA_100,
+        "fuchsia_200" => tailwind::FUCHSIA_200,
+        "fuchsia_300" => tailwind::FUCHSIA_300,
+        "fuchsia_400" => tailwind::FUCHSIA_400,
+        "fuchsia_500" => tailwind::FUCHSIA_500,
+        "fuchsia_600" => tailwind::FUCHSIA_600,
+        "fuchsia_700" => tailwind::FUCHSIA_700,
+        "fuchsia_800" => tailwind::FUCHSIA_800,
+        "fuchsia_900" => tailwind::FUCHSIA_900,
+        "fuchsia_950" => tailwind::FUCHSIA_950,
+        "gray_50" => tailwind::GRAY_50,
+        "gray_100" => tailwind::GRAY_100,
+        "gray_200" => tailwind::GRAY_200,
+        "gray_300" => tailwind::GRAY_300,
+        "gray_400" => tailwind::GRAY_400,
+        "gray_500" => tailwind::GRAY_500,
+        "gray_600" => tailwind::GRAY_600,
+        "gray_700" => tailwind::GRAY_700,
+        "gray_800" => tailwind::GRAY_800,
+        "gray_900" => tailwind::GRAY_900,
+        "gray_950" => tailwind::GRAY_950,
+        "green_50" => tailwind::GREEN_50,
+        "green_100" => tailwind::GREEN_100,
+        "green_200" => tailwind::GREEN_200,
+        "green_300" => tailwind::GREEN_300,
+        "green_400" => tailwind::GREEN_400,
+        "green_500" => tailwind::GREEN_500,
+        "green_600" => tailwind::GREEN_600,
+        "green_700" => tailwind::GREEN_700,
+        "green_800" => tailwind::GREEN_800,
+        "green_900" => tailwind::GREEN_900,
+        "green_950" => tailwind::GREEN_950,
+        "indigo_50" => tailwind::INDIGO_50,
+        "indigo_100" => tailwind::INDIGO_100,
+        "indigo_200" => tailwind::INDIGO_200,
+        "indigo_300" => tailwind::INDIGO_300,
+        "indigo_400" => tailwind::INDIGO_400,
+        "indigo_500" => tailwind::INDIGO_500,
+        "indigo_600" => tailwind::INDIGO_600,
+        "indigo_700" => tailwind::INDIGO_700,
+        "indigo_800" => tailwind::INDIGO_800,
+        "indigo_900" => tailwind::INDIGO_900,
+        "indigo_950" => tailwind::INDIGO_950,
+        "lime_50" => tailwind::LIME_50,
+        "lime_100" => tailwind::LIME_100,
+        "lime_200" => tailwind::LIME_200,
+        "lime_300" => tailwind::LIME_300,
+        "lime_400" => tailwind::LIME_400,
+        "lime_500" => tailwind::LIME_500,
+        "lime_600" => tailwind::LIME_600,
+        "lime_700" => tailwind::LIME_700,
+        "lime_800" => tailwind::LIME_800,
+        "lime_900" => tailwind::LIME_900,
+        "lime_950" => tailwind::LIME_950,
+        "neutral_50" => tailwind::NEUTRAL_50,
+        "neutral_100" => tailwind::NEUTRAL_100,
+        "neutral_200" => tailwind::NEUTRAL_200,
+        "neutral_300" => tailwind::NEUTRAL_300,
+        "neutral_400" => tailwind::NEUTRAL_400,
+        "neutral_500" => tailwind::NEUTRAL_500,
+        "neutral_600" => tailwind::NEUTRAL_600,
+        "neutral_700" => tailwind::NEUTRAL_700,
+        "neutral_800" => tailwind::NEUTRAL_800,
+        "neutral_900" => tailwind::NEUTRAL_900,
+        "neutral_950" => tailwind::NEUTRAL_950,
+        "orange_50" => tailwind::ORANGE_50,
+        "orange_100" => tailwind::ORANGE_100,
+        "orange_200" => tailwind::ORANGE_200,
+        "orange_300" => tailwind::ORANGE_300,
+        "orange_400" => tailwind::ORANGE_400,
+        "orange_500" => tailwind::ORANGE_500,
+        "orange_600" => tailwind::ORANGE_600,
+        "orange_700" => tailwind::ORANGE_700,
+        "orange_800" => tailwind::ORANGE_800,
+        "orange_900" => tailwind::ORANGE_900,
+        "orange_950" => tailwind::ORANGE_950,
+        "pink_50" => tailwind::PINK_50,
+        "pink_100" => tailwind::PINK_100,
+        "pink_200" => tailwind::PINK_200,
+        "pink_300" => tailwind::PINK_300,
+        "pink_400" => tailwind::PINK_400,
+        "pink_500" => tailwind::PINK_500,
+        "pink_600" => tailwind::PINK_600,
+        "pink_700" => tailwind::PINK_700,
+        "pink_800" => tailwind::PINK_800,
+        "pink_900" => tailwind::PINK_900,
+        "pink_950" => tailwind::PINK_950,
+        "purple_50" => tailwind::PURPLE_50,
+        "purple_100" => tailwind::PURPLE_100,
+        "purple_200" => tailwind::PURPLE_200,
+        "purple_300" => tailwind::PURPLE_300,
+        "purple_400" => tailwind::PURPLE_400,
+        "purple_500" => tailwind::PURPLE_500,
+        "purple_600" => tailwind::PURPLE_600,
+        "purple_700" => tailwind::PURPLE_700,
+        "purple_800" => tailwind::PURPLE_800,
+        "purple_900" => tailwind::PURPLE_900,
+        "purple_950" => tailwind::PURPLE_950,
+        "red_50" => tailwind::RED_50,
+        "red_100" => tailwind::RED_100,
+        "red_200" => tailwind::RED_200,
+        "red_300" => tailwind::RED_300,
+        "red_400" => tailwind::RED_400,
+        "red_500" => tailwind::RED_500,
+        "red_600" => tailwind::RED_600,
+        "red_700" => tailwind::RED_700,
+        "red_800" => tailwind::RED_800,
+        "red_900" => tailwind::RED_900,
+        "red_950" => tailwind::RED_950,
+        "rose_50" => tailwind::ROSE_50,
+        "rose_100" => tailwind::ROSE_100,
+        "rose_200" => tailwind::ROSE_200,
+        "rose_300" => tailwind::ROSE_300,
+        "rose_400" => tailwind::ROSE_400,
+        "rose_500" => tailwind::ROSE_500,
+        "rose_600" => tailwind::ROSE_600,
+        "rose_700" => tailwind::ROSE_700,
+        "rose_800" => tailwind::ROSE_800,
+        "rose_900" => tailwind::ROSE_900,
+        "rose_950" => tailwind::ROSE_950,
+        "sky_50" => tailwind::SKY_50,
+        "sky_100" => tailwind::SKY_100,
+        "sky_200" => tailwind::SKY_200,
+        "sky_300" => tailwind::SKY_300,
+        "sky_400" => tailwind::SKY_400,
+        "sky_500" => tailwind::SKY_500,
+        "sky_600" => tailwind::SKY_600,
+        "sky_700" => tailwind::SKY_700,
+        "sky_800" => tailwind::SKY_800,
+        "sky_900" => tailwind::SKY_900,
+        "sky_950" => tailwind::SKY_950,
+        "slate_50" => tailwind::SLATE_50,
+        "slate_100" => tailwind::SLATE_100,
+        "slate_200" => tailwind::SLATE_200,
+        "slate_300" => tailwind::SLATE_300,
+        "slate_400" => tailwind::SLATE_400,
+        "slate_500" => tailwind::SLATE_500,
+        "slate_600" => tailwind::SLATE_600,
+        "slate_700" => tailwind::SLATE_700,
+        "slate_800" => tailwind::SLATE_800,
+        "slate_900" => tailwind::SLATE_900,
+        "slate_950" => tailwind::SLATE_950,
+        "stone_50" => tailwind::STONE_50,
+        "stone_100" => tailwind::STONE_100,
+        "stone_200" => tailwind::STONE_200,
+        "stone_300" => tailwind::STONE_300,
+        "stone_400" => tailwind::STONE_400,
+        "stone_500" => tailwind::STONE_500,
+        "stone_600" => tailwind::STONE_600,
+        "stone_700" => tailwind::STONE_700,
+        "stone_800" => tailwind::STONE_800,
+        "stone_900" => tailwind::STONE_900,
+        "stone_950" => tailwind::STONE_950,
+        "teal_50" => tailwind::TEAL_50,
+        "teal_100" => tailwind::TEAL_100,
+        "teal_200" => tailwind::TEAL_200,
+        "teal_300" => tailwind::TEAL_300,
+        "teal_400" => tailwind::TEAL_400,
+        "teal_500" => tailwind::TEAL_500,
+        "teal_600" => tailwind::TEAL_600,
+        "teal_700" => tailwind::TEAL_700,
+        "teal_800" => tailwind::TEAL_800,
+        "teal_900" => tailwind::TEAL_900,
+        "teal_950" => tailwind::TEAL_950,
+        "violet_50" => tailwind::VIOLET_50,
+        "violet_100" => tailwind::VIOLET_100,
+        "violet_200" => tailwind::VIOLET_200,
+        "violet_300" => tailwind::VIOLET_300,
+        "violet_400" => tailwind::VIOLET_400,
+        "violet_500" => tailwind::VIOLET_500,
+        "violet_600" => tailwind::VIOLET_600,
+        "violet_700" => tailwind::VIOLET_700,
+        "violet_800" => tailwind::VIOLET_800,
+        "violet_900" => tailwind::VIOLET_900,
+        "violet_950" => tailwind::VIOLET_950,
+        "yellow_50" => tailwind::YELLOW_50,
+        "yellow_100" => tailwind::YELLOW_100,
+        "yellow_200" => tailwind::YELLOW_200,
+        "yellow_300" => tailwind::YELLOW_300,
+        "yellow_400" => tailwind::YELLOW_400,
+        "yellow_500" => tailwind::YELLOW_500,
+        "yellow_600" => tailwind::YELLOW_600,
+        "yellow_700" => tailwind::YELLOW_700,
+        "yellow_800" => tailwind::YELLOW_800,
+        "yellow_900" => tailwind::YELLOW_900,
+        "yellow_950" => tailwind::YELLOW_950,
+        "zinc_50" => tailwind::ZINC_50,
+        "zinc_100" => tailwind::ZINC_100,
+        "zinc_200" => tailwind::ZINC_200,
+        "zinc_300" => tailwind::ZINC_300,
+        "zinc_400" => tailwind::ZINC_400,
+        "zinc_500" => tailwind::ZINC_500,
+        "zinc_600" => tailwind::ZINC_600,
+        "zinc_700" => tailwind::ZINC_700,
+        "zinc_800" => tailwind::ZINC_800,
+        "zinc_900" => tailwind::ZINC_900,
+        "zinc_950" => tailwind::ZINC_950,
+        _ => return None,
+    };
+    Some(color)
+}
@@ -20,6 +20,9 @@ pub enum CobValue
     None(CobNone),
     String(CobString),
     Constant(CobConstant),
+    DataMacro(CobDataMacroCall),
+    /// Expressions like `calc($gap * 2)` and `lighten($primary 0.1)`. Evaluated to plain values when resolved.
+    Expr(CobExpr),
 }
 
 impl CobValue
@@ -62,6 +65,12 @@ impl CobValue
             Self::Constant(val) => {
                 val.write_to_with_space(writer, space)?;
             }
+            Self::DataMacro(val) => {
+                val.write_to_with_space(writer, space)?;
+            }
+            Self::Expr(val) => {
+                val.write_to_with_space(writer, space)?;
+            }
         }
         Ok(())
     }
@@ -108,6 +117,14 @@ impl CobValue
             (Some(value), fill, remaining) => return Ok((Some(Self::Constant(value)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobDataMacroCall::try_parse(fill, c))? {
+            (Some(value), fill, remaining) => return Ok((Some(Self::DataMacro(value)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobExpr::try_parse(fill, c))? {
+            (Some(value), fill, remaining) => return Ok((Some(Self::Expr(value)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
 
         Ok((None, fill, content))
     }
@@ -145,6 +162,12 @@ impl CobValue
             (Self::Constant(val), Self::Constant(other_val)) => {
                 val.recover_fill(other_val);
             }
+            (Self::DataMacro(val), Self::DataMacro(other_val)) => {
+                val.recover_fill(other_val);
+            }
+            (Self::Expr(val), Self::Expr(other_val)) => {
+                val.recover_fill(other_val);
+            }
             _ => (),
         }
     }
@@ -170,6 +193,14 @@ impl CobValue
                     }
                 }
             }
+            Self::DataMacro(call) => {
+                *self = call.resolve(resolver)?;
+            }
+            Self::Expr(expr) => {
+                if let Some(value) = expr.resolve(resolver)? {
+                    *self = value;
+                }
+            }
             _ => (),
         }
 
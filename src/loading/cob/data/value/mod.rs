@@ -2,9 +2,11 @@ mod cob_array;
 mod cob_bool;
 mod cob_builtin;
 mod cob_enum;
+mod cob_expr;
 mod cob_map;
 mod cob_none;
 mod cob_number;
+mod cob_palette;
 mod cob_string;
 mod cob_tuple;
 mod cob_value;
@@ -13,9 +15,11 @@ pub use cob_array::*;
 pub use cob_bool::*;
 pub use cob_builtin::*;
 pub use cob_enum::*;
+pub use cob_expr::*;
 pub use cob_map::*;
 pub use cob_none::*;
 pub use cob_number::*;
+pub use cob_palette::*;
 pub use cob_string::*;
 pub use cob_tuple::*;
 pub use cob_value::*;
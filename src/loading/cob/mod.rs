@@ -1,10 +1,12 @@
 mod cob;
+mod cob_formatter;
 mod data;
 mod parsing;
 mod raw_serializer;
 mod sections;
 
 pub use cob::*;
+pub use cob_formatter::*;
 pub use data::*;
 pub use parsing::*;
 pub use raw_serializer::*;
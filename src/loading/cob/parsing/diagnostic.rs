@@ -0,0 +1,384 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Write};
+use std::sync::Arc;
+
+use bevy::prelude::Event;
+use nom::error::ErrorKind;
+
+use super::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+thread_local! {
+    static DIAGNOSTICS: RefCell<Vec<CobDiagnostic>> = RefCell::new(vec![]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a diagnostic in the thread-local diagnostics buffer.
+///
+/// Diagnostics are collected with [`take_cob_diagnostics`].
+pub(crate) fn record_cob_diagnostic(diagnostic: CobDiagnostic)
+{
+    DIAGNOSTICS.with_borrow_mut(|d| d.push(diagnostic));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Logs a diagnostic as a warning and records it in the thread-local diagnostics buffer.
+pub(crate) fn emit_cob_diagnostic(diagnostic: CobDiagnostic)
+{
+    match &diagnostic.location {
+        Some(location) => tracing::warn!("{} at {}:{}:{}",
+            diagnostic.message, diagnostic.file, location.line, location.column),
+        None => tracing::warn!("{} in {}", diagnostic.message, diagnostic.file),
+    }
+    record_cob_diagnostic(diagnostic);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Drains the thread-local diagnostics buffer.
+pub(crate) fn take_cob_diagnostics() -> Vec<CobDiagnostic>
+{
+    DIAGNOSTICS.with_borrow_mut(|d| std::mem::take(d))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Selects the diagnostic that best describes a parsing error.
+///
+/// Prefers a recorded diagnostic at the error location, then the recorded diagnostic that is furthest into the
+/// file (parsing got furthest along that branch), then a generic diagnostic made from the error itself.
+pub(crate) fn diagnose_parse_error(input: Span, code: ErrorKind, recorded: Vec<CobDiagnostic>) -> CobDiagnostic
+{
+    let line = input.location_line();
+    let column = input.get_utf8_column();
+    let at_error = recorded.iter().position(|d| {
+        d.location
+            .as_ref()
+            .is_some_and(|l| l.line == line && l.column == column)
+    });
+
+    match at_error {
+        Some(idx) => recorded.into_iter().nth(idx).unwrap(),
+        None => recorded
+            .into_iter()
+            .rev()
+            .max_by_key(|d| d.location.as_ref().map(|l| (l.line, l.column)))
+            .unwrap_or_else(|| CobDiagnostic::from_parse_error(input, code)),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a [`CobDiagnostic`] error at the start of `content` and makes a [`SpanError`] for it.
+///
+/// The diagnostic message is also logged as a warning.
+pub fn span_diagnostic_error(content: Span, message: impl Into<String>) -> SpanError
+{
+    let diagnostic = CobDiagnostic::from_span(CobDiagnosticSeverity::Error, content, message);
+    tracing::warn!("{} at {}", diagnostic.message, get_location(content).as_str());
+    record_cob_diagnostic(diagnostic);
+    span_verify_error(content)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the edit distance between two strings.
+///
+/// Used to make 'did you mean' suggestions.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize
+{
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finds the candidate closest to `target`, if one is close enough to be a plausible typo.
+///
+/// Used to make 'did you mean' suggestions. A candidate is close enough if its edit distance from `target` is at
+/// most a third of the length of `target` (and at least 1).
+pub fn closest_match<'a>(target: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str>
+{
+    let max_distance = (target.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(target, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The severity of a [`CobDiagnostic`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CobDiagnosticSeverity
+{
+    /// The COB content could not be used.
+    #[default]
+    Error,
+    /// The COB content was used, but may not behave as expected.
+    Warning,
+}
+
+impl CobDiagnosticSeverity
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The location of a [`CobDiagnostic`] in COB source text.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CobSourceLocation
+{
+    /// Line number, starting from 1.
+    pub line: u32,
+    /// Column number in UTF-8 characters, starting from 1.
+    pub column: usize,
+    /// Number of characters highlighted by the diagnostic. Always at least 1.
+    pub len: usize,
+    /// The source line containing the diagnostic, without the line ending.
+    pub source_line: String,
+}
+
+impl CobSourceLocation
+{
+    /// Makes a location from the start of a span.
+    ///
+    /// The highlighted length covers the next token in the span (or one character if there is no token).
+    pub fn from_span(span: Span) -> Self
+    {
+        let token_len = span
+            .fragment()
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .count();
+        Self::from_span_with_len(span, token_len)
+    }
+
+    /// Makes a location from the start of a span with a specific highlighted length.
+    ///
+    /// The length will be clamped to the end of the source line.
+    pub fn from_span_with_len(span: Span, len: usize) -> Self
+    {
+        // Note: `get_line_beginning` returns the entire line containing the span.
+        let source_line = String::from_utf8_lossy(span.get_line_beginning());
+        let source_line = source_line.strip_suffix('\r').unwrap_or(&source_line);
+        let line_end = span.fragment().split('\n').next().unwrap_or_default();
+        let line_end = line_end.strip_suffix('\r').unwrap_or(line_end);
+        let remaining_len = line_end.chars().count();
+
+        Self {
+            line: span.location_line(),
+            column: span.get_utf8_column(),
+            len: len.min(remaining_len).max(1),
+            source_line: String::from(source_line),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The source location of a parsed COB item.
+///
+/// Used to locate diagnostics produced after parsing, e.g. when extracting loadables. Items constructed in code
+/// don't have a location.
+///
+/// Locations are ignored when comparing items, so parsed items compare equal to the same items constructed in
+/// code.
+#[derive(Debug, Default, Clone)]
+pub struct CobItemLocation(pub Option<Arc<CobSourceLocation>>);
+
+impl CobItemLocation
+{
+    /// Makes a location from the start of a span with a specific highlighted length.
+    pub fn from_span_with_len(span: Span, len: usize) -> Self
+    {
+        Self(Some(Arc::new(CobSourceLocation::from_span_with_len(span, len))))
+    }
+
+    pub fn get(&self) -> Option<&CobSourceLocation>
+    {
+        self.0.as_deref()
+    }
+}
+
+impl PartialEq for CobItemLocation
+{
+    fn eq(&self, _other: &Self) -> bool
+    {
+        true
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A source-located error or warning produced while loading a COB file.
+///
+/// Diagnostics are produced when parsing COB files and when extracting their contents. They are emitted as Bevy
+/// events so they can be displayed in-game (e.g. in a debug overlay).
+///
+/// The `Display` implementation renders the diagnostic with a caret-underlined snippet of the source line:
+///
+/// ```text
+/// error: expected ':' after map key "width" (use key:value syntax)
+///   --> main.cob:12:14
+///    |
+/// 12 |     FlexNode{width 10px}
+///    |              ^^^^^
+/// ```
+#[derive(Event, Debug, Default, Clone, PartialEq)]
+pub struct CobDiagnostic
+{
+    pub severity: CobDiagnosticSeverity,
+    /// The file where the diagnostic was produced.
+    pub file: String,
+    /// The source location of the diagnostic, if known.
+    ///
+    /// Diagnostics from COB extraction are located at the item they describe if that item was parsed from source
+    /// (see [`CobItemLocation`]).
+    pub location: Option<CobSourceLocation>,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl CobDiagnostic
+{
+    /// Makes a new error diagnostic without a source location.
+    pub fn error(file: impl Into<String>, message: impl Into<String>) -> Self
+    {
+        Self {
+            severity: CobDiagnosticSeverity::Error,
+            file: file.into(),
+            location: None,
+            message: message.into(),
+        }
+    }
+
+    /// Makes a new warning diagnostic without a source location.
+    pub fn warning(file: impl Into<String>, message: impl Into<String>) -> Self
+    {
+        Self {
+            severity: CobDiagnosticSeverity::Warning,
+            file: file.into(),
+            location: None,
+            message: message.into(),
+        }
+    }
+
+    /// Sets the diagnostic's location to `location`, if the location is known.
+    pub fn at(mut self, location: &CobItemLocation) -> Self
+    {
+        if let Some(location) = location.get() {
+            self.location = Some(location.clone());
+        }
+        self
+    }
+
+    /// Makes a new diagnostic located at the start of `span`.
+    pub fn from_span(severity: CobDiagnosticSeverity, span: Span, message: impl Into<String>) -> Self
+    {
+        Self {
+            severity,
+            file: String::from(span.extra.file),
+            location: Some(CobSourceLocation::from_span(span)),
+            message: message.into(),
+        }
+    }
+
+    /// Makes a fallback error diagnostic for a parsing error that doesn't have a recorded diagnostic.
+    pub fn from_parse_error(input: Span, code: ErrorKind) -> Self
+    {
+        let message = match code {
+            ErrorKind::Char | ErrorKind::Tag => "unexpected character sequence",
+            ErrorKind::Eof => "unexpected content",
+            _ => "invalid syntax",
+        };
+        let mut diagnostic = Self::from_span(CobDiagnosticSeverity::Error, input, message);
+        if input.fragment().is_empty() {
+            diagnostic.message = String::from("unexpected end of file");
+        }
+        diagnostic
+    }
+
+    /// Makes an error diagnostic for an error returned by [`Cob::parse`] or [`Cob::parse_with_recovery`].
+    ///
+    /// Diagnostics recorded while parsing are used to describe the error when possible, so this should be called
+    /// immediately after parsing fails.
+    pub fn from_span_error(file: &str, err: SpanError) -> Self
+    {
+        let recorded = take_cob_diagnostics();
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => diagnose_parse_error(err.input, err.code, recorded),
+            nom::Err::Incomplete(needed) => Self::error(file, format!("insufficient data: {:?}", needed)),
+        }
+    }
+
+    /// Returns `true` if the diagnostic is an error.
+    pub fn is_error(&self) -> bool
+    {
+        self.severity == CobDiagnosticSeverity::Error
+    }
+
+    /// Renders the diagnostic with a caret-underlined source snippet.
+    ///
+    /// Equivalent to `diagnostic.to_string()`.
+    pub fn render(&self) -> String
+    {
+        self.to_string()
+    }
+}
+
+impl Display for CobDiagnostic
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        writeln!(f, "{}: {}", self.severity.as_str(), self.message)?;
+
+        let Some(location) = &self.location else {
+            return write!(f, "  --> {}", self.file);
+        };
+
+        let line_num = location.line.to_string();
+        let gutter = " ".repeat(line_num.len());
+        writeln!(f, "{gutter}--> {}:{}:{}", self.file, location.line, location.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_num} | {}", location.source_line)?;
+
+        // Preserve tabs in the caret offset so the caret lines up with the source line.
+        let mut underline: String = location
+            .source_line
+            .chars()
+            .take(location.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        for _ in 0..location.len.max(1) {
+            underline.write_char('^')?;
+        }
+        write!(f, "{gutter} | {underline}")
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
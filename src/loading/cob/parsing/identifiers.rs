@@ -1,9 +1,10 @@
 use nom::branch::alt;
+use nom::bytes::complete::tag;
 use nom::character::complete::{alpha1, alphanumeric0, char, digit1};
 use nom::combinator::recognize;
 use nom::error::ErrorKind;
 use nom::multi::many0_count;
-use nom::sequence::tuple;
+use nom::sequence::{preceded, tuple};
 use nom::{IResult, InputTakeAtPosition, Parser};
 
 use super::Span;
@@ -81,3 +82,17 @@ pub(crate) fn anything_identifier(input: Span) -> IResult<Span, Span>
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Parses a def path without a def symbol from the input (e.g. `colors::AQUA`).
+///
+/// Path segments are [`anything_identifier`]s separated by `::`.
+pub(crate) fn def_path_identifier(input: Span) -> IResult<Span, Span>
+{
+    recognize(tuple((
+        anything_identifier,
+        many0_count(preceded(tag("::"), anything_identifier)),
+    )))
+    .parse(input)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
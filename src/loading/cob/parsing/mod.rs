@@ -1,9 +1,13 @@
+mod diagnostic;
 mod error;
 mod identifiers;
+mod recovery;
 mod recursion;
 mod span;
 
+pub use diagnostic::*;
 pub use error::*;
 pub(crate) use identifiers::*;
+pub(crate) use recovery::*;
 pub(crate) use recursion::*;
 pub use span::*;
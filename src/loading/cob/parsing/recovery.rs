@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+
+use nom::Slice;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+thread_local! {
+    static RECOVERED_ERRORS: RefCell<Option<Vec<CobDiagnostic>>> = RefCell::new(None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Section keywords that the parser can resynchronize on after an error.
+const SECTION_KEYWORDS: [&str; 8] =
+    ["#manifest", "#import", "#export", "#assets", "#defs", "#commands", "#scenes", "#patches"];
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs a parser callback with error recovery enabled.
+///
+/// Returns the callback's result and diagnostics for all errors that were recovered from.
+pub(crate) fn with_error_recovery<T>(callback: impl FnOnce() -> T) -> (T, Vec<CobDiagnostic>)
+{
+    let prev = RECOVERED_ERRORS.with_borrow_mut(|r| r.replace(vec![]));
+    let result = (callback)();
+    let recovered = RECOVERED_ERRORS.with_borrow_mut(|r| std::mem::replace(r, prev));
+    (result, recovered.unwrap_or_default())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tries to recover from a parsing error.
+///
+/// If error recovery is enabled and the error is recoverable, a diagnostic for the error is saved and the span
+/// where the error occurred is returned. Otherwise the error is returned.
+pub(crate) fn try_recover(err: SpanError) -> Result<Span, SpanError>
+{
+    let nom::Err::Error(nom::error::Error { input, code }) = err else { return Err(err) };
+    if RECOVERED_ERRORS.with_borrow(|r| r.is_none()) {
+        return Err(err);
+    }
+
+    let diagnostic = diagnose_parse_error(input, code, take_cob_diagnostics());
+    RECOVERED_ERRORS.with_borrow_mut(|r| {
+        if let Some(recovered) = r {
+            recovered.push(diagnostic);
+        }
+    });
+
+    Ok(input)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Returns `true` if the line starts with a section keyword (e.g. `#scenes`).
+pub(crate) fn starts_with_section_keyword(line: &str) -> bool
+{
+    SECTION_KEYWORDS.iter().any(|k| line.starts_with(k))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Skips from `content` to the first line at or after `error_at` that satisfies `predicate(indent, line)`.
+///
+/// The line containing `error_at` is only considered if that line starts after the start of `content`, which
+/// ensures parsing always makes progress.
+///
+/// Blank lines and lines that start with a comment are ignored. The returned span starts at the newline before
+/// the target line so the line's indentation can be parsed as fill. If no line is found, the span will be at
+/// the end of `content`.
+pub(crate) fn skip_to_line<'a>(
+    content: Span<'a>,
+    error_at: Span,
+    predicate: impl Fn(usize, &str) -> bool,
+) -> Span<'a>
+{
+    let fragment = *content.fragment();
+    let error_idx = error_at
+        .location_offset()
+        .saturating_sub(content.location_offset())
+        .min(fragment.len());
+    let search_start = fragment.as_bytes()[..error_idx]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .unwrap_or(error_idx);
+
+    // Note: we search bytes since `search_start` may not be on a char boundary.
+    let newlines = fragment.as_bytes()[search_start..]
+        .iter()
+        .enumerate()
+        .filter(|(_, byte)| **byte == b'\n')
+        .map(|(idx, _)| idx + search_start);
+
+    for newline in newlines {
+        let line = &fragment[(newline + 1)..];
+        let line = line.split('\n').next().unwrap_or_default();
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        if (predicate)(indent, trimmed) {
+            return content.slice(newline..);
+        }
+    }
+
+    content.slice(fragment.len()..)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -15,6 +15,13 @@ pub trait RawSerializer: std::io::Write
     /// Only finite numbers are passed in here.
     fn write_f32(&mut self, val: f32) -> Result<(), std::io::Error>;
     fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), std::io::Error>;
+    /// Writes a fill sequence (whitespace, comments, and ignored characters).
+    ///
+    /// Writes the fill unchanged by default.
+    fn write_fill(&mut self, fill: &str) -> Result<(), std::io::Error>
+    {
+        self.write_bytes(fill.as_bytes())
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
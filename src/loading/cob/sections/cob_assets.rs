@@ -0,0 +1,230 @@
+use nom::bytes::complete::tag;
+use nom::Parser;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Commands generated from `#assets` sections, in the order they are applied.
+pub(crate) const ASSET_COMMANDS: [&str; 8] = [
+    "RegisterFontFamilies",
+    "LoadFonts",
+    "LoadLocalizedFonts",
+    "LoadImages",
+    "LoadLocalizedImages",
+    "LoadAudio",
+    "LoadLocalizedAudio",
+    "LoadTextureAtlasLayouts",
+];
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The kinds of assets that can be listed in an `#assets` section.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CobAssetKind
+{
+    /// Image paths and [`LocalizedImage`] maps.
+    Images,
+    /// Font family names and [`LocalizedFont`] maps.
+    Fonts,
+    /// [`RegisterFontFamily`] maps.
+    FontFamilies,
+    /// Audio paths and [`LocalizedAudio`] maps.
+    Audio,
+    /// [`LoadedTextureAtlasLayout`] maps.
+    Atlases,
+}
+
+impl CobAssetKind
+{
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name {
+            "images" => Some(Self::Images),
+            "fonts" => Some(Self::Fonts),
+            "font_families" => Some(Self::FontFamilies),
+            "audio" => Some(Self::Audio),
+            "atlases" => Some(Self::Atlases),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Images => "images",
+            Self::Fonts => "fonts",
+            Self::FontFamilies => "font_families",
+            Self::Audio => "audio",
+            Self::Atlases => "atlases",
+        }
+    }
+
+    /// Gets the name of the command that loads an asset of this kind.
+    ///
+    /// Returns `None` if the value can't be an asset of this kind.
+    pub fn command_for(&self, value: &CobValue) -> Option<&'static str>
+    {
+        match (self, value) {
+            (Self::Images, CobValue::String(_)) => Some("LoadImages"),
+            (Self::Images, CobValue::Map(_)) => Some("LoadLocalizedImages"),
+            (Self::Fonts, CobValue::String(_)) => Some("LoadFonts"),
+            (Self::Fonts, CobValue::Map(_)) => Some("LoadLocalizedFonts"),
+            (Self::FontFamilies, CobValue::Map(_)) => Some("RegisterFontFamilies"),
+            (Self::Audio, CobValue::String(_)) => Some("LoadAudio"),
+            (Self::Audio, CobValue::Map(_)) => Some("LoadLocalizedAudio"),
+            (Self::Atlases, CobValue::Map(_)) => Some("LoadTextureAtlasLayouts"),
+            _ => None,
+        }
+    }
+
+    fn expected(&self) -> &'static str
+    {
+        match self {
+            Self::Images => "image paths or localized image maps",
+            Self::Fonts => "font family names or localized font maps",
+            Self::FontFamilies => "font family maps",
+            Self::Audio => "audio paths or localized audio maps",
+            Self::Atlases => "texture atlas layout maps",
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// {asset kind} {array}
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobAssetsEntry
+{
+    pub entry_fill: CobFill,
+    pub kind: CobAssetKind,
+    /// The value is expected to handle its own fill.
+    pub value: CobValue,
+}
+
+impl CobAssetsEntry
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.entry_fill.write_to_or_else(writer, "\n")?;
+        writer.write_bytes(self.kind.as_str().as_bytes())?;
+        self.value.write_to_with_space(writer, " ")?;
+        Ok(())
+    }
+
+    pub fn try_parse(entry_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, name)) = snake_identifier(content) else {
+            return Ok((None, entry_fill, content));
+        };
+        if !entry_fill.ends_with_newline() {
+            return Err(span_diagnostic_error(
+                content,
+                "asset entry doesn't start on a new line",
+            ));
+        }
+        let Some(kind) = CobAssetKind::from_name(name.fragment()) else {
+            return Err(span_diagnostic_error(
+                content,
+                format!("unknown asset kind {:?}; expected images, fonts, font_families, audio, or atlases",
+                    *name.fragment()),
+            ));
+        };
+        let (value_fill, remaining) = CobFill::parse(remaining);
+        let (Some(value), next_fill, remaining) = rc(remaining, move |rm| CobValue::try_parse(value_fill, rm))?
+        else {
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("expected an array of {} after {}", kind.expected(), kind.as_str()),
+            ));
+        };
+        Ok((Some(Self { entry_fill, kind, value }), next_fill, remaining))
+    }
+
+    /// Resolves the entry's value, then pairs each asset with the name of the command that loads it.
+    pub fn resolve(&mut self, resolver: &CobLoadableResolver) -> Result<Vec<(&'static str, CobValue)>, String>
+    {
+        let kind = self.kind.as_str();
+        if self.value.resolve(resolver)?.is_some() {
+            return Err(format!("#assets {kind} cannot be a value group"));
+        }
+        let CobValue::Array(array) = &self.value else {
+            return Err(format!("#assets {kind} must be an array of {}", self.kind.expected()));
+        };
+
+        array
+            .entries
+            .iter()
+            .map(|value| {
+                let command = self
+                    .kind
+                    .command_for(value)
+                    .ok_or_else(|| format!("#assets {kind} can only contain {}", self.kind.expected()))?;
+                Ok((command, value.clone()))
+            })
+            .collect()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Section that lists assets to pre-load.
+///
+/// Assets are requested before the file's `#commands` are applied, and [`LoadState::Done`] waits for them to
+/// finish loading.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CobAssets
+{
+    pub start_fill: CobFill,
+    pub entries: Vec<CobAssetsEntry>,
+}
+
+impl CobAssets
+{
+    pub fn write_to(&self, first_section: bool, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        let space = if first_section { "" } else { "\n\n" };
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("#assets".as_bytes())?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("#assets").parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+
+        if start_fill.len() != 0 && !start_fill.ends_with_newline() {
+            return Err(span_diagnostic_error(
+                content,
+                "#assets section doesn't start on a new line",
+            ));
+        }
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut entries = vec![];
+
+        let end_fill = loop {
+            match rc(remaining, move |rm| CobAssetsEntry::try_parse(item_fill, rm))? {
+                (Some(entry), next_fill, after_entry) => {
+                    entries.push(entry);
+                    item_fill = next_fill;
+                    remaining = after_entry;
+                }
+                (None, end_fill, after_end) => {
+                    remaining = after_end;
+                    break end_fill;
+                }
+            }
+        };
+
+        let assets = Self { start_fill, entries };
+        Ok((Some(assets), end_fill, remaining))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
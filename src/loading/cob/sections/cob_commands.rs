@@ -7,13 +7,21 @@ use crate::prelude::*;
 
 /// Commands are parsed as loadables.
 #[derive(Debug, Clone, PartialEq)]
-pub struct CobCommandEntry(pub CobLoadable);
+pub enum CobCommandEntry
+{
+    Loadable(CobLoadable),
+    /// A condition that applies to the next command.
+    Condition(CobCondition),
+}
 
 impl CobCommandEntry
 {
     pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
     {
-        self.0.write_to(writer)
+        match self {
+            Self::Loadable(entry) => entry.write_to(writer),
+            Self::Condition(entry) => entry.write_to(writer),
+        }
     }
 
     pub fn try_parse(fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
@@ -21,8 +29,7 @@ impl CobCommandEntry
         let starts_newline = fill.ends_with_newline();
         let check_newline = || -> Result<(), SpanError> {
             if !starts_newline {
-                tracing::warn!("command entry doesn't start on a new line at {}", get_location(content).as_str());
-                return Err(span_verify_error(content));
+                return Err(span_diagnostic_error(content, "command entry doesn't start on a new line"));
             }
             Ok(())
         };
@@ -33,7 +40,14 @@ impl CobCommandEntry
                 // of traversing the structure. Allow errors to be detected downstream (e.g. when deserializing).
                 // TODO: re-evaluate if this is useful; the perf cost of traversing everything again is
                 // non-negligible
-                return Ok((Some(Self(loadable)), next_fill, remaining));
+                return Ok((Some(Self::Loadable(loadable)), next_fill, remaining));
+            }
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobCondition::try_parse_item(fill, c))? {
+            (Some(condition), next_fill, remaining) => {
+                (check_newline)()?;
+                return Ok((Some(Self::Condition(condition)), next_fill, remaining));
             }
             (None, fill, _) => fill,
         };
@@ -71,9 +85,7 @@ impl CobCommands
         };
 
         if start_fill.len() != 0 && !start_fill.ends_with_newline() {
-            tracing::warn!("failed parsing commands section at {} that doesn't start on newline",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "#commands section doesn't start on a new line"));
         }
 
         let (mut item_fill, mut remaining) = CobFill::parse(remaining);
@@ -9,7 +9,11 @@ use crate::prelude::*;
 pub enum CobDefEntry
 {
     Constant(CobConstantDef),
+    DataMacro(CobDataMacroDef),
+    LoadableMacro(CobLoadableMacroDef),
     SceneMacro(CobSceneMacroDef),
+    /// A condition that applies to the next definition.
+    Condition(CobCondition),
 }
 
 impl CobDefEntry
@@ -20,9 +24,18 @@ impl CobDefEntry
             Self::Constant(entry) => {
                 entry.write_to(writer)?;
             }
+            Self::DataMacro(entry) => {
+                entry.write_to(writer)?;
+            }
+            Self::LoadableMacro(entry) => {
+                entry.write_to(writer)?;
+            }
             Self::SceneMacro(entry) => {
                 entry.write_to(writer)?;
             }
+            Self::Condition(entry) => {
+                entry.write_to(writer)?;
+            }
         }
         Ok(())
     }
@@ -32,8 +45,7 @@ impl CobDefEntry
         let starts_newline = fill.ends_with_newline();
         let check_newline = || -> Result<(), SpanError> {
             if !starts_newline {
-                tracing::warn!("def entry doesn't start on a new line at {}", get_location(content).as_str());
-                return Err(span_verify_error(content));
+                return Err(span_diagnostic_error(content, "definition doesn't start on a new line"));
             }
             Ok(())
         };
@@ -44,6 +56,20 @@ impl CobDefEntry
             }
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobDataMacroDef::try_parse(fill, c))? {
+            (Some(def), next_fill, remaining) => {
+                (check_newline)()?;
+                return Ok((Some(Self::DataMacro(def)), next_fill, remaining));
+            }
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobLoadableMacroDef::try_parse(fill, c))? {
+            (Some(def), next_fill, remaining) => {
+                (check_newline)()?;
+                return Ok((Some(Self::LoadableMacro(def)), next_fill, remaining));
+            }
+            (None, fill, _) => fill,
+        };
         let fill = match rc(content, move |c| CobSceneMacroDef::try_parse(fill, c))? {
             (Some(def), next_fill, remaining) => {
                 (check_newline)()?;
@@ -51,6 +77,13 @@ impl CobDefEntry
             }
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobCondition::try_parse_item(fill, c))? {
+            (Some(condition), next_fill, remaining) => {
+                (check_newline)()?;
+                return Ok((Some(Self::Condition(condition)), next_fill, remaining));
+            }
+            (None, fill, _) => fill,
+        };
 
         Ok((None, fill, content))
     }
@@ -86,9 +119,7 @@ impl CobDefs
         };
 
         if start_fill.len() != 0 && !start_fill.ends_with_newline() {
-            tracing::warn!("failed parsing defs section at {} that doesn't start on newline",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "#defs section doesn't start on a new line"));
         }
 
         let (mut item_fill, mut remaining) = CobFill::parse(remaining);
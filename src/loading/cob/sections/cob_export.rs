@@ -0,0 +1,109 @@
+use nom::bytes::complete::tag;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// {def path}
+///
+/// Def paths don't have def symbols. A path exports the definition at that path (of any kind) and all definitions
+/// in that namespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobExportEntry
+{
+    pub entry_fill: CobFill,
+    pub path: SmolStr,
+}
+
+impl CobExportEntry
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.entry_fill.write_to_or_else(writer, "\n")?;
+        writer.write_bytes(self.path.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(entry_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, path)) = def_path_identifier(content) else {
+            return Ok((None, entry_fill, content));
+        };
+        if !entry_fill.ends_with_newline() {
+            return Err(span_diagnostic_error(content, "export entry doesn't start on a new line"));
+        }
+        let (next_fill, remaining) = CobFill::parse(remaining);
+        Ok((
+            Some(Self { entry_fill, path: SmolStr::from(*path.fragment()) }),
+            next_fill,
+            remaining,
+        ))
+    }
+
+    // Makes a new entry with default spacing.
+    pub fn new(path: impl AsRef<str>) -> Self
+    {
+        Self { entry_fill: CobFill::new("\n"), path: SmolStr::from(path.as_ref()) }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Section that lists the definitions a file exposes to files that import it.
+///
+/// Files without an `#export` section export all of their definitions, including imported definitions.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CobExport
+{
+    pub start_fill: CobFill,
+    pub entries: Vec<CobExportEntry>,
+}
+
+impl CobExport
+{
+    pub fn write_to(&self, first_section: bool, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        let space = if first_section { "" } else { "\n\n" };
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("#export".as_bytes())?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("#export").parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+
+        if start_fill.len() != 0 && !start_fill.ends_with_newline() {
+            return Err(span_diagnostic_error(content, "#export section doesn't start on a new line"));
+        }
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut entries = vec![];
+
+        let end_fill = loop {
+            match CobExportEntry::try_parse(item_fill, remaining)? {
+                (Some(entry), next_fill, after_entry) => {
+                    entries.push(entry);
+                    item_fill = next_fill;
+                    remaining = after_entry;
+                }
+                (None, end_fill, after_end) => {
+                    remaining = after_end;
+                    break end_fill;
+                }
+            }
+        };
+
+        let export = Self { start_fill, entries };
+        Ok((Some(export), end_fill, remaining))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
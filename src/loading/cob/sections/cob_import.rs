@@ -71,12 +71,89 @@ impl Default for CobImportAlias
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// A name in a [`CobImportSelection`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobImportName
+{
+    pub name_fill: CobFill,
+    /// Def path without a def symbol.
+    pub name: SmolStr,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// ::{name name ...}
+///
+/// Selects which definitions are imported. A name selects the definition at that path (of any kind) and all
+/// definitions in that namespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobImportSelection
+{
+    pub names: Vec<CobImportName>,
+    /// Fill before `}`.
+    pub end_fill: CobFill,
+}
+
+impl CobImportSelection
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        writer.write_bytes("::{".as_bytes())?;
+        for (idx, name) in self.names.iter().enumerate() {
+            match idx {
+                0 => name.name_fill.write_to(writer)?,
+                _ => name.name_fill.write_to_or_else(writer, " ")?,
+            }
+            writer.write_bytes(name.name.as_bytes())?;
+        }
+        self.end_fill.write_to(writer)?;
+        writer.write_bytes("}".as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(content: Span) -> Result<(Option<Self>, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("::{").parse(content) else {
+            return Ok((None, content));
+        };
+
+        let (mut name_fill, mut remaining) = CobFill::parse(remaining);
+        let mut names = vec![];
+        while let Ok((after_name, name)) = def_path_identifier(remaining) {
+            names.push(CobImportName { name_fill, name: SmolStr::from(*name.fragment()) });
+            (name_fill, remaining) = CobFill::parse(after_name);
+        }
+
+        let Ok((remaining, _)) = char::<_, ()>('}').parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a definition name or '}' in import selection",
+            ));
+        };
+        if names.is_empty() {
+            return Err(span_diagnostic_error(content, "import selection doesn't select any definitions"));
+        }
+
+        Ok((Some(Self { names, end_fill: name_fill }), remaining))
+    }
+
+    /// Collects the selected names.
+    pub fn names(&self) -> Arc<[SmolStr]>
+    {
+        self.names.iter().map(|name| name.name.clone()).collect()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// {manifest key} as {alias}
+/// {manifest key}::{selection} as {alias}
 #[derive(Debug, Clone, PartialEq)]
 pub struct CobImportEntry
 {
     pub entry_fill: CobFill,
     pub key: ManifestKey,
+    pub selection: Option<CobImportSelection>,
     pub as_fill: CobFill,
     pub alias_fill: CobFill,
     pub alias: CobImportAlias,
@@ -88,6 +165,9 @@ impl CobImportEntry
     {
         self.entry_fill.write_to_or_else(writer, "\n")?;
         self.key.write_to(writer)?;
+        if let Some(selection) = &self.selection {
+            selection.write_to(writer)?;
+        }
         self.as_fill.write_to_or_else(writer, " ")?;
         writer.write_bytes("as".as_bytes())?;
         self.alias_fill.write_to_or_else(writer, " ")?;
@@ -101,24 +181,24 @@ impl CobImportEntry
             return Ok((None, entry_fill, content));
         };
         if !entry_fill.ends_with_newline() {
-            tracing::warn!("import entry doesn't start on a new line at {}", get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "import entry doesn't start on a new line"));
         }
+        let (selection, remaining) = CobImportSelection::try_parse(remaining)?;
         let (as_fill, remaining) = CobFill::parse(remaining);
         if as_fill.len() == 0 {
-            tracing::warn!("no fill/whitespace before import 'as' at {}", get_location(remaining).as_str());
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(remaining, "expected whitespace before 'as' in import entry"));
         }
-        let (remaining, _) = tag("as").parse(remaining)?;
+        let Ok((remaining, _)) = tag::<_, _, ()>("as").parse(remaining) else {
+            return Err(span_diagnostic_error(remaining, "expected 'as' after file key in import entry"));
+        };
         let (alias_fill, remaining) = CobFill::parse(remaining);
         if alias_fill.len() == 0 {
-            tracing::warn!("no fill/whitespace after import 'as' at {}", get_location(remaining).as_str());
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(remaining, "expected whitespace after 'as' in import entry"));
         }
         let (alias, remaining) = CobImportAlias::parse(remaining)?;
         let (next_fill, remaining) = CobFill::parse(remaining);
         Ok((
-            Some(Self { entry_fill, key, as_fill, alias_fill, alias }),
+            Some(Self { entry_fill, key, selection, as_fill, alias_fill, alias }),
             next_fill,
             remaining,
         ))
@@ -142,6 +222,7 @@ impl Default for CobImportEntry
         Self {
             entry_fill: CobFill::new("\n"),
             key: Default::default(),
+            selection: None,
             as_fill: CobFill::new(" "),
             alias_fill: CobFill::new(" "),
             alias: Default::default(),
@@ -151,6 +232,29 @@ impl Default for CobImportEntry
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// An import extracted from a [`CobImportEntry`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CobImportInfo
+{
+    pub alias: CobImportAlias,
+    /// Names selected with `key::{..}`. If `None`, then all definitions exported by the imported file are
+    /// imported.
+    pub selection: Option<Arc<[SmolStr]>>,
+}
+
+impl CobImportInfo
+{
+    /// Checks if a def path in the imported file is selected by this import.
+    pub fn selects(&self, path: &str) -> bool
+    {
+        self.selection
+            .as_ref()
+            .is_none_or(|selection| def_path_is_selected(path, selection))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CobImport
 {
@@ -178,9 +282,7 @@ impl CobImport
         };
 
         if start_fill.len() != 0 && !start_fill.ends_with_newline() {
-            tracing::warn!("failed parsing import section at {} that doesn't start on newline",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "#import section doesn't start on a new line"));
         }
 
         let (mut item_fill, mut remaining) = CobFill::parse(remaining);
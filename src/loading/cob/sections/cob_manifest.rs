@@ -105,11 +105,15 @@ impl Default for ManifestKey
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// {file} as {key}
+/// {lazy} {file} as {key}
+///
+/// Files marked `lazy` are only loaded when one of their scenes is requested or a loaded file imports them.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CobManifestEntry
 {
     pub entry_fill: CobFill,
+    /// Fill after the `lazy` keyword. `None` if the entry is loaded eagerly.
+    pub lazy_fill: Option<CobFill>,
     pub file: CobManifestFile,
     pub as_fill: CobFill,
     pub key_fill: CobFill,
@@ -121,6 +125,10 @@ impl CobManifestEntry
     pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
     {
         self.entry_fill.write_to_or_else(writer, "\n")?;
+        if let Some(lazy_fill) = &self.lazy_fill {
+            writer.write_bytes("lazy".as_bytes())?;
+            lazy_fill.write_to_or_else(writer, " ")?;
+        }
         self.file.write_to(writer)?;
         self.as_fill.write_to_or_else(writer, " ")?;
         writer.write_bytes("as".as_bytes())?;
@@ -131,32 +139,55 @@ impl CobManifestEntry
 
     pub fn try_parse(entry_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
     {
-        let Ok((file, remaining)) = CobManifestFile::parse(content) else {
+        let (lazy_fill, file_content) = match tag::<_, _, ()>("lazy").parse(content) {
+            Ok((remaining, _)) => {
+                let (lazy_fill, remaining) = CobFill::parse(remaining);
+                (Some(lazy_fill), remaining)
+            }
+            Err(_) => (None, content),
+        };
+        let Ok((file, remaining)) = CobManifestFile::parse(file_content) else {
+            if lazy_fill.is_some() {
+                return Err(span_diagnostic_error(file_content, "expected file path after 'lazy' in manifest entry"));
+            }
             return Ok((None, entry_fill, content));
         };
         if !entry_fill.ends_with_newline() {
-            tracing::warn!("manifest entry doesn't start on a new line at {}", get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "manifest entry doesn't start on a new line"));
+        }
+        if let Some(lazy_fill) = &lazy_fill {
+            if lazy_fill.len() == 0 {
+                return Err(span_diagnostic_error(file_content, "expected whitespace after 'lazy' in manifest entry"));
+            }
+            if file == CobManifestFile::SelfRef {
+                return Err(span_diagnostic_error(file_content, "'self' manifest entries can't be lazy"));
+            }
         }
         let (as_fill, remaining) = CobFill::parse(remaining);
         if as_fill.len() == 0 {
-            tracing::warn!("no fill/whitespace before manifest 'as' at {}", get_location(remaining).as_str());
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(remaining, "expected whitespace before 'as' in manifest entry"));
         }
-        let (remaining, _) = tag("as").parse(remaining)?;
+        let Ok((remaining, _)) = tag::<_, _, ()>("as").parse(remaining) else {
+            return Err(span_diagnostic_error(remaining, "expected 'as' after file name in manifest entry"));
+        };
         let (key_fill, remaining) = CobFill::parse(remaining);
         if key_fill.len() == 0 {
-            tracing::warn!("no fill/whitespace after manifest 'as' at {}", get_location(remaining).as_str());
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(remaining, "expected whitespace after 'as' in manifest entry"));
         }
         let (key, remaining) = ManifestKey::parse(remaining)?;
         let (next_fill, remaining) = CobFill::parse(remaining);
         Ok((
-            Some(Self { entry_fill, file, as_fill, key_fill, key }),
+            Some(Self { entry_fill, lazy_fill, file, as_fill, key_fill, key }),
             next_fill,
             remaining,
         ))
     }
+
+    /// Returns `true` if the entry's file is loaded on demand.
+    pub fn is_lazy(&self) -> bool
+    {
+        self.lazy_fill.is_some()
+    }
 }
 
 impl Default for CobManifestEntry
@@ -165,6 +196,7 @@ impl Default for CobManifestEntry
     {
         Self {
             entry_fill: CobFill::new("\n"),
+            lazy_fill: None,
             file: Default::default(),
             as_fill: CobFill::new(" "),
             key_fill: CobFill::new(" "),
@@ -202,9 +234,7 @@ impl CobManifest
         };
 
         if start_fill.len() != 0 && !start_fill.ends_with_newline() {
-            tracing::warn!("failed parsing manifest section at {} that doesn't start on newline",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "#manifest section doesn't start on a new line"));
         }
 
         let (mut item_fill, mut remaining) = CobFill::parse(remaining);
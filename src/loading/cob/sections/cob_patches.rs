@@ -0,0 +1,157 @@
+use nom::bytes::complete::tag;
+use nom::combinator::recognize;
+use nom::multi::separated_list1;
+use nom::sequence::preceded;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// {manifest key}::{scene path}
+///     {scene items}
+///
+/// Overlays scene items onto a scene node in another file, e.g. `base.menu::main_menu::footer`. The items are
+/// applied like the contents of a scene macro call, so `-"node"` can be used to remove scene nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobScenePatch
+{
+    pub start_fill: CobFill,
+    pub file: ManifestKey,
+    /// Path to the patched scene node, starting at the root scene (e.g. `main_menu::footer`).
+    pub path: SmolStr,
+    pub entries: Vec<CobSceneLayerEntry>,
+}
+
+impl CobScenePatch
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, "\n")?;
+        self.file.write_to(writer)?;
+        writer.write_bytes(SCENE_PATH_SEPARATOR.as_bytes())?;
+        writer.write_bytes(self.path.as_bytes())?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((file, remaining)) = ManifestKey::parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+        if start_fill.ends_newline_then_num_spaces() != Some(0) {
+            return Err(span_diagnostic_error(
+                content,
+                "patch target doesn't start on a new line with zero indentation",
+            ));
+        }
+        let Ok((remaining, path)) = preceded(
+            tag(SCENE_PATH_SEPARATOR),
+            recognize(separated_list1(tag(SCENE_PATH_SEPARATOR), anything_identifier)),
+        )
+        .parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a scene path after the manifest key (e.g. `base.menu::main_menu::footer`)",
+            ));
+        };
+
+        let (item_fill, remaining) = CobFill::parse(remaining);
+        let (entries, end_fill, remaining) =
+            CobSceneLayer::try_parse_entries(0, item_fill, remaining, "a patch target")?;
+
+        let patch = Self {
+            start_fill,
+            file,
+            path: SmolStr::from(*path.fragment()),
+            entries,
+        };
+        Ok((Some(patch), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        for (entry, other) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.recover_fill(other);
+        }
+    }
+
+    /// Gets the patched scene node.
+    pub fn scene_ref(&self) -> SceneRef
+    {
+        SceneRef {
+            file: SceneFile::ManifestKey(self.file.clone()),
+            path: ScenePath::new(self.path.as_str()),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Section that overlays scene items onto scenes in other files.
+///
+/// Patches are applied in manifest order, so patches from files loaded later take precedence.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CobPatches
+{
+    pub start_fill: CobFill,
+    pub patches: Vec<CobScenePatch>,
+}
+
+impl CobPatches
+{
+    pub fn write_to(&self, first_section: bool, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        let space = if first_section { "" } else { "\n\n" };
+        self.start_fill.write_to_or_else(writer, space)?;
+        writer.write_bytes("#patches".as_bytes())?;
+        for patch in self.patches.iter() {
+            patch.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("#patches").parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+
+        if start_fill.len() != 0 && !start_fill.ends_with_newline() {
+            return Err(span_diagnostic_error(content, "#patches section doesn't start on a new line"));
+        }
+
+        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
+        let mut patches = vec![];
+
+        let end_fill = loop {
+            match rc(remaining, move |rm| CobScenePatch::try_parse(item_fill, rm)) {
+                Ok((Some(patch), next_fill, after_patch)) => {
+                    patches.push(patch);
+                    item_fill = next_fill;
+                    remaining = after_patch;
+                }
+                Ok((None, end_fill, after_end)) => {
+                    remaining = after_end;
+                    break end_fill;
+                }
+                Err(err) => {
+                    // Resync at the next patch, or at the next section.
+                    let error_at = try_recover(err)?;
+                    let next_patch = skip_to_line(remaining, error_at, |indent, _| indent == 0);
+                    (item_fill, remaining) = CobFill::parse(next_patch);
+                }
+            }
+        };
+
+        let patches = Self { start_fill, patches };
+        Ok((Some(patches), end_fill, remaining))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
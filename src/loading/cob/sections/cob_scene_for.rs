@@ -0,0 +1,210 @@
+use nom::bytes::complete::tag;
+use nom::Parser;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loop that repeats scene nodes for each element of an array, e.g. `#for row in $settings_rows`.
+///
+/// Each element is bound to a local constant (`$row`). If the element is a map, then its fields are also bound
+/// (`$row::label`). Loops are expanded when the containing scene layer is resolved. Each scene node in the loop
+/// content is renamed with the element index (`"row"` becomes `"row_0"`, `"row_1"`, etc.) so nodes are stable
+/// across reloads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobSceneFor
+{
+    /// Fill before `#for`.
+    ///
+    /// Whitespace between `#for` and the most recent newline is used to control scene layer depth.
+    pub start_fill: CobFill,
+    pub binding_fill: CobFill,
+    pub binding: SmolStr,
+    /// Fill before `in`.
+    pub in_fill: CobFill,
+    /// The value is expected to handle its own fill.
+    pub iterable: CobValue,
+    pub entries: Vec<CobSceneLayerEntry>,
+}
+
+impl CobSceneFor
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.start_fill.write_to_or_else(writer, "\n")?;
+        writer.write_bytes("#for".as_bytes())?;
+        self.binding_fill.write_to_or_else(writer, " ")?;
+        writer.write_bytes(self.binding.as_bytes())?;
+        self.in_fill.write_to_or_else(writer, " ")?;
+        writer.write_bytes("in".as_bytes())?;
+        self.iterable.write_to_with_space(writer, " ")?;
+        for entry in self.entries.iter() {
+            entry.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_parse(start_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("#for").parse(content) else {
+            return Ok((None, start_fill, content));
+        };
+
+        // Extract loop indent
+        let Some(for_indent) = start_fill.ends_newline_then_num_spaces() else {
+            return Err(span_diagnostic_error(
+                content,
+                "#for isn't on a separate line from the previous item",
+            ));
+        };
+
+        // Header
+        let (binding_fill, remaining) = CobFill::parse(remaining);
+        let Ok((remaining, binding)) = snake_identifier(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a snake-case binding name after #for (e.g. `#for row in $rows`)",
+            ));
+        };
+        if binding_fill.len() == 0 {
+            return Err(span_diagnostic_error(content, "expected whitespace after #for"));
+        }
+        let (in_fill, remaining) = CobFill::parse(remaining);
+        let Ok((after_in, _)) = tag::<_, _, ()>("in").parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected `in` after #for binding name",
+            ));
+        };
+        if in_fill.len() == 0 {
+            return Err(span_diagnostic_error(remaining, "expected whitespace before `in`"));
+        }
+        let (value_fill, remaining) = CobFill::parse(after_in);
+        if value_fill.len() == 0 {
+            return Err(span_diagnostic_error(after_in, "expected whitespace after `in`"));
+        }
+        let (Some(iterable), item_fill, remaining) = rc(remaining, move |rm| CobValue::try_parse(value_fill, rm))?
+        else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected an array to iterate over after `in` (e.g. `$rows`)",
+            ));
+        };
+
+        // Content
+        let (entries, end_fill, remaining) =
+            CobSceneLayer::try_parse_entries(for_indent, item_fill, remaining, "a #for header")?;
+        for entry in entries.iter() {
+            let err_msg = match entry {
+                CobSceneLayerEntry::Layer(_)
+                | CobSceneLayerEntry::SceneMacroCall(_)
+                | CobSceneLayerEntry::For(_)
+                | CobSceneLayerEntry::Condition(_) => continue,
+                CobSceneLayerEntry::Loadable(_) => "loadables",
+                CobSceneLayerEntry::LoadableMacroCall(_) => "loadable macro invocations",
+                CobSceneLayerEntry::SceneMacroCommand(_) => "scene macro commands",
+            };
+            return Err(span_diagnostic_error(
+                content,
+                format!("#for loops cannot directly contain {err_msg}; put them in a scene node inside the loop"),
+            ));
+        }
+
+        let entry = Self {
+            start_fill,
+            binding_fill,
+            binding: SmolStr::from(*binding.fragment()),
+            in_fill,
+            iterable,
+            entries,
+        };
+        Ok((Some(entry), end_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.start_fill.recover(&other.start_fill);
+        self.binding_fill.recover(&other.binding_fill);
+        self.in_fill.recover(&other.in_fill);
+        self.iterable.recover_fill(&other.iterable);
+        for (entry, other) in self.entries.iter_mut().zip(other.entries.iter()) {
+            entry.recover_fill(other);
+        }
+    }
+
+    /// Gets the local constants for one element of the iterated array.
+    fn bindings(&self, element: &CobValue) -> Vec<(SmolStr, CobConstantValue)>
+    {
+        let mut bindings = vec![(self.binding.clone(), CobConstantValue::Value(element.clone()))];
+        if let CobValue::Map(map) = element {
+            bindings.extend(map.entries.iter().filter_map(|entry| {
+                let CobMapEntry::KeyValue(CobMapKeyValue {
+                    key: CobMapKey::FieldName { name, .. }, value, ..
+                }) = entry
+                else {
+                    return None;
+                };
+                let path = SmolStr::from(format!("{}::{}", self.binding.as_str(), name.as_str()));
+                Some((path, CobConstantValue::Value(value.clone())))
+            }));
+        }
+        bindings
+    }
+
+    /// Expands the loop into scene nodes.
+    ///
+    /// The loop content is fully resolved for each element, since element bindings are only available during
+    /// expansion.
+    ///
+    /// Loops in scene macros are expanded when the macro is defined, so they can't iterate over the macro's
+    /// parameters.
+    pub fn resolve(&mut self, resolver: &mut CobResolver) -> Result<Vec<CobSceneLayerEntry>, String>
+    {
+        if self.iterable.resolve(&resolver.loadables)?.is_some() {
+            return Err(format!("#for {} cannot iterate over a value group", self.binding.as_str()));
+        }
+        // Constants that are still present after resolving are parameter placeholders.
+        if let CobValue::Constant(constant) = &self.iterable {
+            return Err(format!(
+                "#for {} cannot iterate over scene macro parameter ${}; loops in scene macros are expanded when \
+                the macro is defined, so the array must be a constant or written inline",
+                self.binding.as_str(),
+                constant.path.path.as_str()
+            ));
+        }
+        let CobValue::Array(array) = &self.iterable else {
+            return Err(format!("#for {} can only iterate over an array", self.binding.as_str()));
+        };
+
+        let mut expanded = Vec::with_capacity(array.entries.len() * self.entries.len());
+        for (idx, element) in array.entries.iter().enumerate() {
+            // Resolve the content with this element's bindings.
+            let prev_params = resolver
+                .loadables
+                .constants
+                .push_params(self.bindings(element));
+            let mut entries = self.entries.clone();
+            let result =
+                CobSceneLayer::resolve_entries_impl("#for", &mut entries, resolver, SceneResolveMode::Full);
+            resolver.loadables.constants.truncate_params(prev_params);
+            result?;
+
+            // Give nodes stable names.
+            for entry in entries {
+                let CobSceneLayerEntry::Layer(mut layer) = entry else {
+                    return Err(format!("#for {} content expanded to items that aren't scene nodes",
+                        self.binding.as_str()));
+                };
+                if !layer.name.is_empty() {
+                    layer.name = CobSceneNodeName(SmolStr::from(format!("{}_{idx}", layer.name.as_str())));
+                }
+                expanded.push(CobSceneLayerEntry::Layer(layer));
+            }
+        }
+
+        Ok(expanded)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -3,7 +3,7 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
 use nom::combinator::{map, success};
-use nom::sequence::terminated;
+use nom::sequence::{preceded, terminated};
 use nom::Parser;
 use smol_str::SmolStr;
 
@@ -33,9 +33,10 @@ impl CobSceneNodeName
             tag("\""),
         )
         .parse(remaining) else {
-            tracing::warn!("failed parsing scene node name at {}; name is not snake-case (e.g. a_b_c)",
-                get_location(content).as_str());
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                content,
+                "invalid scene node name; names must contain only letters, numbers, and underscores (e.g. \"a_b_c\")",
+            ));
         };
 
         Ok((Some(Self(SmolStr::from(name))), remaining))
@@ -49,9 +50,14 @@ impl CobSceneNodeName
 pub enum CobSceneLayerEntry
 {
     Loadable(CobLoadable),
+    LoadableMacroCall(CobLoadableMacroCall),
     SceneMacroCall(CobSceneMacroCall),
     SceneMacroCommand(CobSceneMacroCommand),
     Layer(CobSceneLayer),
+    /// A `#for` loop that repeats scene nodes for each element of an array.
+    For(CobSceneFor),
+    /// A condition that applies to the next item in the layer.
+    Condition(CobCondition),
 }
 
 impl CobSceneLayerEntry
@@ -62,6 +68,9 @@ impl CobSceneLayerEntry
             Self::Loadable(entry) => {
                 entry.write_to(writer)?;
             }
+            Self::LoadableMacroCall(entry) => {
+                entry.write_to(writer)?;
+            }
             Self::SceneMacroCall(entry) => {
                 entry.write_to(writer)?;
             }
@@ -71,6 +80,12 @@ impl CobSceneLayerEntry
             Self::Layer(entry) => {
                 entry.write_to(writer)?;
             }
+            Self::For(entry) => {
+                entry.write_to(writer)?;
+            }
+            Self::Condition(entry) => {
+                entry.write_to(writer)?;
+            }
         }
         Ok(())
     }
@@ -88,9 +103,7 @@ impl CobSceneLayerEntry
                 // End-of-file
                 return Ok((None, fill, content));
             }
-            tracing::warn!("failed parsing scene at {}; encountered something that isn't on a separate line",
-                get_location(content));
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(content, "scene item isn't on a separate line"));
         };
 
         // The next item isn't on the active layer.
@@ -109,6 +122,10 @@ impl CobSceneLayerEntry
             (Some(item), fill, remaining) => return Ok((Some(Self::Loadable(item)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobLoadableMacroCall::try_parse(fill, c))? {
+            (Some(item), fill, remaining) => return Ok((Some(Self::LoadableMacroCall(item)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
         let fill = match rc(content, move |c| CobSceneMacroCall::try_parse(fill, c))? {
             (Some(item), fill, remaining) => return Ok((Some(Self::SceneMacroCall(item)), fill, remaining)),
             (None, fill, _) => fill,
@@ -121,6 +138,14 @@ impl CobSceneLayerEntry
             (Some(item), fill, remaining) => return Ok((Some(Self::Layer(item)), fill, remaining)),
             (None, fill, _) => fill,
         };
+        let fill = match rc(content, move |c| CobSceneFor::try_parse(fill, c))? {
+            (Some(item), fill, remaining) => return Ok((Some(Self::For(item)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
+        let fill = match rc(content, move |c| CobCondition::try_parse_item(fill, c))? {
+            (Some(item), fill, remaining) => return Ok((Some(Self::Condition(item)), fill, remaining)),
+            (None, fill, _) => fill,
+        };
 
         Ok((None, fill, content))
     }
@@ -131,6 +156,9 @@ impl CobSceneLayerEntry
             (Self::Loadable(entry), Self::Loadable(other_entry)) => {
                 entry.recover_fill(other_entry);
             }
+            (Self::LoadableMacroCall(entry), Self::LoadableMacroCall(other_entry)) => {
+                entry.recover_fill(other_entry);
+            }
             (Self::SceneMacroCall(entry), Self::SceneMacroCall(other_entry)) => {
                 entry.recover_fill(other_entry);
             }
@@ -140,6 +168,12 @@ impl CobSceneLayerEntry
             (Self::Layer(entry), Self::Layer(other_entry)) => {
                 entry.recover_fill(other_entry);
             }
+            (Self::For(entry), Self::For(other_entry)) => {
+                entry.recover_fill(other_entry);
+            }
+            (Self::Condition(entry), Self::Condition(other_entry)) => {
+                entry.recover_fill(other_entry);
+            }
             _ => (),
         }
     }
@@ -157,6 +191,9 @@ impl CobSceneLayerEntry
                     entry.resolve(&resolver.loadables)?;
                 }
             },
+            Self::LoadableMacroCall(entry) => {
+                return entry.resolve(resolver, resolve_mode).map(|e| Some(e));
+            }
             Self::SceneMacroCall(entry) => {
                 // Upgrade resolve mode to ensure macro call gets resolved properly.
                 let resolve_mode = if resolve_mode == SceneResolveMode::OneLayerSceneOnly {
@@ -174,6 +211,15 @@ impl CobSceneLayerEntry
                     entry.resolve(resolver, resolve_mode)?;
                 }
             },
+            // Loops are always fully expanded, since their bindings are only available during expansion.
+            Self::For(entry) => {
+                return entry.resolve(resolver).map(|e| Some(e));
+            }
+            Self::Condition(_) => {
+                return Err(String::from(
+                    "unexpected condition; conditions should be applied before resolving",
+                ));
+            }
         }
 
         Ok(None)
@@ -196,6 +242,99 @@ pub enum SceneResolveMode
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Reference to a scene in another file, e.g. `=> widgets.card::card`.
+///
+/// The file is a manifest key, and the scene must be a root scene in that file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CobSceneInstanceRef
+{
+    /// Fill before `=>`.
+    pub arrow_fill: CobFill,
+    pub file_fill: CobFill,
+    pub file: ManifestKey,
+    pub scene: SmolStr,
+}
+
+impl CobSceneInstanceRef
+{
+    pub fn write_to(&self, writer: &mut impl RawSerializer) -> Result<(), std::io::Error>
+    {
+        self.arrow_fill.write_to_or_else(writer, " ")?;
+        writer.write_bytes("=>".as_bytes())?;
+        self.file_fill.write_to_or_else(writer, " ")?;
+        self.file.write_to(writer)?;
+        writer.write_bytes(SCENE_PATH_SEPARATOR.as_bytes())?;
+        writer.write_bytes(self.scene.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn try_parse(arrow_fill: CobFill, content: Span) -> Result<(Option<Self>, CobFill, Span), SpanError>
+    {
+        let Ok((remaining, _)) = tag::<_, _, ()>("=>").parse(content) else {
+            return Ok((None, arrow_fill, content));
+        };
+        if arrow_fill.ends_newline_then_num_spaces().is_some() {
+            return Err(span_diagnostic_error(
+                content,
+                "=> must be on the same line as the scene node name",
+            ));
+        }
+
+        let (file_fill, remaining) = CobFill::parse(remaining);
+        let Ok((file, after_file)) = ManifestKey::parse(remaining) else {
+            return Err(span_diagnostic_error(
+                remaining,
+                "expected a manifest key after => (e.g. `widgets.card::card`)",
+            ));
+        };
+        if file_fill.ends_newline_then_num_spaces().is_some() {
+            return Err(span_diagnostic_error(
+                remaining,
+                "scene reference must be on the same line as =>",
+            ));
+        }
+        let Ok((after_scene, scene)) = preceded(tag(SCENE_PATH_SEPARATOR), anything_identifier).parse(after_file)
+        else {
+            return Err(span_diagnostic_error(
+                after_file,
+                "expected a scene name after the manifest key (e.g. `widgets.card::card`)",
+            ));
+        };
+        if after_scene.fragment().starts_with(SCENE_PATH_SEPARATOR) {
+            return Err(span_diagnostic_error(
+                after_file,
+                "only root scenes can be referenced (e.g. `widgets.card::card`)",
+            ));
+        }
+
+        let (next_fill, remaining) = CobFill::parse(after_scene);
+        let entry = Self {
+            arrow_fill,
+            file_fill,
+            file,
+            scene: SmolStr::from(*scene.fragment()),
+        };
+        Ok((Some(entry), next_fill, remaining))
+    }
+
+    pub fn recover_fill(&mut self, other: &Self)
+    {
+        self.arrow_fill.recover(&other.arrow_fill);
+        self.file_fill.recover(&other.file_fill);
+    }
+
+    /// Gets the referenced scene.
+    pub fn scene_ref(&self) -> SceneRef
+    {
+        SceneRef {
+            file: SceneFile::ManifestKey(self.file.clone()),
+            path: ScenePath::new(self.scene.as_str()),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CobSceneLayer
 {
@@ -204,6 +343,10 @@ pub struct CobSceneLayer
     /// Whitespace between the name and most recent newline is used to control scene layer depth.
     pub name_fill: CobFill,
     pub name: CobSceneNodeName,
+    /// Location of the layer name in its source file.
+    pub location: CobItemLocation,
+    /// A scene from another file to spawn inside this node, e.g. `"slot" => widgets.card::card`.
+    pub instance_ref: Option<CobSceneInstanceRef>,
     pub entries: Vec<CobSceneLayerEntry>,
 }
 
@@ -213,6 +356,9 @@ impl CobSceneLayer
     {
         self.name_fill.write_to_or_else(writer, "\n")?;
         self.name.write_to(writer)?;
+        if let Some(instance_ref) = &self.instance_ref {
+            instance_ref.write_to(writer)?;
+        }
         for entry in self.entries.iter() {
             entry.write_to(writer)?;
         }
@@ -225,51 +371,106 @@ impl CobSceneLayer
         let (Some(name), remaining) = CobSceneNodeName::try_parse(content)? else {
             return Ok((None, name_fill, content));
         };
+        let location = CobItemLocation::from_span_with_len(content, name.len() + 2);
 
         // Extract layer indent
         let Some(layer_indent) = name_fill.ends_newline_then_num_spaces() else {
-            tracing::warn!("failed parsing scene at {}; node name is not on a separate line from the previous item",
-                get_location(remaining));
-            return Err(span_verify_error(content));
+            return Err(span_diagnostic_error(
+                content,
+                "scene node name isn't on a separate line from the previous item",
+            ));
         };
 
+        let (item_fill, remaining) = CobFill::parse(remaining);
+        let (instance_ref, item_fill, remaining) =
+            rc(remaining, move |rm| CobSceneInstanceRef::try_parse(item_fill, rm))?;
+        let (entries, end_fill, remaining) =
+            Self::try_parse_entries(layer_indent, item_fill, remaining, "a scene node name")?;
+
+        Ok((
+            Some(Self { name_fill, name, location, instance_ref, entries }),
+            end_fill,
+            remaining,
+        ))
+    }
+
+    /// Parses the items of a layer-like scene item whose header is indented by `layer_indent`.
+    ///
+    /// `item_fill` is the fill after the header, and `header` describes the header in error messages.
+    pub(crate) fn try_parse_entries<'a>(
+        layer_indent: usize,
+        mut item_fill: CobFill,
+        mut remaining: Span<'a>,
+        header: &str,
+    ) -> Result<(Vec<CobSceneLayerEntry>, CobFill, Span<'a>), SpanError<'a>>
+    {
         // Get content indent from first item_fill.
-        let (mut item_fill, mut remaining) = CobFill::parse(remaining);
         let Some(content_indent) = item_fill.ends_newline_then_num_spaces() else {
             if remaining.fragment().len() == 0 {
                 // End-of-file
-                return Ok((Some(Self { name_fill, name, entries: vec![] }), item_fill, remaining));
+                return Ok((vec![], item_fill, remaining));
             }
-            tracing::warn!("failed parsing scene at {}; first item after a node name isn't on a separate line",
-                get_location(remaining));
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(
+                remaining,
+                format!("first item after {header} isn't on a separate line"),
+            ));
         };
 
         // Collect entries.
         let mut entries = vec![];
         let end_fill = loop {
             // Note: this will properly handle the case where content_indent <= layer_indent.
-            match rc(remaining, move |rm| {
+            let result = rc(remaining, move |rm| {
                 CobSceneLayerEntry::try_parse(layer_indent, content_indent, item_fill, rm)
-            })? {
-                (Some(entry), next_fill, after_entry) => {
+            })
+            .and_then(|result| match result {
+                // Items in this layer that fail to parse are invalid, unless they close a containing group.
+                (None, end_fill, after_end)
+                    if end_fill
+                        .ends_newline_then_num_spaces()
+                        .is_some_and(|indent| indent > layer_indent)
+                        && !after_end.fragment().is_empty()
+                        && !after_end.fragment().starts_with(['}', ')', ']', '\\']) =>
+                {
+                    Err(span_diagnostic_error(
+                        after_end,
+                        "expected a scene item (e.g. a loadable, macro call, or scene node name)",
+                    ))
+                }
+                result => Ok(result),
+            });
+
+            match result {
+                Ok((Some(entry), next_fill, after_entry)) => {
                     entries.push(entry);
                     item_fill = next_fill;
                     remaining = after_entry;
                 }
-                (None, end_fill, after_end) => {
+                Ok((None, end_fill, after_end)) => {
                     remaining = after_end;
                     break end_fill;
                 }
+                Err(err) => {
+                    // Resync at the next item in this layer, or at the end of the layer.
+                    // - Lines that start with closing brackets are assumed to be the tail of the failed item.
+                    let error_at = try_recover(err)?;
+                    let next_item = skip_to_line(remaining, error_at, |indent, line| {
+                        indent <= content_indent && !line.starts_with(['}', ')', ']'])
+                    });
+                    (item_fill, remaining) = CobFill::parse(next_item);
+                }
             }
         };
 
-        Ok((Some(Self { name_fill, name, entries }), end_fill, remaining))
+        Ok((entries, end_fill, remaining))
     }
 
     pub fn recover_fill(&mut self, other: &Self)
     {
         self.name_fill.recover(&other.name_fill);
+        if let (Some(instance_ref), Some(other_ref)) = (&mut self.instance_ref, &other.instance_ref) {
+            instance_ref.recover_fill(other_ref);
+        }
         for (entry, other) in self.entries.iter_mut().zip(other.entries.iter()) {
             entry.recover_fill(other);
         }
@@ -287,9 +488,13 @@ impl CobSceneLayer
         resolve_mode: SceneResolveMode,
     ) -> Result<(), String>
     {
+        // Names of nodes generated by #for loops in this layer.
+        let mut loop_names: Vec<SmolStr> = vec![];
+
         let mut idx = 0;
         while idx < entries.len() {
             // If resolving the entry returns a group of entries, they need to be flattened into this layer.
+            let is_loop = matches!(entries[idx], CobSceneLayerEntry::For(_));
             let Some(mut group) = entries[idx].resolve(resolver, resolve_mode)? else {
                 idx += 1;
                 continue;
@@ -298,6 +503,13 @@ impl CobSceneLayer
             // Remove the old entry.
             entries.remove(idx);
 
+            if is_loop {
+                loop_names.extend(group.iter().filter_map(|entry| match entry {
+                    CobSceneLayerEntry::Layer(layer) if !layer.name.is_empty() => Some(layer.name.0.clone()),
+                    _ => None,
+                }));
+            }
+
             // Flatten the group into the layer.
             for entry in group.drain(..) {
                 match entry {
@@ -305,6 +517,10 @@ impl CobSceneLayer
                         entries.insert(idx, entry);
                         idx += 1;
                     }
+                    CobSceneLayerEntry::LoadableMacroCall(_) => {
+                        return Err(format!("failed resolving scene layer named {}; loadable macro call unexpectedly not resolved",
+                            name));
+                    }
                     CobSceneLayerEntry::SceneMacroCall(_) => {
                         return Err(format!("failed resolving scene layer named {}; scene macro call unexpectedly not resolved",
                             name));
@@ -315,10 +531,35 @@ impl CobSceneLayer
                             name),
                         );
                     }
+                    CobSceneLayerEntry::For(_) => {
+                        return Err(
+                            format!("failed resolving scene layer named {}; #for loop unexpectedly not expanded",
+                            name),
+                        );
+                    }
+                    CobSceneLayerEntry::Condition(_) => {
+                        return Err(format!("failed resolving scene layer named {}; unexpected condition", name));
+                    }
                 }
             }
         }
 
+        // Nodes generated by loops are named `{name}_{idx}`, which can collide with other nodes in the layer.
+        for loop_name in loop_names.iter() {
+            let count = entries
+                .iter()
+                .filter(|entry| matches!(entry, CobSceneLayerEntry::Layer(layer) if layer.name.0 == *loop_name))
+                .count();
+            if count > 1 {
+                return Err(format!(
+                    "failed resolving scene layer named {}; scene node {:?} generated by a #for loop has the same \
+                    name as another node in the layer",
+                    name,
+                    loop_name.as_str()
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -352,9 +593,10 @@ impl CobScenes
         };
 
         if start_fill.len() != 0 && !start_fill.ends_with_newline() {
-            tracing::warn!("failed parsing scenes section at {} that doesn't start on newline",
-                get_location(remaining).as_str());
-            return Err(span_verify_error(remaining));
+            return Err(span_diagnostic_error(
+                remaining,
+                "#scenes section doesn't start on a new line",
+            ));
         }
 
         let (mut item_fill, mut remaining) = CobFill::parse(remaining);
@@ -362,21 +604,36 @@ impl CobScenes
 
         let end_fill = loop {
             let item_depth = item_fill.ends_newline_then_num_spaces();
-            match rc(remaining, move |rm| CobSceneLayer::try_parse(item_fill, rm))? {
-                (Some(entry), next_fill, after_entry) => {
-                    if item_depth != Some(0) {
-                        tracing::warn!("failed parsing scene at {}; scene is assessed to be on base layer \
-                            but doesn't start with a newline", get_location(remaining).as_str());
-                        return Err(span_verify_error(remaining));
-                    }
+            let result =
+                rc(remaining, move |rm| CobSceneLayer::try_parse(item_fill, rm)).and_then(|result| match result {
+                    (Some(_), _, _) if item_depth != Some(0) => Err(span_diagnostic_error(
+                        remaining,
+                        "scene root node doesn't start on a new line with zero indentation",
+                    )),
+                    result => Ok(result),
+                });
+
+            match result {
+                Ok((Some(entry), next_fill, after_entry)) => {
                     scenes.push(entry);
                     item_fill = next_fill;
                     remaining = after_entry;
                 }
-                (None, end_fill, after_end) => {
+                Ok((None, end_fill, after_end)) => {
                     remaining = after_end;
                     break end_fill;
                 }
+                Err(err) => {
+                    // Resync at the next scene, or at the next section.
+                    let error_at = try_recover(err)?;
+                    let next_scene = skip_to_line(remaining, error_at, |indent, line| {
+                        indent == 0
+                            && (line.starts_with('"')
+                                || starts_with_section_keyword(line)
+                                || CobCondition::starts_with_condition(line))
+                    });
+                    (item_fill, remaining) = CobFill::parse(next_scene);
+                }
             }
         };
 
@@ -1,11 +1,19 @@
+mod cob_assets;
 mod cob_commands;
 mod cob_defs;
+mod cob_export;
 mod cob_import;
 mod cob_manifest;
+mod cob_patches;
+mod cob_scene_for;
 mod cob_scenes;
 
+pub use cob_assets::*;
 pub use cob_commands::*;
 pub use cob_defs::*;
+pub use cob_export::*;
 pub use cob_import::*;
 pub use cob_manifest::*;
+pub use cob_patches::*;
+pub use cob_scene_for::*;
 pub use cob_scenes::*;
@@ -11,6 +11,7 @@ use crate::prelude::*;
 
 struct CobAssetLoader
 {
+    diagnostics: CobDiagnosticsQueue,
     #[cfg(feature = "editor")]
     registry: CobHashRegistry,
 }
@@ -39,47 +40,75 @@ impl AssetLoader for CobAssetLoader
         let mut string = String::default();
         reader.read_to_string(&mut string).await?;
 
-        // When using the editor, we may be able to discard incoming files if they were saved by the editor.
-        #[cfg(feature = "editor")]
-        let hash = CobFileHash::new(string.as_bytes());
-        #[cfg(feature = "editor")]
-        {
-            if !self.registry.try_refresh_file(&file, hash) {
-                tracing::info!("ignoring file reload for {}; file did not change since last save", file.as_str());
-                return Ok(CobAssetFile::Ignore);
-            }
-        }
+        parse_cob_file(
+            &file,
+            &string,
+            &self.diagnostics,
+            #[cfg(feature = "editor")]
+            &self.registry,
+        )
+    }
 
-        // Parse the raw file data.
-        let data = match Cob::parse(Span::new_extra(&string, CobLocationMetadata { file: file.as_str() })) {
-            Ok(data) => data,
-            Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
-                let nom::error::Error { input, code } = err;
-                return Err(CobAssetLoaderError::CobParsing(
-                    format!("error at {}: {:?}", get_location(input).as_str(), code),
-                ));
-            }
-            Err(nom::Err::Incomplete(err)) => {
-                return Err(CobAssetLoaderError::CobParsing(
-                    format!("insufficient data in {}: {:?}", file.as_str(), err),
-                ));
-            }
-        };
+    fn extensions(&self) -> &[&str]
+    {
+        &[".cob"]
+    }
+}
 
-        #[cfg(not(feature = "editor"))]
-        {
-            return Ok(CobAssetFile::File { data });
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Parses the raw contents of a COB file.
+///
+/// Used by the asset loader and for COB files loaded from strings.
+pub(crate) fn parse_cob_file(
+    file: &str,
+    string: &str,
+    diagnostics: &CobDiagnosticsQueue,
+    #[cfg(feature = "editor")] registry: &CobHashRegistry,
+) -> Result<CobAssetFile, CobAssetLoaderError>
+{
+    // When using the editor, we may be able to discard incoming files if they were saved by the editor.
+    #[cfg(feature = "editor")]
+    let hash = CobFileHash::new(string.as_bytes());
+    #[cfg(feature = "editor")]
+    {
+        if !registry.try_refresh_file(file, hash) {
+            tracing::info!("ignoring file reload for {}; file did not change since last save", file);
+            return Ok(CobAssetFile::Ignore);
         }
+    }
 
-        #[cfg(feature = "editor")]
-        {
-            return Ok(CobAssetFile::File { hash, data });
+    // Parse the raw file data.
+    // - Clear stale diagnostics in case a previous parse on this thread was interrupted.
+    // - Errors are recovered from where possible so the parts of the file that parsed cleanly can be used.
+    let _ = take_cob_diagnostics();
+    let span = Span::new_extra(string, CobLocationMetadata { file });
+    let data = match Cob::parse_with_recovery(span) {
+        Ok((data, errors)) => {
+            for error in errors.iter() {
+                tracing::error!("failed parsing part of COB file {}; the affected content will be \
+                    skipped\n{}", file, error);
+            }
+            diagnostics.extend(errors);
+            data
+        }
+        Err(err) => {
+            let diagnostic = CobDiagnostic::from_span_error(file, err);
+            diagnostics.push(diagnostic.clone());
+            return Err(CobAssetLoaderError::CobParsing(diagnostic));
         }
+    };
+    // Any remaining recorded diagnostics came from branches that were backtracked.
+    let _ = take_cob_diagnostics();
+
+    #[cfg(not(feature = "editor"))]
+    {
+        return Ok(CobAssetFile::File { data });
     }
 
-    fn extensions(&self) -> &[&str]
+    #[cfg(feature = "editor")]
     {
-        &[".cob"]
+        return Ok(CobAssetFile::File { hash, data });
     }
 }
 
@@ -93,9 +122,9 @@ pub enum CobAssetLoaderError
     /// An [IO Error](std::io::Error).
     #[error("Could not read the CobAssetFile file: {0}")]
     Io(#[from] std::io::Error),
-    /// A COB Error.
-    #[error("Could not parse the CobAssetFile data: {0}")]
-    CobParsing(String),
+    /// A COB parsing error.
+    #[error("Could not parse the CobAssetFile data:\n{0}")]
+    CobParsing(CobDiagnostic),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -124,9 +153,14 @@ impl Plugin for CobAssetLoaderPlugin
 {
     fn build(&self, app: &mut App)
     {
+        let diagnostics = app
+            .world_mut()
+            .get_resource_or_init::<CobDiagnosticsQueue>()
+            .clone();
+
         #[cfg(not(feature = "editor"))]
         {
-            app.register_asset_loader(CobAssetLoader {});
+            app.register_asset_loader(CobAssetLoader { diagnostics });
         }
 
         #[cfg(feature = "editor")]
@@ -135,7 +169,7 @@ impl Plugin for CobAssetLoaderPlugin
                 .world_mut()
                 .get_resource_or_init::<CobHashRegistry>()
                 .clone();
-            app.register_asset_loader(CobAssetLoader { registry });
+            app.register_asset_loader(CobAssetLoader { diagnostics, registry });
         }
     }
 }
@@ -0,0 +1,63 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Collects [`CobDiagnostics`](CobDiagnostic) produced by the asset loader and by COB extraction.
+///
+/// The queue is shared with the asset loader, which runs on a different thread than the app.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct CobDiagnosticsQueue
+{
+    queue: Arc<Mutex<Vec<CobDiagnostic>>>,
+}
+
+impl CobDiagnosticsQueue
+{
+    pub(crate) fn push(&self, diagnostic: CobDiagnostic)
+    {
+        self.extend([diagnostic]);
+    }
+
+    pub(crate) fn extend(&self, diagnostics: impl IntoIterator<Item = CobDiagnostic>)
+    {
+        let Ok(mut queue) = self.queue.lock() else {
+            warn_once!("CobDiagnosticsQueue's internal mutex is poisoned, COB diagnostics will not be emitted; \
+                this warning only prints once");
+            return;
+        };
+        queue.extend(diagnostics);
+    }
+
+    pub(crate) fn take(&self) -> Vec<CobDiagnostic>
+    {
+        let Ok(mut queue) = self.queue.lock() else { return vec![] };
+        std::mem::take(&mut *queue)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn emit_cob_diagnostics(queue: Res<CobDiagnosticsQueue>, mut events: EventWriter<CobDiagnostic>)
+{
+    events.send_batch(queue.take());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct CobDiagnosticsPlugin;
+
+impl Plugin for CobDiagnosticsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<CobDiagnosticsQueue>()
+            .add_event::<CobDiagnostic>()
+            .add_systems(First, emit_cob_diagnostics.after(FileProcessingSet));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Flags that can be used in COB conditions like `#[if(wasm)]`.
+///
+/// Built-in flags:
+/// - Platforms: `wasm`, `android`, `ios`, `windows`, `macos`, `linux`, `mobile` (android or ios), `desktop`
+///   (windows, macos, or linux).
+/// - Build: `debug` (debug assertions are enabled), `hot_reload` and `editor` (the crate features of the same
+///   names are enabled).
+///
+/// Custom flags can be added with [`CobFlagsAppExt::set_cob_flag`] or by mutating this resource. For example, to
+/// gate COB content on one of your own cargo features use `app.set_cob_flag("my_feature", cfg!(feature =
+/// "my_feature"))`.
+///
+/// If the `hot_reload` feature is enabled, changing a flag will re-extract all COB files with the new flag values.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct CobFlags
+{
+    flags: HashMap<String, bool>,
+}
+
+impl CobFlags
+{
+    /// Sets a flag's value. The flag will be added if it doesn't exist.
+    pub fn set(&mut self, flag: impl AsRef<str>, value: bool)
+    {
+        let flag = flag.as_ref();
+        match self.flags.get_mut(flag) {
+            Some(prev) => *prev = value,
+            None => {
+                self.flags.insert(String::from(flag), value);
+            }
+        }
+    }
+
+    /// Gets a flag's value.
+    ///
+    /// Returns `None` if the flag doesn't exist.
+    pub fn get(&self, flag: impl AsRef<str>) -> Option<bool>
+    {
+        self.flags.get(flag.as_ref()).copied()
+    }
+
+    /// Iterates over all flags and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> + '_
+    {
+        self.flags
+            .iter()
+            .map(|(flag, value)| (flag.as_str(), *value))
+    }
+}
+
+impl Default for CobFlags
+{
+    fn default() -> Self
+    {
+        let mut flags = Self { flags: HashMap::default() };
+        flags.set("wasm", cfg!(target_family = "wasm"));
+        flags.set("android", cfg!(target_os = "android"));
+        flags.set("ios", cfg!(target_os = "ios"));
+        flags.set("windows", cfg!(target_os = "windows"));
+        flags.set("macos", cfg!(target_os = "macos"));
+        flags.set("linux", cfg!(target_os = "linux"));
+        flags.set("mobile", cfg!(any(target_os = "android", target_os = "ios")));
+        flags.set(
+            "desktop",
+            cfg!(any(target_os = "windows", target_os = "macos", target_os = "linux")),
+        );
+        flags.set("debug", cfg!(debug_assertions));
+        flags.set("hot_reload", cfg!(feature = "hot_reload"));
+        flags.set("editor", cfg!(feature = "editor"));
+        flags
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for setting [`CobFlags`].
+pub trait CobFlagsAppExt
+{
+    /// Sets a flag that can be used in COB conditions like `#[if(my_flag)]`.
+    fn set_cob_flag(&mut self, flag: impl AsRef<str>, value: bool) -> &mut Self;
+}
+
+impl CobFlagsAppExt for App
+{
+    fn set_cob_flag(&mut self, flag: impl AsRef<str>, value: bool) -> &mut Self
+    {
+        self.world_mut()
+            .get_resource_or_init::<CobFlags>()
+            .set(flag, value);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct CobFlagsPlugin;
+
+impl Plugin for CobFlagsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<CobFlags>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A COB file tracked by [`CobProject`].
+#[derive(Debug, Clone)]
+pub struct CobProjectFile
+{
+    /// The parsed file.
+    ///
+    /// If the file has syntax errors, then this only contains the parts of the file that parsed cleanly.
+    pub data: Cob,
+    /// The file's imports.
+    pub imports: HashMap<ManifestKey, CobImportInfo>,
+    /// The file after conditions have been applied, definitions have been extracted, and all commands and scenes
+    /// have been fully resolved (including macro expansion).
+    ///
+    /// Will be `None` until [`CobProject::resolve`] is called, or if the file's imports could not be resolved.
+    pub resolved: Option<Cob>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads and resolves COB files without a Bevy app.
+///
+/// Files are read from an asset directory. Loading a file will also load all files in its manifest tree. After
+/// loading, [`Self::resolve`] will resolve imports, definitions, commands, and scenes the same way they are
+/// resolved at runtime.
+///
+/// Loadables are not checked against the app's type registry, since there is no app. Conditions are evaluated with
+/// [`Self::flags`].
+///
+/// Example:
+/// ```rust
+/// let mut project = CobProject::new("assets");
+/// project.load("main.cob");
+/// project.resolve();
+///
+/// for diagnostic in project.diagnostics() {
+///     println!("{diagnostic}");
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct CobProject
+{
+    asset_dir: PathBuf,
+    /// Files in the order they were loaded.
+    load_order: Vec<CobFile>,
+    /// Files that were requested, including files that failed to load.
+    requested: HashSet<CobFile>,
+    files: HashMap<CobFile, CobProjectFile>,
+    manifest_map: HashMap<ManifestKey, CobFile>,
+    /// File contents that replace the contents on disk.
+    overrides: HashMap<CobFile, String>,
+    /// Resolvers for files that were resolved, containing each file's definitions and imported definitions.
+    resolvers: HashMap<CobFile, CobResolver>,
+    /// Flags used to evaluate conditions when resolving files.
+    flags: CobFlags,
+    diagnostics: Vec<CobDiagnostic>,
+}
+
+impl CobProject
+{
+    /// Makes a new project that reads files from `asset_dir`.
+    pub fn new(asset_dir: impl Into<PathBuf>) -> Self
+    {
+        Self { asset_dir: asset_dir.into(), ..Default::default() }
+    }
+
+    /// Sets the content of a file, which will be used instead of reading the file from disk.
+    ///
+    /// Useful for files with unsaved changes (e.g. in an editor). Must be called before the file is loaded.
+    pub fn override_file(&mut self, file: CobFile, content: impl Into<String>)
+    {
+        self.overrides.insert(file, content.into());
+    }
+
+    /// Gets the flags used to evaluate conditions (e.g. `#[if(wasm)]`) when resolving files.
+    ///
+    /// Defaults to the flags for the current platform.
+    pub fn flags(&self) -> &CobFlags
+    {
+        &self.flags
+    }
+
+    /// Gets the flags used to evaluate conditions (e.g. `#[if(wasm)]`) when resolving files.
+    ///
+    /// Flags should be set before [`Self::resolve`] is called.
+    pub fn flags_mut(&mut self) -> &mut CobFlags
+    {
+        &mut self.flags
+    }
+
+    /// Loads a file from the asset directory, then loads all files in its manifest tree.
+    ///
+    /// The file path should be relative to the asset directory (e.g. `ui/home.cob`).
+    pub fn load(&mut self, file: impl AsRef<str>)
+    {
+        let file = file.as_ref();
+        let Some(file) = CobFile::try_new(file) else {
+            self.diagnostics
+                .push(CobDiagnostic::error(file, "COB file name doesn't end with '.cob'"));
+            return;
+        };
+
+        let mut queue = VecDeque::from([file]);
+        while let Some(file) = queue.pop_front() {
+            if !self.requested.insert(file.clone()) {
+                continue;
+            }
+
+            let content = match self.overrides.get(&file) {
+                Some(content) => content.clone(),
+                None => {
+                    let path = self.asset_dir.join(file.as_str());
+                    match std::fs::read_to_string(&path) {
+                        Ok(content) => content,
+                        Err(err) => {
+                            self.diagnostics.push(CobDiagnostic::error(
+                                file.as_str(),
+                                format!("failed reading {}: {err}", path.display()),
+                            ));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            queue.extend(self.add_file(file, &content));
+        }
+    }
+
+    /// Parses a file and extracts its manifest and imports.
+    ///
+    /// Returns files in the file's manifest.
+    fn add_file(&mut self, file: CobFile, content: &str) -> Vec<CobFile>
+    {
+        let _ = take_cob_diagnostics();
+        let span = Span::new_extra(content, CobLocationMetadata { file: file.as_str() });
+        let data = match Cob::parse_with_recovery(span) {
+            Ok((data, errors)) => {
+                self.diagnostics.extend(errors);
+                data
+            }
+            Err(err) => {
+                self.diagnostics
+                    .push(CobDiagnostic::from_span_error(file.as_str(), err));
+                return vec![];
+            }
+        };
+        let _ = take_cob_diagnostics();
+
+        // Extract manifest and import sections.
+        let mut manifest = vec![];
+        let mut imports = HashMap::default();
+
+        for section in data.sections.iter() {
+            match section {
+                CobSection::Manifest(section) => extract_manifest_section(&file, section, &mut manifest),
+                CobSection::Import(section) => extract_import_section(section, &mut imports),
+                _ => (),
+            }
+        }
+        self.diagnostics.extend(take_cob_diagnostics());
+
+        // Register manifest keys.
+        // - Lazy files are loaded like other files so the whole project is checked.
+        let mut manifest_files = Vec::with_capacity(manifest.len());
+        for (manifest_file, manifest_key, _) in manifest {
+            if let Some(prev_file) = self
+                .manifest_map
+                .insert(manifest_key.clone(), manifest_file.clone())
+            {
+                if prev_file != manifest_file {
+                    self.diagnostics.push(CobDiagnostic::warning(
+                        file.as_str(),
+                        format!("replacing file for manifest key {:?} (old: {:?}, new: {:?})",
+                            manifest_key.as_str(), prev_file.as_str(), manifest_file.as_str()),
+                    ));
+                }
+            }
+            manifest_files.push(manifest_file);
+        }
+
+        self.load_order.push(file.clone());
+        self.files
+            .insert(file, CobProjectFile { data, imports, resolved: None });
+
+        manifest_files
+    }
+
+    /// Resolves all loaded files.
+    ///
+    /// Files are resolved after the files they import. Problems are reported in [`Self::diagnostics`].
+    pub fn resolve(&mut self)
+    {
+        let mut resolvers: HashMap<CobFile, CobResolver> = HashMap::default();
+        let mut pending: Vec<CobFile> = self.load_order.clone();
+
+        // Loop until no more files can be resolved.
+        loop {
+            let num_pending = pending.len();
+
+            pending.retain(|file| {
+                let project_file = self.files.get_mut(file).unwrap();
+
+                // Check if any dependency is not ready.
+                if project_file
+                    .imports
+                    .keys()
+                    .any(|i| match self.manifest_map.get(i) {
+                        Some(i) => !resolvers.contains_key(i),
+                        None => true,
+                    })
+                {
+                    return true;
+                }
+
+                // Initialize resolver from dependencies.
+                let mut resolver = CobResolver::default();
+                for (key, import) in project_file.imports.iter() {
+                    let dependency = self.manifest_map.get(key).unwrap();
+                    resolver.append(file, key, import, resolvers.get(dependency).unwrap());
+                }
+
+                // Resolve the file.
+                let mut data = project_file.data.clone();
+                apply_cob_conditions(file, &mut data, &self.flags);
+                extract_cob_importables(file.clone(), &mut data, &mut resolver);
+                self.diagnostics.extend(take_cob_diagnostics());
+                resolve_cob_file(file, &mut data, &mut resolver, &mut self.diagnostics);
+
+                project_file.resolved = Some(data);
+                resolvers.insert(file.clone(), resolver);
+
+                false
+            });
+
+            if pending.len() == num_pending {
+                break;
+            }
+        }
+
+        // Report files that couldn't be resolved.
+        for file in pending {
+            let project_file = self.files.get(&file).unwrap();
+            for import in project_file.imports.keys() {
+                let message = match self.manifest_map.get(import) {
+                    None => {
+                        format!("failed resolving import {:?}; no file has this manifest key", import.as_str())
+                    }
+                    Some(import_file) if !self.files.contains_key(import_file) => {
+                        format!("failed resolving import {:?}; file {:?} failed to load",
+                            import.as_str(), import_file.as_str())
+                    }
+                    Some(import_file) if !resolvers.contains_key(import_file) => {
+                        format!("failed resolving import {:?}; file {:?} has a dependency cycle or failed to resolve \
+                            its own imports", import.as_str(), import_file.as_str())
+                    }
+                    Some(_) => continue,
+                };
+                self.diagnostics
+                    .push(CobDiagnostic::error(file.as_str(), message));
+            }
+        }
+
+        self.resolvers = resolvers;
+    }
+
+    /// Gets diagnostics for problems found while loading and resolving files.
+    pub fn diagnostics(&self) -> &[CobDiagnostic]
+    {
+        &self.diagnostics
+    }
+
+    /// Returns `true` if any error diagnostics have been produced.
+    pub fn has_errors(&self) -> bool
+    {
+        self.diagnostics.iter().any(|d| d.is_error())
+    }
+
+    /// Iterates loaded files in the order they were loaded.
+    pub fn files(&self) -> impl Iterator<Item = (&CobFile, &CobProjectFile)> + '_
+    {
+        self.load_order
+            .iter()
+            .filter_map(|file| self.files.get_key_value(file))
+    }
+
+    /// Gets a loaded file.
+    pub fn get(&self, file: &CobFile) -> Option<&CobProjectFile>
+    {
+        self.files.get(file)
+    }
+
+    /// Gets the resolver for a file that was resolved.
+    ///
+    /// The resolver contains the file's definitions and all definitions it imports (e.g. use
+    /// `resolver.loadables.constants.get("alias::name")` to look up a constant).
+    pub fn resolver(&self, file: &CobFile) -> Option<&CobResolver>
+    {
+        self.resolvers.get(file)
+    }
+
+    /// Gets the file registered for a manifest key.
+    pub fn get_manifest_file(&self, key: &ManifestKey) -> Option<&CobFile>
+    {
+        self.manifest_map.get(key)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves assets, commands, and scenes in a file whose definitions have been extracted.
+fn resolve_cob_file(
+    file: &CobFile,
+    data: &mut Cob,
+    resolver: &mut CobResolver,
+    diagnostics: &mut Vec<CobDiagnostic>,
+)
+{
+    let mut id_scratch = String::default();
+
+    for section in data.sections.iter_mut() {
+        match section {
+            CobSection::Assets(section) => {
+                for entry in section.entries.iter_mut() {
+                    let Err(err) = entry.resolve(&resolver.loadables) else { continue };
+                    diagnostics.push(CobDiagnostic::error(
+                        file.as_str(),
+                        format!("failed resolving assets: {err}"),
+                    ));
+                }
+            }
+            CobSection::Commands(section) => {
+                for entry in section.entries.iter_mut() {
+                    let CobCommandEntry::Loadable(loadable) = entry else { continue };
+                    let Err(err) = loadable.resolve(&resolver.loadables) else { continue };
+                    id_scratch = loadable.id.to_canonical(Some(id_scratch));
+                    diagnostics.push(
+                        CobDiagnostic::error(
+                            file.as_str(),
+                            format!("failed resolving command {id_scratch}: {err}"),
+                        )
+                        .at(&loadable.id.location),
+                    );
+                }
+            }
+            CobSection::Scenes(section) => {
+                for layer in section.scenes.iter_mut() {
+                    let Err(err) = layer.resolve(resolver, SceneResolveMode::Full) else { continue };
+                    diagnostics.push(
+                        CobDiagnostic::error(
+                            file.as_str(),
+                            format!("failed resolving scene {:?}: {err}", layer.name.as_str()),
+                        )
+                        .at(&layer.location),
+                    );
+                }
+            }
+            CobSection::Patches(section) => {
+                for patch in section.patches.iter_mut() {
+                    let Err(err) = CobSceneLayer::resolve_entries_impl(
+                        patch.path.as_str(),
+                        &mut patch.entries,
+                        resolver,
+                        SceneResolveMode::Full,
+                    ) else {
+                        continue;
+                    };
+                    diagnostics.push(CobDiagnostic::error(
+                        file.as_str(),
+                        format!("failed resolving patch {}::{}: {err}", patch.file.as_str(), patch.path.as_str()),
+                    ));
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
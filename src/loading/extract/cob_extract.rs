@@ -24,7 +24,7 @@ pub(crate) fn preprocess_cob_file(
 
     // Extract manifest and import sections.
     let mut manifest = vec![];
-    let mut imports: HashMap<ManifestKey, CobImportAlias> = HashMap::default();
+    let mut imports: HashMap<ManifestKey, CobImportInfo> = HashMap::default();
 
     for section in data.sections.iter() {
         match section {
@@ -36,7 +36,23 @@ pub(crate) fn preprocess_cob_file(
 
     // Register manifest keys.
     let mut descendants = vec![];
-    for (other_file, manifest_key) in manifest {
+    for (other_file, manifest_key, lazy) in manifest {
+        let is_new = cob_cache.register_manifest_key(other_file.clone(), Some(manifest_key));
+
+        // Lazy files are loaded on demand, so they aren't descendants of this file.
+        if lazy {
+            if is_new {
+                cob_cache.mark_lazy_file(other_file);
+            }
+            continue;
+        }
+
+        // Files marked lazy elsewhere are requested instead.
+        if cob_cache.is_lazy_file(&other_file) {
+            cob_cache.request_lazy_file(&other_file);
+            continue;
+        }
+
         // Cache file for commands buffer.
         // - We skip any self reference in the manifest.
         if other_file != data.file {
@@ -44,7 +60,7 @@ pub(crate) fn preprocess_cob_file(
         }
 
         // Continue if this file has been registered before.
-        if !cob_cache.register_manifest_key(other_file.clone(), Some(manifest_key)) {
+        if !is_new {
             continue;
         }
 
@@ -67,7 +83,7 @@ pub(crate) fn preprocess_cob_file(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Extracts importable values (defs sections).
+/// Extracts importable values (defs and export sections).
 ///
 /// This is semi-destructive, because definitions will be removed and inserted to appropriate maps/buffers.
 pub(crate) fn extract_cob_importables(file: CobFile, data: &mut Cob, resolver: &mut CobResolver)
@@ -84,6 +100,14 @@ pub(crate) fn extract_cob_importables(file: CobFile, data: &mut Cob, resolver: &
     }
 
     resolver.end_new_file();
+
+    // Exports are extracted last so they can be checked against the file's definitions.
+    for section in data.sections.iter() {
+        match section {
+            CobSection::Export(section) => extract_export_section(&file, section, resolver),
+            _ => (),
+        }
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -99,12 +123,37 @@ pub(crate) fn extract_cob_commands(
 )
 {
     let mut commands = vec![];
+    let mut seen_shortnames = vec![];
+
+    // Assets are requested before other commands are applied.
+    let assets = data
+        .sections
+        .iter_mut()
+        .filter_map(|section| match section {
+            CobSection::Assets(section) => Some(section),
+            _ => None,
+        });
+    extract_assets_sections(
+        type_registry,
+        &mut commands,
+        &mut seen_shortnames,
+        &file,
+        assets,
+        loadables,
+        resolver,
+    );
 
     for section in data.sections.iter_mut() {
         match section {
-            CobSection::Commands(section) => {
-                extract_commands_section(type_registry, &mut commands, &file, section, loadables, resolver)
-            }
+            CobSection::Commands(section) => extract_commands_section(
+                type_registry,
+                &mut commands,
+                &mut seen_shortnames,
+                &file,
+                section,
+                loadables,
+                resolver,
+            ),
             _ => (),
         }
     }
@@ -144,3 +193,45 @@ pub(crate) fn extract_cob_scenes(
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Extracts scene patches from a `Cob`.
+///
+/// Returns files with scenes targeted by patches that were added, changed, or removed.
+pub(crate) fn extract_cob_patches(
+    scene_buffer: &mut SceneBuffer,
+    file: &CobFile,
+    order: Vec<usize>,
+    data: &Cob,
+    resolver: &mut CobResolver,
+) -> Vec<CobFile>
+{
+    let mut patches = vec![];
+
+    for section in data.sections.iter() {
+        let CobSection::Patches(section) = section else { continue };
+
+        for patch in section.patches.iter() {
+            // Patch items are resolved with this file's defs, since the target file may not import them.
+            let mut entries = patch.entries.clone();
+            if let Err(err) = CobSceneLayer::resolve_entries_impl(
+                patch.path.as_str(),
+                &mut entries,
+                resolver,
+                SceneResolveMode::Full,
+            ) {
+                emit_cob_diagnostic(CobDiagnostic::error(
+                    file.as_str(),
+                    format!("failed extracting patch {}::{}; error resolving defs: {err}",
+                        patch.file.as_str(), patch.path.as_str()),
+                ));
+                continue;
+            }
+
+            patches.push(ScenePatch { target: patch.scene_ref(), entries });
+        }
+    }
+
+    scene_buffer.set_file_patches(file, order, patches)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
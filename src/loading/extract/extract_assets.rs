@@ -0,0 +1,76 @@
+use bevy::reflect::TypeRegistry;
+use smol_str::SmolStr;
+
+use super::*;
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extracts the assets listed in a file's `#assets` sections.
+///
+/// Assets are collected into the commands that load them (e.g. `LoadImages`), which are added to `commands`.
+pub(super) fn extract_assets_sections<'a>(
+    type_registry: &TypeRegistry,
+    commands: &mut Vec<(&'static str, ErasedLoadable)>,
+    seen_shortnames: &mut Vec<&'static str>,
+    file: &CobFile,
+    sections: impl Iterator<Item = &'a mut CobAssets>,
+    loadables: &LoadableRegistry,
+    resolver: &CobResolver,
+)
+{
+    // Sort assets by the command that loads them.
+    let mut assets: [Vec<CobValue>; ASSET_COMMANDS.len()] = Default::default();
+
+    for section in sections {
+        for entry in section.entries.iter_mut() {
+            let resolved = match entry.resolve(&resolver.loadables) {
+                Ok(resolved) => resolved,
+                Err(err) => {
+                    emit_cob_diagnostic(CobDiagnostic::error(
+                        file.as_str(),
+                        format!("failed extracting assets; {err}"),
+                    ));
+                    continue;
+                }
+            };
+
+            for (command, value) in resolved {
+                let Some(idx) = ASSET_COMMANDS.iter().position(|c| *c == command) else { continue };
+                assets[idx].push(value);
+            }
+        }
+    }
+
+    // Make commands in order.
+    for (command, entries) in ASSET_COMMANDS.into_iter().zip(assets) {
+        if entries.is_empty() {
+            continue;
+        }
+
+        let mut loadable = CobLoadable {
+            fill: CobFill::default(),
+            id: CobLoadableIdentifier {
+                name: SmolStr::new_static(command),
+                generics: None,
+                location: CobItemLocation::default(),
+            },
+            variant: CobLoadableVariant::Array(CobArray {
+                start_fill: CobFill::default(),
+                entries,
+                end_fill: CobFill::default(),
+            }),
+        };
+        extract_command(
+            type_registry,
+            commands,
+            seen_shortnames,
+            file,
+            &mut loadable,
+            loadables,
+            resolver,
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
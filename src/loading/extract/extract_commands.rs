@@ -5,56 +5,100 @@ use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-pub(super) fn extract_commands_section(
+/// Extracts a single command loadable.
+///
+/// `seen_shortnames` is shared by all commands in a file, since a file can only contain one command of each type.
+pub(super) fn extract_command(
     type_registry: &TypeRegistry,
     commands: &mut Vec<(&'static str, ErasedLoadable)>,
+    seen_shortnames: &mut Vec<&'static str>,
     file: &CobFile,
-    section: &mut CobCommands,
+    loadable: &mut CobLoadable,
     loadables: &LoadableRegistry,
     resolver: &CobResolver,
 )
 {
-    if section.entries.is_empty() {
+    let mock_path = ScenePath::new("#commands");
+
+    // Get the shortname.
+    let shortname = loadable.id.to_canonical(None);
+
+    // Get the loadable's longname.
+    let Some((short_name, long_name, type_id, deserializer)) = get_loadable_meta(
+        type_registry,
+        file,
+        &mock_path,
+        shortname.as_str(),
+        &loadable.id.location,
+        loadables,
+    ) else {
+        return;
+    };
+
+    // Check for duplicate.
+    if seen_shortnames.iter().any(|other| *other == short_name) {
+        emit_cob_diagnostic(
+            CobDiagnostic::warning(
+                file.as_str(),
+                format!("ignoring duplicate command {short_name}; use Multi<{short_name}> instead"),
+            )
+            .at(&loadable.id.location),
+        );
         return;
     }
 
-    let mock_path = ScenePath::new("#commands");
-    let mut shortname = String::default();
-    let mut seen_shortnames = vec![];
+    seen_shortnames.push(short_name);
 
-    for entry in section.entries.iter_mut() {
-        let CobCommandEntry(loadable) = entry;
-        // Get the shortname.
-        shortname = loadable.id.to_canonical(Some(shortname));
-
-        // Get the loadable's longname.
-        let Some((short_name, long_name, type_id, deserializer)) =
-            get_loadable_meta(type_registry, file, &mock_path, shortname.as_str(), loadables)
-        else {
-            continue;
-        };
+    // Resolve defs.
+    if let Err(err) = loadable.resolve(&resolver.loadables) {
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting command {short_name}; error resolving defs: {err}"),
+            )
+            .at(&loadable.id.location),
+        );
+        return;
+    }
 
-        // Check for duplicate.
-        if seen_shortnames.iter().any(|other| *other == short_name) {
-            tracing::warn!("ignoring duplicate command {} in {:?}; use Multi<{}> instead",
-                short_name, file, short_name);
-            continue;
-        }
+    // Get the commands's value.
+    let command_value = get_loadable_value(deserializer, loadable);
 
-        seen_shortnames.push(short_name);
+    // Save the command.
+    commands.push((long_name, ErasedLoadable { type_id, loadable: command_value }));
+}
 
-        // Resolve defs.
-        if let Err(err) = loadable.resolve(&resolver.loadables) {
-            tracing::warn!("failed extracting command {:?} in {:?}; error resolving defs: {:?}",
-                short_name, file, err.as_str());
-            continue;
-        }
+//-------------------------------------------------------------------------------------------------------------------
 
-        // Get the commands's value.
-        let command_value = get_loadable_value(deserializer, loadable);
+pub(super) fn extract_commands_section(
+    type_registry: &TypeRegistry,
+    commands: &mut Vec<(&'static str, ErasedLoadable)>,
+    seen_shortnames: &mut Vec<&'static str>,
+    file: &CobFile,
+    section: &mut CobCommands,
+    loadables: &LoadableRegistry,
+    resolver: &CobResolver,
+)
+{
+    if section.entries.is_empty() {
+        return;
+    }
 
-        // Save the command.
-        commands.push((long_name, ErasedLoadable { type_id, loadable: command_value }));
+    for entry in section.entries.iter_mut() {
+        let CobCommandEntry::Loadable(loadable) = entry else {
+            tracing::error!("ignoring unexpectedly unapplied condition in #commands section of {:?} (this is a bug)",
+                file);
+            continue;
+        };
+        extract_command(
+            type_registry,
+            commands,
+            seen_shortnames,
+            file,
+            loadable,
+            loadables,
+            resolver,
+        );
     }
 }
 
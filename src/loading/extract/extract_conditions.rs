@@ -0,0 +1,131 @@
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn evaluate_condition(file: &CobFile, condition: &CobCondition, flags: &CobFlags) -> bool
+{
+    match condition.evaluate(flags) {
+        Ok(result) => result,
+        Err(err) => {
+            emit_cob_diagnostic(CobDiagnostic::warning(
+                file.as_str(),
+                format!("excluding item after condition; {err}"),
+            ));
+            false
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Removes conditions from `entries`, along with items whose conditions evaluate to false.
+fn filter_entries<T>(
+    file: &CobFile,
+    flags: &CobFlags,
+    entries: &mut Vec<T>,
+    as_condition: impl Fn(&T) -> Option<&CobCondition>,
+)
+{
+    let mut pending = false;
+    let mut include = true;
+
+    entries.retain(|entry| {
+        if let Some(condition) = (as_condition)(entry) {
+            // Evaluate stacked conditions even if the item is already excluded, so bad flags are reported.
+            include &= evaluate_condition(file, condition, flags);
+            pending = true;
+            return false;
+        }
+
+        let keep = include;
+        pending = false;
+        include = true;
+        keep
+    });
+
+    if pending {
+        emit_cob_diagnostic(CobDiagnostic::warning(
+            file.as_str(),
+            "ignoring condition that isn't followed by an item",
+        ));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_scene_conditions(file: &CobFile, flags: &CobFlags, entries: &mut Vec<CobSceneLayerEntry>)
+{
+    filter_entries(file, flags, entries, |entry| match entry {
+        CobSceneLayerEntry::Condition(condition) => Some(condition),
+        _ => None,
+    });
+
+    for entry in entries.iter_mut() {
+        match entry {
+            CobSceneLayerEntry::Layer(layer) => apply_scene_conditions(file, flags, &mut layer.entries),
+            CobSceneLayerEntry::LoadableMacroCall(call) => {
+                apply_scene_conditions(file, flags, &mut call.container.entries)
+            }
+            CobSceneLayerEntry::SceneMacroCall(call) => {
+                apply_scene_conditions(file, flags, &mut call.container.entries)
+            }
+            CobSceneLayerEntry::For(for_entry) => apply_scene_conditions(file, flags, &mut for_entry.entries),
+            _ => (),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Evaluates conditions (e.g. `#[if(wasm)]`) in a `Cob`.
+///
+/// Conditions are removed, along with sections and items whose conditions evaluate to false.
+pub(crate) fn apply_cob_conditions(file: &CobFile, data: &mut Cob, flags: &CobFlags)
+{
+    filter_entries(file, flags, &mut data.sections, |section| match section {
+        CobSection::Condition(condition) => Some(condition),
+        _ => None,
+    });
+
+    for section in data.sections.iter_mut() {
+        match section {
+            CobSection::Defs(section) => {
+                filter_entries(file, flags, &mut section.entries, |entry| match entry {
+                    CobDefEntry::Condition(condition) => Some(condition),
+                    _ => None,
+                });
+
+                for entry in section.entries.iter_mut() {
+                    match entry {
+                        CobDefEntry::LoadableMacro(def) => {
+                            apply_scene_conditions(file, flags, &mut def.value.entries)
+                        }
+                        CobDefEntry::SceneMacro(def) => {
+                            apply_scene_conditions(file, flags, &mut def.value.entries)
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            CobSection::Commands(section) => {
+                filter_entries(file, flags, &mut section.entries, |entry| match entry {
+                    CobCommandEntry::Condition(condition) => Some(condition),
+                    _ => None,
+                });
+            }
+            CobSection::Scenes(section) => {
+                for layer in section.scenes.iter_mut() {
+                    apply_scene_conditions(file, flags, &mut layer.entries);
+                }
+            }
+            CobSection::Patches(section) => {
+                for patch in section.patches.iter_mut() {
+                    apply_scene_conditions(file, flags, &mut patch.entries);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
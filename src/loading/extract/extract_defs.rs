@@ -1,3 +1,5 @@
+use smol_str::SmolStr;
+
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -6,8 +8,14 @@ fn extract_constant_entry(file: &CobFile, mut entry: CobConstantDef, resolver: &
 {
     // Resolve the def's internal value.
     if let Err(err) = entry.value.resolve(resolver) {
-        tracing::warn!("failed extracting constant definition {:?} in {:?}; error resolving internal defs: {:?}",
-            entry.name.as_str(), file, err.as_str());
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting constant definition {:?}; error resolving internal defs: {err}",
+                    entry.name.as_str()),
+            )
+            .at(&entry.name.location),
+        );
         return;
     }
 
@@ -19,12 +27,93 @@ fn extract_constant_entry(file: &CobFile, mut entry: CobConstantDef, resolver: &
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn extract_scene_macro_entry(file: &CobFile, mut entry: CobSceneMacroDef, resolver: &mut CobResolver)
+fn extract_data_macro_entry(file: &CobFile, mut entry: CobDataMacroDef, resolver: &mut CobLoadableResolver)
+{
+    // Resolve the def's internal value.
+    // - Parameters are left in place so they can be substituted when the macro is expanded.
+    resolver.constants.set_params(
+        entry
+            .params
+            .iter()
+            .map(|param| (SmolStr::from(param), macro_param_placeholder(param))),
+    );
+    let result = entry.value.resolve(resolver).and_then(|group| match group {
+        Some(_) => Err(String::from("data macro values cannot be value groups")),
+        None => Ok(()),
+    });
+    resolver.constants.clear_params();
+
+    if let Err(err) = result {
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting data macro definition {:?}; error resolving internal defs: {err}",
+                    entry.name.as_str()),
+            )
+            .at(&entry.name.location),
+        );
+        return;
+    }
+
+    // Save the data macro definition in the data macros buffer.
+    resolver
+        .data_macros
+        .insert(file, entry.name.name, entry.params, entry.value);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn extract_loadable_macro_entry(file: &CobFile, mut entry: CobLoadableMacroDef, resolver: &mut CobResolver)
 {
     // Full-resolve the definition content.
     if let Err(err) = entry.value.resolve(resolver, SceneResolveMode::Full) {
-        tracing::warn!("failed extracting scene macro definition {:?} in {:?}; error resolving internal defs: {:?}",
-            entry.name.as_str(), file, err.as_str());
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting loadable macro definition {:?}; error resolving internal defs: {err}",
+                    entry.name.as_str()),
+            )
+            .at(&entry.name.location),
+        );
+        return;
+    }
+
+    // Save the loadable macro definition in the loadable macro buffer.
+    resolver
+        .scenes
+        .loadable_macros
+        .insert(file, entry.name.name, entry.value);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn extract_scene_macro_entry(file: &CobFile, mut entry: CobSceneMacroDef, resolver: &mut CobResolver)
+{
+    let mut params = entry.params.take().unwrap_or_default();
+
+    // Full-resolve the definition content.
+    // - Parameter defaults are resolved first, since they can't reference parameters.
+    // - Parameters are left in place so they can be substituted when the macro is expanded.
+    let result = params.resolve(&resolver.loadables).and_then(|_| {
+        resolver.loadables.constants.set_params(
+            params
+                .iter()
+                .map(|param| (SmolStr::from(param), macro_param_placeholder(param))),
+        );
+        let result = entry.value.resolve(resolver, SceneResolveMode::Full);
+        resolver.loadables.constants.clear_params();
+        result
+    });
+
+    if let Err(err) = result {
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting scene macro definition {:?}; error resolving internal defs: {err}",
+                    entry.name.as_str()),
+            )
+            .at(&entry.name.location),
+        );
         return;
     }
 
@@ -32,7 +121,7 @@ fn extract_scene_macro_entry(file: &CobFile, mut entry: CobSceneMacroDef, resolv
     resolver
         .scenes
         .scene_macros
-        .insert(file, entry.name.name, entry.value);
+        .insert(file, entry.name.name, params, entry.value);
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -43,7 +132,13 @@ pub(super) fn extract_defs_section(file: &CobFile, section: &mut CobDefs, resolv
     for entry in section.entries.drain(..) {
         match entry {
             CobDefEntry::Constant(entry) => extract_constant_entry(file, entry, &mut resolver.loadables),
+            CobDefEntry::DataMacro(entry) => extract_data_macro_entry(file, entry, &mut resolver.loadables),
+            CobDefEntry::LoadableMacro(entry) => extract_loadable_macro_entry(file, entry, resolver),
             CobDefEntry::SceneMacro(entry) => extract_scene_macro_entry(file, entry, resolver),
+            CobDefEntry::Condition(_) => {
+                tracing::error!("ignoring unexpectedly unapplied condition in #defs section of {:?} (this is a bug)",
+                    file);
+            }
         }
     }
 }
@@ -1,14 +1,41 @@
 use std::collections::HashMap;
 
+use smol_str::SmolStr;
+
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-pub(super) fn extract_import_section(section: &CobImport, imports: &mut HashMap<ManifestKey, CobImportAlias>)
+pub(crate) fn extract_import_section(section: &CobImport, imports: &mut HashMap<ManifestKey, CobImportInfo>)
+{
+    for entry in section.entries.iter() {
+        let import = CobImportInfo {
+            alias: entry.alias.clone(),
+            selection: entry.selection.as_ref().map(|selection| selection.names()),
+        };
+        imports.insert(entry.key.clone(), import);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Adds the names in an `#export` section to the resolver's exports.
+///
+/// Names that don't match any definitions are reported as warnings. The file's own definitions must already be
+/// extracted.
+pub(crate) fn extract_export_section(file: &CobFile, section: &CobExport, resolver: &mut CobResolver)
 {
+    let mut exports: Vec<SmolStr> = resolver.exports.as_deref().unwrap_or_default().to_vec();
+
     for entry in section.entries.iter() {
-        imports.insert(entry.key.clone(), entry.alias.clone());
+        if !resolver.selects_any(entry.path.as_str(), None) {
+            let message = format!("#export {} doesn't match any definitions", entry.path.as_str());
+            emit_cob_diagnostic(CobDiagnostic::warning(file.as_str(), message));
+        }
+        exports.push(entry.path.clone());
     }
+
+    resolver.exports = Some(exports.into());
 }
 
 //-------------------------------------------------------------------------------------------------------------------
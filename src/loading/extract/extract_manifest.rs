@@ -2,10 +2,10 @@ use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-pub(super) fn extract_manifest_section(
+pub(crate) fn extract_manifest_section(
     file: &CobFile,
     section: &CobManifest,
-    manifests: &mut Vec<(CobFile, ManifestKey)>,
+    manifests: &mut Vec<(CobFile, ManifestKey, bool)>,
 )
 {
     for entry in section.entries.iter() {
@@ -16,14 +16,16 @@ pub(super) fn extract_manifest_section(
 
         if manifests
             .iter()
-            .any(|(other_file, _)| entry_file == *other_file)
+            .any(|(other_file, _, _)| entry_file == *other_file)
         {
-            tracing::warn!("ignoring duplicate file {:?} in manifest of {:?}",
-                entry_file, file);
+            emit_cob_diagnostic(CobDiagnostic::warning(
+                file.as_str(),
+                format!("ignoring duplicate file {:?} in manifest", entry_file.as_str()),
+            ));
             continue;
         }
 
-        manifests.push((entry_file, entry.key.clone()));
+        manifests.push((entry_file, entry.key.clone(), entry.is_lazy()));
     }
 }
 
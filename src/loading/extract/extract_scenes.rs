@@ -20,29 +20,57 @@ fn handle_loadable(
 {
     // Get the loadable's longname.
     let id_scratch = loadable.id.to_canonical(Some(id_scratch));
-    let Some((short_name, long_name, type_id, deserializer)) =
-        get_loadable_meta(type_registry, file, current_path, id_scratch.as_str(), loadables)
-    else {
+    let Some((short_name, long_name, type_id, deserializer)) = get_loadable_meta(
+        type_registry,
+        file,
+        current_path,
+        id_scratch.as_str(),
+        &loadable.id.location,
+        loadables,
+    ) else {
         return id_scratch;
     };
 
     // Check for duplicate.
     if seen_shortnames.iter().any(|other| *other == short_name) {
-        tracing::warn!("ignoring duplicate loadable {} at {:?} in {:?}; use Multi<{}> instead",
-            short_name, current_path, file, short_name);
+        emit_cob_diagnostic(
+            CobDiagnostic::warning(
+                file.as_str(),
+                format!("ignoring duplicate loadable {short_name} at {:?}; use Multi<{short_name}> instead",
+                    current_path),
+            )
+            .at(&loadable.id.location),
+        );
         return id_scratch;
     }
 
     // Resolve defs.
     if let Err(err) = loadable.resolve(resolver) {
-        tracing::warn!("failed extracting loadable {:?} at {:?} in {:?}; error resolving defs: {:?}",
-            short_name, current_path, file, err.as_str());
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("failed extracting loadable {short_name} at {:?}; error resolving defs: {err}",
+                    current_path),
+            )
+            .at(&loadable.id.location),
+        );
         return id_scratch;
     }
 
     // Get the loadable's value.
     let loadable_value = get_loadable_value(deserializer, loadable);
 
+    // Check the value. Invalid values are still saved so they can be fixed by hot reloading.
+    if let Err(err) = loadables.validate(type_id, &loadable_value) {
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                file.as_str(),
+                format!("invalid loadable {short_name} at {:?}; {err}", current_path),
+            )
+            .at(&loadable.id.location),
+        );
+    }
+
     // Save this loadable.
     let loadable_index = seen_shortnames.len();
     seen_shortnames.push(short_name);
@@ -90,8 +118,14 @@ fn handle_scene_node(
     };
 
     let Some(node_path) = parent_path.extend_single(layer_name) else {
-        tracing::error!("failed parsing scene node {:?} at {:?} in {:?}, node ID is a multi-segment path, only \
-            single-segment node ids are allowed in scene definitions", layer_name, parent_path, scene.file);
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                scene.file.as_str(),
+                format!("invalid scene node id {layer_name:?} at {:?}; only single-segment node ids are allowed in \
+                    scene definitions", parent_path),
+            )
+            .at(&cob_layer.location),
+        );
         return id_scratch;
     };
 
@@ -153,11 +187,48 @@ fn extract_scene_layer(
 
     // Resolve the scene layer.
     if let Err(err) = cob_layer.resolve(resolver, SceneResolveMode::OneLayerSceneOnly) {
-        tracing::warn!("failed extracting scene layer {:?} at {:?} in {:?}; error resolving defs: {:?}",
-            cob_layer.name.as_str(), current_path, scene.file, err.as_str());
+        emit_cob_diagnostic(
+            CobDiagnostic::error(
+                scene.file.as_str(),
+                format!("failed extracting scene layer {:?} at {:?}; error resolving defs: {err}",
+                    cob_layer.name.as_str(), current_path),
+            )
+            .at(&cob_layer.location),
+        );
         return id_scratch;
     }
 
+    // Overlay patches from other files.
+    id_scratch = scene_buffer.apply_scene_patches(id_scratch, &scene_location, &mut cob_layer.entries);
+
+    // Record the scene spawned inside this node.
+    let mut instance_ref = None;
+    if let Some(cob_ref) = &cob_layer.instance_ref {
+        if cob_layer
+            .entries
+            .iter()
+            .any(|entry| matches!(entry, CobSceneLayerEntry::Layer(_)))
+        {
+            emit_cob_diagnostic(
+                CobDiagnostic::error(
+                    scene.file.as_str(),
+                    format!("scene layer {:?} at {:?} references scene {}::{} so it can't have child scene nodes",
+                        cob_layer.name.as_str(), current_path, cob_ref.file.as_str(), cob_ref.scene.as_str()),
+                )
+                .at(&cob_layer.location),
+            );
+        } else {
+            instance_ref = Some(cob_ref.scene_ref());
+        }
+    }
+    #[cfg(feature = "hot_reload")]
+    {
+        if scene_layer.instance_ref() != instance_ref.as_ref() {
+            scene_loader.handle_changed_scene_instance_ref(c, scene, current_path, instance_ref.clone());
+        }
+    }
+    scene_layer.set_instance_ref(instance_ref);
+
     // Begin layer update.
     scene_layer.start_update(cob_layer.entries.len());
 
@@ -188,10 +259,22 @@ fn extract_scene_layer(
                 tracing::error!("ignoring unexpectedly unresolved scene macro command in scene layer {:?} at {:?} \
                     in {:?} (this is a bug)", cob_layer.name.as_str(), current_path, scene.file);
             }
+            CobSceneLayerEntry::LoadableMacroCall(_) => {
+                tracing::error!("ignoring unexpectedly unresolved loadable macro call in scene layer {:?} at {:?} \
+                    in {:?} (this is a bug)", cob_layer.name.as_str(), current_path, scene.file);
+            }
             CobSceneLayerEntry::SceneMacroCall(_) => {
                 tracing::error!("ignoring unexpectedly unresolved scene macro call in scene layer {:?} at {:?} \
                     in {:?} (this is a bug)", cob_layer.name.as_str(), current_path, scene.file);
             }
+            CobSceneLayerEntry::For(_) => {
+                tracing::error!("ignoring unexpectedly unexpanded #for loop in scene layer {:?} at {:?} \
+                    in {:?} (this is a bug)", cob_layer.name.as_str(), current_path, scene.file);
+            }
+            CobSceneLayerEntry::Condition(_) => {
+                tracing::error!("ignoring unexpectedly unapplied condition in scene layer {:?} at {:?} \
+                    in {:?} (this is a bug)", cob_layer.name.as_str(), current_path, scene.file);
+            }
         }
     }
 
@@ -260,8 +343,14 @@ pub(super) fn extract_scenes(
     for cob_layer in section.scenes.iter_mut() {
         // Get this scene for editing.
         let Some(path) = ScenePath::parse_single(&*cob_layer.name) else {
-            tracing::error!("failed parsing scene {:?} in {:?}, scene root ID is a multi-segment path, only \
-                single-segment node ids are allowed in scene definitions", *cob_layer.name, file);
+            emit_cob_diagnostic(
+                CobDiagnostic::error(
+                    file.as_str(),
+                    format!("invalid scene id {:?}; only single-segment node ids are allowed in scene definitions",
+                        *cob_layer.name),
+                )
+                .at(&cob_layer.location),
+            );
             continue;
         };
         let scene_ref = SceneRef { file: SceneFile::File(file.clone()), path };
@@ -285,6 +374,10 @@ pub(super) fn extract_scenes(
     }
 
     scene_loader.return_scene_registry(scene_registry);
+
+    // Scenes that are spawning incrementally may have been changed.
+    #[cfg(feature = "hot_reload")]
+    scene_loader.restart_spawning_scenes(c, file);
 }
 
 //-------------------------------------------------------------------------------------------------------------------
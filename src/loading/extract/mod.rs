@@ -1,5 +1,7 @@
 mod cob_extract;
+mod extract_assets;
 mod extract_commands;
+mod extract_conditions;
 mod extract_defs;
 mod extract_import;
 mod extract_manifest;
@@ -8,10 +10,12 @@ mod reflected_loadable;
 mod utils;
 
 pub(crate) use cob_extract::*;
+pub(self) use extract_assets::*;
 pub(self) use extract_commands::*;
+pub(crate) use extract_conditions::*;
 pub(self) use extract_defs::*;
-pub(self) use extract_import::*;
-pub(self) use extract_manifest::*;
+pub(crate) use extract_import::*;
+pub(crate) use extract_manifest::*;
 pub(self) use extract_scenes::*;
 pub(crate) use reflected_loadable::*;
 pub(self) use utils::*;
@@ -1,4 +1,5 @@
 use std::any::TypeId;
+use std::fmt::Write;
 use std::sync::Arc;
 
 use bevy::reflect::serde::TypedReflectDeserializer;
@@ -14,6 +15,7 @@ pub(super) fn get_loadable_meta<'a>(
     file: &CobFile,
     current_path: &ScenePath,
     short_name: &str,
+    location: &CobItemLocation,
     loadables: &LoadableRegistry,
 ) -> Option<(&'static str, &'static str, TypeId, TypedReflectDeserializer<'a>)>
 {
@@ -21,9 +23,12 @@ pub(super) fn get_loadable_meta<'a>(
     let registration = match loadables.get_type_id(short_name) {
         Some(type_id) => type_registry.get(type_id),
         None => {
-            tracing::warn!("failed getting type id for loadable {} at {:?} in {:?}; no loadable with this name was \
-                registered in the app",
-                short_name, current_path, file);
+            let mut message = format!("unknown loadable {short_name} at {:?}; no loadable with this name was \
+                registered in the app", current_path);
+            if let Some(suggestion) = closest_match(short_name, loadables.short_names()) {
+                let _ = write!(&mut message, " (did you mean {suggestion}?)");
+            }
+            emit_cob_diagnostic(CobDiagnostic::error(file.as_str(), message).at(location));
             return None;
         }
     };
@@ -1,11 +1,12 @@
 use std::any::TypeId;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy::ecs::system::EntityCommands;
 use bevy::ecs::world::Command;
 use bevy::prelude::*;
 use bevy::reflect::GetTypeRegistration;
 use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
 
 use crate::prelude::*;
 
@@ -22,7 +23,10 @@ fn validate_loadable_name(shortname: &'static str) -> bool
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn register_loadable_type<T: Loadable>(app: &mut App) -> Option<(&mut LoadableRegistry, TypeId)>
+fn register_loadable_type<T: Loadable>(
+    app: &mut App,
+    kind: LoadableKind,
+) -> Option<(&mut LoadableRegistry, TypeId)>
 {
     // Look up canonical short name.
     let type_id = TypeId::of::<T>();
@@ -51,6 +55,11 @@ fn register_loadable_type<T: Loadable>(app: &mut App) -> Option<(&mut LoadableRe
         }
     }
 
+    let kinds = loadables.kinds.entry(type_id).or_default();
+    if !kinds.contains(&kind) {
+        kinds.push(kind);
+    }
+
     Some((loadables.into_inner(), type_id))
 }
 
@@ -59,7 +68,7 @@ fn register_loadable_type<T: Loadable>(app: &mut App) -> Option<(&mut LoadableRe
 fn register_command_loadable<T: Command + Loadable>(app: &mut App)
 {
     // Register type.
-    let Some((loadables, type_id)) = register_loadable_type::<T>(app) else { return };
+    let Some((loadables, type_id)) = register_loadable_type::<T>(app, LoadableKind::Command) else { return };
 
     // Add callback entry.
     let entry = loadables.command_callbacks.entry(type_id);
@@ -76,16 +85,16 @@ fn register_node_loadable<T: Loadable + 'static>(
     app: &mut App,
     callback: fn(&mut World, Entity, ReflectedLoadable, SceneRef),
     _reverter: fn(Entity, &mut World),
-    register_type: &'static str,
+    kind: LoadableKind,
 )
 {
     // Register type.
-    let Some((loadables, type_id)) = register_loadable_type::<T>(app) else { return };
+    let Some((loadables, type_id)) = register_loadable_type::<T>(app, kind) else { return };
 
     // Applier callback.
     let entry = loadables.node_callbacks.entry(type_id);
     if matches!(entry, std::collections::hash_map::Entry::Occupied(_)) {
-        tracing::warn!("tried registering {register_type} loadable {} multiple times", std::any::type_name::<T>());
+        tracing::warn!("tried registering {} loadable {} multiple times", kind.as_str(), std::any::type_name::<T>());
     }
 
     entry.or_insert(callback);
@@ -237,10 +246,19 @@ pub(crate) struct LoadableRegistry
     /// [ short name : type id ]
     loadables: HashMap<&'static str, TypeId>,
 
+    /// [ type id : how the loadable was registered ]
+    kinds: HashMap<TypeId, Vec<LoadableKind>>,
+
     command_callbacks: HashMap<TypeId, fn(&mut World, ReflectedLoadable, SceneRef)>,
     node_callbacks: HashMap<TypeId, fn(&mut World, Entity, ReflectedLoadable, SceneRef)>,
     #[cfg(feature = "hot_reload")]
     revert_callbacks: HashMap<TypeId, fn(Entity, &mut World)>,
+
+    /// [ type id : checks extracted values ]
+    validators: HashMap<TypeId, fn(&dyn PartialReflect, &LoadableRegistry) -> Result<(), String>>,
+
+    /// Names of callbacks registered with [`UiCallbackAppExt::register_ui_callback`].
+    ui_callbacks: HashSet<SmolStr>,
 }
 
 impl LoadableRegistry
@@ -264,10 +282,67 @@ impl LoadableRegistry
         self.revert_callbacks.get(&type_id).cloned()
     }
 
+    pub(crate) fn set_validator(
+        &mut self,
+        type_id: TypeId,
+        validator: fn(&dyn PartialReflect, &LoadableRegistry) -> Result<(), String>,
+    )
+    {
+        self.validators.insert(type_id, validator);
+    }
+
+    /// Checks an extracted loadable value with the validator registered for its type.
+    pub(crate) fn validate(&self, type_id: TypeId, loadable: &ReflectedLoadable) -> Result<(), String>
+    {
+        let (Some(validator), ReflectedLoadable::Value(value)) = (self.validators.get(&type_id), loadable) else {
+            return Ok(());
+        };
+        (validator)(value.as_partial_reflect(), self)
+    }
+
+    pub(crate) fn add_ui_callback(&mut self, name: SmolStr)
+    {
+        self.ui_callbacks.insert(name);
+    }
+
+    pub(crate) fn has_ui_callback(&self, name: &str) -> bool
+    {
+        self.ui_callbacks.contains(name)
+    }
+
+    /// Iterates the names of all registered UI callbacks.
+    pub(crate) fn ui_callback_names(&self) -> impl Iterator<Item = &str> + '_
+    {
+        self.ui_callbacks.iter().map(|name| name.as_str())
+    }
+
     pub(crate) fn get_type_id(&self, id: impl AsRef<str>) -> Option<TypeId>
     {
         self.loadables.get(id.as_ref()).copied()
     }
+
+    /// Iterates the short names of all registered loadables.
+    pub(crate) fn short_names(&self) -> impl Iterator<Item = &'static str> + '_
+    {
+        self.loadables.keys().copied()
+    }
+
+    /// Iterates the short names and type ids of all registered loadables.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'static str, TypeId)> + '_
+    {
+        self.loadables
+            .iter()
+            .map(|(name, type_id)| (*name, *type_id))
+    }
+
+    /// Gets the ways a loadable was registered.
+    pub(crate) fn kinds(&self, type_id: TypeId) -> &[LoadableKind]
+    {
+        self.kinds
+            .get(&type_id)
+            .map(|k| k.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -335,7 +410,7 @@ impl CobLoadableRegistrationAppExt for App
 
     fn register_component<T: Component + Loadable>(&mut self) -> &mut Self
     {
-        register_node_loadable::<T>(self, bundle_loader::<T>, revert_bundle::<T>, "component");
+        register_node_loadable::<T>(self, bundle_loader::<T>, revert_bundle::<T>, LoadableKind::Component);
         self
     }
 
@@ -346,7 +421,7 @@ impl CobLoadableRegistrationAppExt for App
 
     fn register_bundle<T: Bundle + Loadable>(&mut self) -> &mut Self
     {
-        register_node_loadable::<T>(self, bundle_loader::<T>, revert_bundle::<T>, "bundle");
+        register_node_loadable::<T>(self, bundle_loader::<T>, revert_bundle::<T>, LoadableKind::Bundle);
         self
     }
 
@@ -357,7 +432,7 @@ impl CobLoadableRegistrationAppExt for App
 
     fn register_reactive<T: ReactComponent + Loadable>(&mut self) -> &mut Self
     {
-        register_node_loadable::<T>(self, reactive_loader::<T>, revert_reactive::<T>, "reactive");
+        register_node_loadable::<T>(self, reactive_loader::<T>, revert_reactive::<T>, LoadableKind::Reactive);
         self
     }
 
@@ -370,7 +445,7 @@ impl CobLoadableRegistrationAppExt for App
 
     fn register_instruction<T: Instruction + Loadable>(&mut self) -> &mut Self
     {
-        register_node_loadable::<T>(self, instruction_loader::<T>, T::revert, "instruction");
+        register_node_loadable::<T>(self, instruction_loader::<T>, T::revert, LoadableKind::Instruction);
         self
     }
 
@@ -420,6 +495,28 @@ impl CobLoadingEntityCommandsExt for EntityCommands<'_>
     }
 }
 
+impl CobLoadingEntityCommandsExt for EntityWorldMut<'_>
+{
+    fn load(&mut self, scene_ref: SceneRef) -> &mut Self
+    {
+        self.load_with_initializer(scene_ref, |_| {})
+    }
+
+    /// Loadables are applied to the entity before this method returns.
+    fn load_with_initializer(&mut self, scene_ref: SceneRef, initializer: fn(&mut EntityCommands)) -> &mut Self
+    {
+        let id = self.id();
+        self.world_scope(|world| {
+            world
+                .commands()
+                .entity(id)
+                .load_with_initializer(scene_ref, initializer);
+            world.flush();
+        });
+        self
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) struct LoadExtPlugin;
@@ -0,0 +1,459 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::reflect::{NamedField, TypeInfo, TypeRegistry, UnnamedField, VariantInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes a string as a quoted COB string.
+fn cob_string(value: &str) -> String
+{
+    let mut bytes = vec![];
+    if let Ok(value) = CobValue::extract(value) {
+        let _ = value.write_to(&mut DefaultRawSerializer::new(&mut bytes));
+    }
+    String::from_utf8_lossy(&bytes).trim().to_string()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes a reflected value as COB.
+fn write_reflect_default(value: &(dyn PartialReflect + 'static), registry: &TypeRegistry) -> Option<String>
+{
+    let value = CobValue::extract_partial_reflect(value, registry).ok()?;
+    let mut bytes = vec![];
+    value
+        .write_to(&mut DefaultRawSerializer::new(&mut bytes))
+        .ok()?;
+    Some(String::from_utf8_lossy(&bytes).trim().to_string())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn named_field_schema(field: &NamedField, registry: &TypeRegistry) -> FieldSchema
+{
+    FieldSchema::new(field.name(), field.type_path(), field.type_id(), registry)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn unnamed_field_schema(field: &UnnamedField, registry: &TypeRegistry) -> FieldSchema
+{
+    FieldSchema::new(field.index().to_string(), field.type_path(), field.type_id(), registry)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the reflected default value of a type, if it has one.
+fn reflect_default(type_id: std::any::TypeId, registry: &TypeRegistry) -> Option<Box<dyn PartialReflect>>
+{
+    let default = registry.get_type_data::<ReflectDefault>(type_id)?;
+    Some(default.default().into_partial_reflect())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// How a loadable was registered in the app.
+///
+/// See [`CobLoadableRegistrationAppExt`].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadableKind
+{
+    Command,
+    Component,
+    Bundle,
+    Reactive,
+    Instruction,
+}
+
+impl LoadableKind
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            Self::Command => "command",
+            Self::Component => "component",
+            Self::Bundle => "bundle",
+            Self::Reactive => "reactive",
+            Self::Instruction => "instruction",
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A field in a [`LoadableSchema`] or [`VariantSchema`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldSchema
+{
+    /// Field name. Tuple fields are named by index (`0`, `1`, ...).
+    pub name: String,
+    /// Type path of the field.
+    #[serde(rename = "type", default)]
+    pub type_path: String,
+    /// Variant names if the field's type is an enum.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    /// The field's value in the loadable's default value, written as COB.
+    ///
+    /// Only available if the loadable type registers `ReflectDefault`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl FieldSchema
+{
+    fn new(name: impl Into<String>, type_path: &str, type_id: std::any::TypeId, registry: &TypeRegistry) -> Self
+    {
+        let variants = match registry.get_type_info(type_id) {
+            Some(TypeInfo::Enum(info)) => info.variant_names().iter().map(|v| v.to_string()).collect(),
+            _ => vec![],
+        };
+
+        Self {
+            name: name.into(),
+            type_path: String::from(type_path),
+            variants,
+            default: None,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A variant of an enum loadable.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VariantSchema
+{
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Description of a loadable type registered in the app.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LoadableSchema
+{
+    /// Short name used in COB files.
+    pub name: String,
+    /// Full type path.
+    #[serde(default)]
+    pub type_path: String,
+    /// How the loadable was registered. A loadable can be registered in multiple ways.
+    #[serde(default)]
+    pub kinds: Vec<LoadableKind>,
+    /// Fields if the loadable is a struct.
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+    /// Variants if the loadable is an enum.
+    #[serde(default)]
+    pub variants: Vec<VariantSchema>,
+    /// The loadable's default value, written as COB.
+    ///
+    /// Only available if the loadable type registers `ReflectDefault`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl LoadableSchema
+{
+    /// Makes a schema for a reflected type.
+    fn new(
+        name: &str,
+        kinds: Vec<LoadableKind>,
+        type_id: std::any::TypeId,
+        registry: &TypeRegistry,
+    ) -> Option<Self>
+    {
+        let type_info = registry.get_type_info(type_id)?;
+        let mut schema = Self {
+            name: String::from(name),
+            type_path: String::from(type_info.type_path()),
+            kinds,
+            fields: vec![],
+            variants: vec![],
+            default: None,
+        };
+
+        match type_info {
+            TypeInfo::Struct(info) => {
+                schema.fields = info
+                    .iter()
+                    .map(|f| named_field_schema(f, registry))
+                    .collect();
+            }
+            TypeInfo::TupleStruct(info) => {
+                schema.fields = info
+                    .iter()
+                    .map(|f| unnamed_field_schema(f, registry))
+                    .collect();
+            }
+            TypeInfo::Enum(info) => {
+                schema.variants = info
+                    .iter()
+                    .map(|variant| {
+                        let fields = match variant {
+                            VariantInfo::Struct(info) => info
+                                .iter()
+                                .map(|f| named_field_schema(f, registry))
+                                .collect(),
+                            VariantInfo::Tuple(info) => info
+                                .iter()
+                                .map(|f| unnamed_field_schema(f, registry))
+                                .collect(),
+                            VariantInfo::Unit(_) => vec![],
+                        };
+                        VariantSchema { name: String::from(variant.name()), fields }
+                    })
+                    .collect();
+            }
+            _ => (),
+        }
+
+        // Add defaults.
+        if let Some(default) = reflect_default(type_id, registry) {
+            if let Ok(loadable) = CobLoadable::extract_partial_reflect(&*default, registry) {
+                let mut bytes = vec![];
+                if loadable
+                    .write_to(&mut DefaultRawSerializer::new(&mut bytes))
+                    .is_ok()
+                {
+                    schema.default = Some(String::from_utf8_lossy(&bytes).trim().to_string());
+                }
+            }
+
+            match default.reflect_ref() {
+                bevy::reflect::ReflectRef::Struct(value) => {
+                    for field in schema.fields.iter_mut() {
+                        let Some(field_value) = value.field(&field.name) else { continue };
+                        field.default = write_reflect_default(&*field_value.clone_value(), registry);
+                    }
+                }
+                bevy::reflect::ReflectRef::TupleStruct(value) => {
+                    for (idx, field) in schema.fields.iter_mut().enumerate() {
+                        let Some(field_value) = value.field(idx) else { continue };
+                        field.default = write_reflect_default(&*field_value.clone_value(), registry);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Some(schema)
+    }
+
+    fn write_cob_fields(fields: &[FieldSchema], indent: &str, out: &mut String)
+    {
+        for field in fields.iter() {
+            out.push_str(&format!(
+                "{indent}{{name:{} type:{}",
+                cob_string(&field.name),
+                cob_string(&field.type_path)
+            ));
+            if !field.variants.is_empty() {
+                let variants: Vec<_> = field.variants.iter().map(|v| cob_string(v)).collect();
+                out.push_str(&format!(" variants:[{}]", variants.join(" ")));
+            }
+            if let Some(default) = &field.default {
+                out.push_str(&format!(" default:{default}"));
+            }
+            out.push_str("}\n");
+        }
+    }
+
+    /// Writes the schema as a COB map.
+    fn write_cob(&self, out: &mut String)
+    {
+        let kinds: Vec<_> = self.kinds.iter().map(|k| cob_string(k.as_str())).collect();
+        out.push_str("    {\n");
+        out.push_str(&format!("        name:{}\n", cob_string(&self.name)));
+        out.push_str(&format!("        type_path:{}\n", cob_string(&self.type_path)));
+        out.push_str(&format!("        kinds:[{}]\n", kinds.join(" ")));
+        if !self.fields.is_empty() {
+            out.push_str("        fields:[\n");
+            Self::write_cob_fields(&self.fields, "            ", out);
+            out.push_str("        ]\n");
+        }
+        if !self.variants.is_empty() {
+            out.push_str("        variants:[\n");
+            for variant in self.variants.iter() {
+                out.push_str(&format!("            {{name:{}", cob_string(&variant.name)));
+                if variant.fields.is_empty() {
+                    out.push_str("}\n");
+                    continue;
+                }
+                out.push_str(" fields:[\n");
+                Self::write_cob_fields(&variant.fields, "                ", out);
+                out.push_str("            ]}\n");
+            }
+            out.push_str("        ]\n");
+        }
+        if let Some(default) = &self.default {
+            out.push_str(&format!("        default:{default}\n"));
+        }
+        out.push_str("    }\n");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Schema of all loadables registered in an app.
+///
+/// The schema can be written as JSON or COB and used by tools that need to know about an app's loadables without
+/// running the app (e.g. editors, language servers, and CI validation of COB files). Use
+/// [`LoadableSchemaPlugin`] to write the schema from the command line.
+///
+/// The JSON format is:
+/// ```json
+/// {
+///     "loadables": [
+///         {
+///             "name": "BackgroundColor",
+///             "type_path": "bevy_ui::ui_node::BackgroundColor",
+///             "kinds": ["component"],
+///             "fields": [{ "name": "0", "type": "bevy_color::color::Color", "default": "Srgba{...}" }],
+///             "variants": [],
+///             "default": "BackgroundColor(Srgba{...})"
+///         }
+///     ]
+/// }
+/// ```
+///
+/// The COB format contains the same data in a `$loadables` constant.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct LoadableSchemas
+{
+    /// Loadables sorted by name.
+    #[serde(default)]
+    pub loadables: Vec<LoadableSchema>,
+}
+
+impl LoadableSchemas
+{
+    /// Collects schemas for all loadables registered in the world.
+    ///
+    /// Loadables must be registered with [`CobLoadableRegistrationAppExt`].
+    pub fn from_world(world: &World) -> Self
+    {
+        let Some(loadables) = world.get_resource::<LoadableRegistry>() else { return Self::default() };
+        let registry = world.resource::<AppTypeRegistry>().read();
+        Self::new(loadables, &registry)
+    }
+
+    pub(crate) fn new(loadables: &LoadableRegistry, registry: &TypeRegistry) -> Self
+    {
+        let mut schemas: Vec<_> = loadables
+            .iter()
+            .filter_map(|(name, type_id)| {
+                LoadableSchema::new(name, loadables.kinds(type_id).to_vec(), type_id, registry)
+            })
+            .collect();
+        schemas.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { loadables: schemas }
+    }
+
+    /// Gets the schema of a loadable by its short name.
+    pub fn get(&self, name: &str) -> Option<&LoadableSchema>
+    {
+        self.loadables.iter().find(|l| l.name == name)
+    }
+
+    /// Writes the schemas as pretty-printed JSON.
+    pub fn to_json(&self) -> String
+    {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Reads schemas from JSON.
+    pub fn from_json(json: &str) -> Result<Self, String>
+    {
+        serde_json::from_str(json).map_err(|err| format!("failed parsing loadable schemas: {err}"))
+    }
+
+    /// Writes the schemas as a COB file.
+    pub fn to_cob(&self) -> String
+    {
+        let mut out = String::from("// Loadable schemas exported by bevy_cobweb_ui.\n\n#defs\n$loadables = [\n");
+        for schema in self.loadables.iter() {
+            schema.write_cob(&mut out);
+        }
+        out.push_str("]\n");
+        out
+    }
+
+    /// Writes the schemas to a file.
+    ///
+    /// The schemas are written as COB if the file extension is `.cob`, and as JSON otherwise.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), String>
+    {
+        let path = path.as_ref();
+        let content = match path.extension().and_then(|e| e.to_str()) {
+            Some("cob") => self.to_cob(),
+            _ => self.to_json(),
+        };
+        std::fs::write(path, content).map_err(|err| format!("failed writing {}: {err}", path.display()))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Plugin that writes the app's [`LoadableSchemas`] to a file and exits if the app is started with
+/// `--dump-schema <path>`.
+///
+/// The schemas are written on startup after all plugins are built. If the flag is not present, this plugin does
+/// nothing.
+///
+/// Example:
+/// ```ignore
+/// cargo run -- --dump-schema loadables.json
+/// ```
+pub struct LoadableSchemaPlugin;
+
+impl LoadableSchemaPlugin
+{
+    /// Gets the schema path from the command line arguments.
+    fn schema_path() -> Option<String>
+    {
+        let mut args = std::env::args();
+        while let Some(arg) = args.next() {
+            if arg == "--dump-schema" {
+                return args.next();
+            }
+            if let Some(path) = arg.strip_prefix("--dump-schema=") {
+                return Some(String::from(path));
+            }
+        }
+        None
+    }
+}
+
+impl Plugin for LoadableSchemaPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        let Some(path) = Self::schema_path() else { return };
+
+        app.add_systems(Startup, move |world: &mut World| {
+            let schemas = LoadableSchemas::from_world(world);
+            match schemas.write(&path) {
+                Ok(()) => {
+                    tracing::info!("wrote {} loadable schemas to {path}", schemas.loadables.len());
+                    world.send_event(AppExit::Success);
+                }
+                Err(err) => {
+                    tracing::error!("failed dumping loadable schemas: {err}");
+                    world.send_event(AppExit::error());
+                }
+            }
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -7,23 +7,31 @@ mod app_load_ext;
 mod cache;
 pub mod cob;
 mod cob_asset_loader;
+mod cob_diagnostics;
+mod cob_flags;
+mod cob_project;
 mod extract;
 mod load_ext;
 mod load_progress;
 mod loadable;
+mod loadable_schema;
 mod plugin;
 mod references;
 mod scene;
 
 pub use app_load_ext::*;
 pub use cache::*;
-pub use cob::Cob;
+pub use cob::{Cob, CobDiagnostic, CobDiagnosticSeverity, CobSourceLocation};
 pub(crate) use cob::*;
 pub(crate) use cob_asset_loader::*;
+pub(crate) use cob_diagnostics::*;
+pub use cob_flags::*;
+pub use cob_project::*;
 pub(crate) use extract::*;
 pub use load_ext::*;
 pub use load_progress::*;
 pub use loadable::*;
+pub use loadable_schema::*;
 pub(crate) use plugin::*;
 pub use references::*;
 pub use scene::*;
@@ -12,6 +12,8 @@ impl Plugin for LoadingPlugin
     {
         app.add_plugins(LoadProgressPlugin)
             .add_plugins(LoadExtPlugin)
+            .add_plugins(CobDiagnosticsPlugin)
+            .add_plugins(CobFlagsPlugin)
             .add_plugins(CobAssetLoaderPlugin)
             .add_plugins(AppLoadExtPlugin)
             .add_plugins(CobAssetCachePlugin)
@@ -261,6 +261,10 @@ pub trait LoadSceneExt: scene_traits::SceneNodeLoader
     /// The `callback` can be used to edit the scene's root node, which in turn can be used to edit inner nodes
     /// of the scene via [`LoadedScene::edit`].
     ///
+    /// If the scene is in a lazy file that hasn't loaded yet, the file will be requested and the scene will be
+    /// loaded into the spawned entity once the file is ready. The `callback` is not called in that case. See
+    /// [`SceneLoader::request_scene`].
+    ///
     /// Will log a warning and do nothing if the parent entity does not exist.
     fn load_scene_and_edit<C>(
         &mut self,
@@ -270,6 +274,20 @@ pub trait LoadSceneExt: scene_traits::SceneNodeLoader
     ) -> &mut Self
     where
         C: for<'a> FnOnce(&mut LoadedScene<'a, <Self as scene_traits::SceneNodeLoader>::Loaded<'a>>);
+
+    /// Spawns an entity like [`LoadSceneExt::load_scene`], then spawns the scene's nodes into it over multiple
+    /// frames.
+    ///
+    /// Nodes are spawned in [`First`] within the [`SceneSpawnBudget`] set with
+    /// [`SceneLoader::set_spawn_budget`]. The root entity has [`Visibility::Hidden`] until all nodes are spawned,
+    /// then its previous visibility is restored and [`SceneSpawned`] is sent to it. Nodes that haven't spawned
+    /// yet are reported to [`LoadProgress`].
+    ///
+    /// If the scene's file is hot reloaded while the scene is spawning, the nodes spawned so far are despawned and
+    /// spawning restarts from the scene's new contents.
+    ///
+    /// Returns the scene's root entity. Returns [`Entity::PLACEHOLDER`] if the parent entity does not exist.
+    fn load_scene_incremental(&mut self, path: impl Into<SceneRef>, scene_loader: &mut SceneLoader) -> Entity;
 }
 
 impl<T> LoadSceneExt for T
@@ -306,25 +324,151 @@ where
 
         // Load the scene into the root entity.
         let mut commands = self.commands();
-        if !scene_loader.load_scene::<T>(&mut commands, root_entity, path.clone()) {
-            return self;
-        }
+        load_scene_into::<T, C>(&mut commands, root_entity, path, scene_loader, callback);
 
-        // Allow editing the scene via callback.
-        {
-            let mut root_node = LoadedScene {
-                scene_loader,
-                builder: T::loaded_scene_builder(&mut commands, root_entity),
-                scene: path,
-            };
+        self
+    }
+
+    fn load_scene_incremental(&mut self, path: impl Into<SceneRef>, scene_loader: &mut SceneLoader) -> Entity
+    {
+        let path = path.into();
+
+        // Spawn either a child or a raw entity to be the scene's root node.
+        let root_entity = self
+            .scene_parent_entity()
+            .map(|parent| self.commands().spawn_empty().set_parent(parent).id())
+            .unwrap_or_else(|| self.commands().spawn_empty().id());
 
-            (callback)(&mut root_node);
+        // Avoid panicking if the parent is invalid.
+        if self.commands().get_entity(root_entity).is_none() {
+            tracing::warn!("failed loading scene at {:?}; parent {root_entity:?} does not exist", path);
+            return Entity::PLACEHOLDER;
         }
 
-        // Cleanup
-        scene_loader.release_active_scene();
+        let mut commands = self.commands();
+        scene_loader.load_scene_incremental::<T>(&mut commands, root_entity, path);
 
-        self
+        root_entity
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads a scene into an existing root entity, then calls `callback` to edit the scene.
+fn load_scene_into<T, C>(
+    commands: &mut Commands,
+    root_entity: Entity,
+    path: SceneRef,
+    scene_loader: &mut SceneLoader,
+    callback: C,
+) where
+    T: scene_traits::SceneNodeLoader,
+    C: for<'a> FnOnce(&mut LoadedScene<'a, <T as scene_traits::SceneNodeLoader>::Loaded<'a>>),
+{
+    if !scene_loader.load_scene::<T>(commands, root_entity, path.clone()) {
+        return;
+    }
+
+    // Allow editing the scene via callback.
+    {
+        let mut root_node = LoadedScene {
+            scene_loader,
+            builder: T::loaded_scene_builder(commands, root_entity),
+            scene: path,
+        };
+
+        (callback)(&mut root_node);
+    }
+
+    // Cleanup
+    scene_loader.release_active_scene(commands);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns a root entity (as a child of `parent` if specified), loads a scene into it, then applies all commands so
+/// the scene hierarchy is immediately available.
+fn load_scene_in_world<C>(world: &mut World, parent: Option<Entity>, path: SceneRef, callback: C) -> Entity
+where
+    C: for<'a> FnOnce(&mut LoadedScene<'a, EntityCommands<'a>>),
+{
+    let mut root = world.spawn_empty();
+    if let Some(parent) = parent {
+        root.set_parent(parent);
+    }
+    let root_entity = root.id();
+
+    // The scene loader is removed from the world while loading so scene nodes can be spawned with world commands.
+    world.resource_scope(|world, mut scene_loader: Mut<SceneLoader>| {
+        let mut commands = world.commands();
+        load_scene_into::<Commands, C>(&mut commands, root_entity, path, &mut scene_loader, callback);
+    });
+    world.flush();
+
+    root_entity
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extention trait for loading scenes with direct [`World`] access.
+///
+/// Unlike [`LoadSceneExt`], the scene hierarchy is spawned and its loadables are applied before these methods
+/// return, which is useful in exclusive systems and tests. There are two exceptions:
+/// - Scenes in lazy files that haven't loaded yet are spawned once the file is ready. Only the root entity exists
+///   when the method returns.
+/// - With the `hot_reload` feature, loadables are applied later if COB commands are waiting to be applied
+///   (e.g. while a file with `#commands` is being reloaded). The hierarchy is still spawned.
+///
+/// [`DeferredWorld`](bevy::ecs::world::DeferredWorld) is not supported, since scenes can't be spawned without
+/// structural world access. In observers and hooks, queue a command that uses this trait instead.
+pub trait LoadSceneWorldExt
+{
+    /// Equivalent to [`LoadSceneWorldExt::load_scene_and_edit`] with no callback.
+    fn load_scene(&mut self, path: impl Into<SceneRef>) -> Entity;
+
+    /// Spawns an entity (as a child of the current entity for [`EntityWorldMut`]), then loads the scene at `path`
+    /// into it.
+    ///
+    /// The `callback` can be used to edit the scene with [`EntityCommands`]. Commands queued by the callback are
+    /// applied along with the scene's loadables before this method returns.
+    ///
+    /// If the scene is in a lazy file that hasn't loaded yet, the file will be requested and the scene will be
+    /// loaded into the spawned entity once the file is ready. The `callback` is not called in that case.
+    ///
+    /// Returns the scene's root entity.
+    fn load_scene_and_edit<C>(&mut self, path: impl Into<SceneRef>, callback: C) -> Entity
+    where
+        C: for<'a> FnOnce(&mut LoadedScene<'a, EntityCommands<'a>>);
+}
+
+impl LoadSceneWorldExt for World
+{
+    fn load_scene(&mut self, path: impl Into<SceneRef>) -> Entity
+    {
+        self.load_scene_and_edit(path, |_| {})
+    }
+
+    fn load_scene_and_edit<C>(&mut self, path: impl Into<SceneRef>, callback: C) -> Entity
+    where
+        C: for<'a> FnOnce(&mut LoadedScene<'a, EntityCommands<'a>>),
+    {
+        load_scene_in_world(self, None, path.into(), callback)
+    }
+}
+
+impl LoadSceneWorldExt for EntityWorldMut<'_>
+{
+    fn load_scene(&mut self, path: impl Into<SceneRef>) -> Entity
+    {
+        self.load_scene_and_edit(path, |_| {})
+    }
+
+    fn load_scene_and_edit<C>(&mut self, path: impl Into<SceneRef>, callback: C) -> Entity
+    where
+        C: for<'a> FnOnce(&mut LoadedScene<'a, EntityCommands<'a>>),
+    {
+        let parent = self.id();
+        self.world_scope(|world| load_scene_in_world(world, Some(parent), path.into(), callback))
     }
 }
 
@@ -1,5 +1,7 @@
 mod load_scene_ext;
+mod scene_instance;
 mod scene_loader;
 
 pub use load_scene_ext::*;
+pub use scene_instance::*;
 pub use scene_loader::*;
@@ -0,0 +1,195 @@
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use super::scene_loader::{despawn_scene_instance, replace_scene_instance};
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies a scene instance spawned by the [`SceneLoader`].
+///
+/// Ids are stable when an instance is reloaded or swapped with [`ReloadSceneInstance`] and [`SwapScene`], even
+/// though the instance's root entity changes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SceneInstanceId(pub(super) u64);
+
+impl SceneInstanceId
+{
+    pub(super) const PLACEHOLDER: Self = Self(0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component inserted on every node of a scene instance, including the root node.
+///
+/// Use this to find which scene instance an entity is part of. Nodes that contain a scene referenced with
+/// `=> file::scene` belong to the outer scene, and the referenced scene's root entity belongs to its own instance.
+#[derive(Component, Debug, Clone)]
+pub struct SceneNode
+{
+    pub(super) instance: SceneInstanceId,
+    pub(super) scene: SceneRef,
+}
+
+impl SceneNode
+{
+    /// Gets the scene instance the node belongs to.
+    pub fn instance(&self) -> SceneInstanceId
+    {
+        self.instance
+    }
+
+    /// Gets the location of the node in its scene.
+    pub fn scene_ref(&self) -> &SceneRef
+    {
+        &self.scene
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Entity event sent to a scene's root entity after the scene is spawned.
+///
+/// When loading with [`LoadSceneExt::load_scene_and_edit`], the event is sent after commands queued in the
+/// callback.
+///
+/// When loading with [`LoadSceneExt::load_scene_incremental`], the event is sent after all of the scene's nodes
+/// are spawned.
+#[derive(Debug, Clone)]
+pub struct SceneSpawned
+{
+    pub instance: SceneInstanceId,
+    pub scene: SceneRef,
+}
+
+/// Entity event sent to a scene's root entity when hot reloading adds a node to the scene.
+#[derive(Debug, Clone)]
+pub struct SceneNodeAdded
+{
+    pub instance: SceneInstanceId,
+    pub path: ScenePath,
+    pub entity: Entity,
+}
+
+/// Entity event sent to a scene's root entity when hot reloading removes a node from the scene.
+///
+/// The node entity will already be despawned.
+#[derive(Debug, Clone)]
+pub struct SceneNodeRemoved
+{
+    pub instance: SceneInstanceId,
+    pub path: ScenePath,
+    pub entity: Entity,
+}
+
+/// Entity event sent to the new root entity of a scene instance replaced by [`ReloadSceneInstance`] or
+/// [`SwapScene`].
+///
+/// Sent after [`SceneSpawned`].
+#[derive(Debug, Clone)]
+pub struct SceneReloaded
+{
+    pub instance: SceneInstanceId,
+    pub scene: SceneRef,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for despawning a scene instance, including all of its root entity's descendants.
+#[derive(Debug, Clone)]
+pub struct DespawnSceneInstance(pub SceneInstanceId);
+
+impl Command for DespawnSceneInstance
+{
+    fn apply(self, world: &mut World)
+    {
+        world.syscall(self.0, despawn_scene_instance);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for respawning a scene instance from its scene's current contents.
+///
+/// A new root entity is spawned in place of the old root entity (at the same position in its parent's children),
+/// then the old root entity is despawned recursively. Edits made to the old instance are lost. Sends
+/// [`SceneReloaded`] to the new root entity.
+///
+/// If the scene is in a lazy file that hasn't loaded yet, the old root entity is kept until the new instance is
+/// loaded. If the scene fails to load, the old instance is kept.
+#[derive(Debug, Clone)]
+pub struct ReloadSceneInstance(pub SceneInstanceId);
+
+impl Command for ReloadSceneInstance
+{
+    fn apply(self, world: &mut World)
+    {
+        world.syscall((self.0, None), replace_scene_instance);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Command for replacing the scene instance that contains `entity` with a different scene.
+///
+/// `entity` can be any node in the scene instance. The instance keeps its [`SceneInstanceId`]. See
+/// [`ReloadSceneInstance`].
+#[derive(Debug, Clone)]
+pub struct SwapScene
+{
+    pub entity: Entity,
+    pub scene: SceneRef,
+}
+
+impl Command for SwapScene
+{
+    fn apply(self, world: &mut World)
+    {
+        let Some(node) = world.get::<SceneNode>(self.entity) else {
+            tracing::warn!("failed swapping scene of {:?} with {:?}, entity is not part of a scene instance",
+                self.entity, self.scene);
+            return;
+        };
+        let instance = node.instance();
+        world.syscall((instance, Some(self.scene)), replace_scene_instance);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `Commands` with methods for managing scene instances.
+pub trait SceneInstanceCommandsExt
+{
+    /// Queues a [`DespawnSceneInstance`] command.
+    fn despawn_scene_instance(&mut self, instance: SceneInstanceId) -> &mut Self;
+
+    /// Queues a [`ReloadSceneInstance`] command.
+    fn reload_scene_instance(&mut self, instance: SceneInstanceId) -> &mut Self;
+
+    /// Queues a [`SwapScene`] command.
+    fn swap_scene(&mut self, entity: Entity, scene: impl Into<SceneRef>) -> &mut Self;
+}
+
+impl SceneInstanceCommandsExt for Commands<'_, '_>
+{
+    fn despawn_scene_instance(&mut self, instance: SceneInstanceId) -> &mut Self
+    {
+        self.queue(DespawnSceneInstance(instance));
+        self
+    }
+
+    fn reload_scene_instance(&mut self, instance: SceneInstanceId) -> &mut Self
+    {
+        self.queue(ReloadSceneInstance(instance));
+        self
+    }
+
+    fn swap_scene(&mut self, entity: Entity, scene: impl Into<SceneRef>) -> &mut Self
+    {
+        self.queue(SwapScene { entity, scene: scene.into() });
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,13 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
-#[cfg(feature = "hot_reload")]
 use bevy_cobweb::prelude::*;
 #[cfg(feature = "hot_reload")]
 use smallvec::SmallVec;
+use wasm_timer::Instant;
 
+use super::scene_instance::SceneInstanceId;
 use crate::prelude::*;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -42,6 +44,304 @@ fn find_scene_child_pos(world: &World, parent_entity: Entity, new_index: usize)
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Loads a referenced scene into a scene node after the node's `=> file::scene` reference was hot reloaded.
+#[cfg(feature = "hot_reload")]
+fn load_scene_instance_queued(
+    In((parent, instance_ref, initializer)): In<(Entity, SceneRef, NodeInitializer)>,
+    mut c: Commands,
+    mut scene_loader: ResMut<SceneLoader>,
+)
+{
+    scene_loader.load_scene_instance(&mut c, parent, instance_ref, initializer.initializer);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Spawns nodes of scenes loaded with [`LoadSceneExt::load_scene_incremental`] until the [`SceneSpawnBudget`] is
+/// used up.
+///
+/// Commands are applied after each node so the time budget includes applying the node's loadables.
+fn spawn_incremental_scenes(world: &mut World)
+{
+    let Some(budget) = world
+        .get_resource::<SceneLoader>()
+        .filter(|scene_loader| !scene_loader.spawning_scenes.is_empty())
+        .map(|scene_loader| scene_loader.spawn_budget)
+    else {
+        return;
+    };
+
+    // At least one node is spawned each frame so scenes always make progress.
+    let start = Instant::now();
+    let mut num_spawned = 0;
+    loop {
+        let spawned = world.resource_scope(|world, mut scene_loader: Mut<SceneLoader>| {
+            let mut c = world.commands();
+            scene_loader.spawn_incremental_node(&mut c)
+        });
+        world.flush();
+
+        num_spawned += 1;
+        if !spawned || num_spawned >= budget.max_nodes || start.elapsed() >= budget.max_time {
+            break;
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Hides the root entity of a scene that is spawning incrementally, saving its previous visibility.
+///
+/// This is queued after the root's loadables so a `Visibility` loadable on the root is restored when the scene is
+/// finished.
+fn hide_spawning_scene_root(world: &mut World, root_entity: Entity)
+{
+    let prev_visibility = world
+        .get::<Visibility>(root_entity)
+        .copied()
+        .unwrap_or_default();
+    let Some(mut scene_loader) = world.get_resource_mut::<SceneLoader>() else { return };
+    let Some(spawning) = scene_loader
+        .spawning_scenes
+        .iter_mut()
+        .find(|s| s.instance.root_entity() == root_entity)
+    else {
+        return;
+    };
+    spawning.prev_visibility = Some(prev_visibility);
+    world.entity_mut(root_entity).insert(Visibility::Hidden);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Removes despawned scene roots from the [`SceneLoader`]'s scene tracking.
+fn cleanup_scene_roots(mut scene_loader: ResMut<SceneLoader>, mut removed: RemovedComponents<CobSceneRoot>)
+{
+    for removed in removed.read() {
+        let Some(id) = scene_loader.scene_roots.remove(&removed) else { continue };
+
+        // Replaced scene instances keep their id, so only remove the instance if the root wasn't replaced.
+        if scene_loader
+            .instances
+            .get(&id)
+            .is_some_and(|i| i.root_entity == removed)
+        {
+            scene_loader.instances.remove(&id);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Replaces a scene instance with a new copy of `scene_ref`, or of its current scene if `scene_ref` is `None`.
+pub(super) fn replace_scene_instance(
+    In((id, scene_ref)): In<(SceneInstanceId, Option<SceneRef>)>,
+    mut c: Commands,
+    mut scene_loader: ResMut<SceneLoader>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+)
+{
+    let Some(info) = scene_loader.instances.get(&id) else {
+        tracing::warn!("failed replacing scene instance {id:?}, the instance doesn't exist");
+        return;
+    };
+    let old_root = info.root_entity;
+    let scene_ref = scene_ref.unwrap_or_else(|| info.scene_ref.clone());
+    let initializer = info.initializer;
+    let is_instance = info.is_instance;
+
+    // Spawn the new root entity in the old root's place.
+    let new_root = c.spawn_empty().id();
+    if let Ok(parent) = parents.get(old_root) {
+        let position = children
+            .get(parent.get())
+            .ok()
+            .and_then(|children| children.iter().position(|e| *e == old_root))
+            .unwrap_or_default();
+        c.entity(parent.get())
+            .insert_children(position, &[new_root]);
+    }
+
+    // Load the scene, then despawn the old instance.
+    // - If the scene's file is lazy and hasn't loaded yet, the old instance is despawned once the replacement is
+    //   loaded by `SceneLoader::spawn_pending_scenes`.
+    match scene_loader.load_scene_impl(
+        &mut c,
+        new_root,
+        scene_ref.clone(),
+        initializer,
+        is_instance,
+        Some(id),
+        false,
+    ) {
+        SceneLoadResult::Loaded => scene_loader.release_active_scene(&mut c),
+        SceneLoadResult::Spawning => (),
+        SceneLoadResult::Pending => return,
+        SceneLoadResult::Failed => {
+            tracing::warn!("failed replacing scene instance {id:?} with {scene_ref:?}, keeping the old instance");
+            c.entity(new_root).despawn_recursive();
+            return;
+        }
+    }
+    c.entity(old_root).despawn_recursive();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(super) fn despawn_scene_instance(In(id): In<SceneInstanceId>, mut c: Commands, scene_loader: Res<SceneLoader>)
+{
+    let Some(root_entity) = scene_loader.root_entity(id) else {
+        tracing::warn!("failed despawning scene instance {id:?}, the instance doesn't exist");
+        return;
+    };
+    let Some(ec) = c.get_entity(root_entity) else { return };
+    ec.despawn_recursive();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks the root entity of a loaded scene.
+#[derive(Component)]
+pub(crate) struct CobSceneRoot;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum SceneLoadResult
+{
+    Loaded,
+    /// The scene's file is lazy and hasn't loaded yet. The scene will be loaded once the file is ready.
+    Pending,
+    /// The scene's nodes are being spawned incrementally.
+    Spawning,
+    Failed,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A scene waiting for its lazy file to load.
+struct PendingScene
+{
+    root_entity: Entity,
+    scene_ref: SceneRef,
+    initializer: fn(&mut EntityCommands),
+    /// Scene instances are despawned if they fail to load.
+    is_instance: bool,
+    /// Id of the scene instance being replaced.
+    replaces: Option<SceneInstanceId>,
+    /// Whether the scene should be spawned incrementally.
+    incremental: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A scene whose nodes are being spawned incrementally.
+struct SpawningScene
+{
+    instance: SceneInstance,
+    initializer: fn(&mut EntityCommands),
+    /// Nodes that haven't been spawned yet, in traversal order.
+    nodes: VecDeque<(ScenePath, Option<SceneRef>)>,
+    /// Scenes referenced by spawned nodes. They are spawned after the hierarchy is complete.
+    instance_refs: Vec<(Entity, SceneRef)>,
+    parent_stack: Vec<Entity>,
+    prev_entity: Entity,
+    prev_path_length: usize,
+    /// The root entity's visibility before it was hidden, or `None` if the root hasn't been hidden.
+    prev_visibility: Option<Visibility>,
+}
+
+impl SpawningScene
+{
+    /// Queues all nodes of the scene for spawning into the scene's root entity.
+    fn queue_nodes(&mut self, root_scene_layer: &SceneLayer)
+    {
+        let root_entity = self.instance.root_entity();
+        self.nodes.clear();
+        self.instance_refs.clear();
+        self.parent_stack.clear();
+        self.prev_entity = root_entity;
+        self.prev_path_length = 1;
+
+        root_scene_layer.traverse(&mut |scene_node_path, node_layer| {
+            self.nodes
+                .push_back((scene_node_path.clone(), node_layer.instance_ref().cloned()));
+        });
+        if let Some(instance_ref) = root_scene_layer.instance_ref() {
+            self.instance_refs.push((root_entity, instance_ref.clone()));
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits how many nodes of scenes loaded with [`LoadSceneExt::load_scene_incremental`] are spawned each frame.
+///
+/// Nodes are spawned until either limit is reached. At least one node is spawned per frame while scenes are
+/// spawning. Scenes referenced by nodes with `=> file::scene` are spawned in one step.
+///
+/// Set with [`SceneLoader::set_spawn_budget`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SceneSpawnBudget
+{
+    /// Maximum number of nodes spawned per frame.
+    ///
+    /// Defaults to `100`.
+    pub max_nodes: usize,
+    /// Maximum time spent spawning nodes per frame.
+    ///
+    /// Defaults to 2ms.
+    pub max_time: Duration,
+}
+
+impl Default for SceneSpawnBudget
+{
+    fn default() -> Self
+    {
+        Self { max_nodes: 100, max_time: Duration::from_millis(2) }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks a live scene instance.
+struct SceneInstanceInfo
+{
+    root_entity: Entity,
+    scene_ref: SceneRef,
+    initializer: fn(&mut EntityCommands),
+    is_instance: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Handle for a scene requested with [`SceneLoader::request_scene`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneHandle
+{
+    scene: SceneRef,
+}
+
+impl SceneHandle
+{
+    /// Gets the requested scene.
+    pub fn scene(&self) -> &SceneRef
+    {
+        &self.scene
+    }
+
+    /// Returns `true` if the scene can be loaded immediately.
+    ///
+    /// See [`SceneLoader::is_scene_ready`].
+    pub fn is_ready(&self, scene_loader: &SceneLoader) -> bool
+    {
+        scene_loader.is_scene_ready(&self.scene)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) enum SceneLayerInsertionResult<'a>
 {
     #[cfg(feature = "hot_reload")]
@@ -93,9 +393,9 @@ impl SceneLayerData
     }
 
     /// Inspects this layer's id, then traverses its child layer.
-    pub(crate) fn traverse(&self, inspector: &mut impl FnMut(&ScenePath))
+    pub(crate) fn traverse(&self, inspector: &mut impl FnMut(&ScenePath, &SceneLayer))
     {
-        (inspector)(&self.id);
+        (inspector)(&self.id, &self.layer);
         self.layer.traverse(inspector);
     }
 }
@@ -108,6 +408,8 @@ pub(crate) struct SceneLayer
     children: Vec<SceneLayerData>,
     end_index: usize,
     total_child_nodes: usize,
+    /// A scene from another file that is spawned inside this layer's node.
+    instance_ref: Option<SceneRef>,
 }
 
 impl SceneLayer
@@ -185,8 +487,20 @@ impl SceneLayer
         self.total_child_nodes
     }
 
+    /// Sets the scene that should be spawned inside this layer's node.
+    pub(crate) fn set_instance_ref(&mut self, instance_ref: Option<SceneRef>)
+    {
+        self.instance_ref = instance_ref;
+    }
+
+    /// Gets the scene that should be spawned inside this layer's node.
+    pub(crate) fn instance_ref(&self) -> Option<&SceneRef>
+    {
+        self.instance_ref.as_ref()
+    }
+
     /// Iterates over the node's children in order, applying the inspector function to each one.
-    pub(crate) fn traverse(&self, inspector: &mut impl FnMut(&ScenePath))
+    pub(crate) fn traverse(&self, inspector: &mut impl FnMut(&ScenePath, &SceneLayer))
     {
         for child in self.children.iter() {
             child.traverse(inspector);
@@ -216,6 +530,13 @@ impl SceneRegistry
     {
         self.scenes.get(scene_ref)
     }
+
+    /// Removes all scenes in a file.
+    pub(crate) fn remove_file(&mut self, file: &CobFile)
+    {
+        self.scenes
+            .retain(|scene_ref, _| scene_ref.file.file() != Some(file));
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -225,6 +546,10 @@ pub(crate) struct SceneInstance
 {
     /// Reference to the scene root.
     scene_ref: SceneRef,
+    /// Public id of the instance.
+    id: SceneInstanceId,
+    /// Whether the instance replaced a previous instance with the same id.
+    replaced: bool,
     /// Root entity.
     entity: Entity,
     /// Prep function for new nodes.
@@ -239,6 +564,8 @@ impl SceneInstance
     {
         Self {
             scene_ref,
+            id: SceneInstanceId::PLACEHOLDER,
+            replaced: false,
             entity: Entity::PLACEHOLDER,
             new_node_prep_fn: NodeInitializer { initializer: |_| {} },
             nodes: HashMap::default(),
@@ -249,12 +576,16 @@ impl SceneInstance
     pub(crate) fn prepare(
         &mut self,
         scene_ref: SceneRef,
+        id: SceneInstanceId,
+        replaced: bool,
         entity: Entity,
         new_node_prep_fn: fn(&mut EntityCommands),
         node_count: usize,
     )
     {
         self.scene_ref = scene_ref;
+        self.id = id;
+        self.replaced = replaced;
         self.entity = entity;
         self.new_node_prep_fn = NodeInitializer { initializer: new_node_prep_fn };
         self.nodes.clear();
@@ -286,6 +617,12 @@ impl SceneInstance
         &self.scene_ref
     }
 
+    /// Returns the public id of this instance.
+    pub(crate) fn id(&self) -> SceneInstanceId
+    {
+        self.id
+    }
+
     /// Returns the root entity of this instance.
     pub(crate) fn root_entity(&self) -> Entity
     {
@@ -333,6 +670,28 @@ pub struct SceneLoader
     scene_registry: Option<SceneRegistry>,
     /// Entity vector cached for reuse when constructing scene instances.
     scene_parent_stack_cached: Vec<Entity>,
+    /// Scenes that are currently spawning scene instances referenced by their nodes.
+    ///
+    /// Used to detect scenes that contain themselves.
+    scene_ref_stack: Vec<SceneRef>,
+    /// Scenes waiting for their lazy files to load.
+    pending_scenes: Vec<PendingScene>,
+    /// [ root entity : scene instance ]
+    scene_roots: HashMap<Entity, SceneInstanceId>,
+    /// Live scene instances.
+    ///
+    /// Used to detect files with live scenes, and to reload or replace scene instances.
+    instances: HashMap<SceneInstanceId, SceneInstanceInfo>,
+    /// Counter for allocating scene instance ids.
+    next_instance_id: u64,
+    /// Scenes being spawned incrementally, in the order they were loaded.
+    spawning_scenes: VecDeque<SpawningScene>,
+    /// Number of nodes queued for incremental spawning since `spawning_scenes` was last empty.
+    ///
+    /// Used to report load progress.
+    queued_nodes: usize,
+    /// Limits incremental spawning each frame.
+    spawn_budget: SceneSpawnBudget,
     /// Tracks scene instances that exist in the world (hierarchies of entities).
     ///
     /// Used to update scene structures (add/remove/rearrange entities) in response to hot reloaded changes.
@@ -353,6 +712,119 @@ impl SceneLoader
         self.manifest_map.lock().unwrap()
     }
 
+    /// Gets the file of a scene, looking up its manifest key if necessary.
+    fn scene_file(&self, scene: &SceneRef) -> Option<CobFile>
+    {
+        match &scene.file {
+            SceneFile::File(file) => Some(file.clone()),
+            SceneFile::ManifestKey(key) => self.manifest_map().get(key),
+        }
+    }
+
+    /// Requests a scene, starting to load its file if the file is marked `lazy` in a manifest.
+    ///
+    /// The returned handle can be used to check when the scene is ready. Scenes in lazy files can also be loaded
+    /// with [`LoadSceneExt::load_scene`] before they are ready, in which case they will be spawned automatically
+    /// once their file and its imports have been extracted.
+    ///
+    /// Does nothing if the scene's manifest key hasn't been registered yet.
+    pub fn request_scene(&mut self, scene: impl Into<SceneRef>) -> SceneHandle
+    {
+        let scene = scene.into();
+        if let Some(file) = self.scene_file(&scene) {
+            self.manifest_map().request_lazy_file(&file);
+        }
+        SceneHandle { scene }
+    }
+
+    /// Returns `true` if the scene has been extracted and can be loaded immediately.
+    pub fn is_scene_ready(&self, scene: &SceneRef) -> bool
+    {
+        let Some(file) = self.scene_file(scene) else { return false };
+        if self
+            .manifest_map()
+            .lazy_file_status(&file)
+            .is_some_and(|status| status != LazyFileStatus::Loaded)
+        {
+            return false;
+        }
+
+        let scene = SceneRef { file: SceneFile::File(file), path: scene.path.clone() };
+        self.scene_registry
+            .as_ref()
+            .is_some_and(|registry| registry.get(&scene).is_some())
+    }
+
+    /// Gets lazy files that are loaded but have no live scenes.
+    ///
+    /// A scene is live until the root entity it was loaded into is despawned.
+    pub fn unused_lazy_files(&self) -> Vec<CobFile>
+    {
+        self.manifest_map()
+            .loaded_lazy_files()
+            .filter(|file| {
+                !self
+                    .instances
+                    .values()
+                    .any(|i| i.scene_ref.file.file() == Some(*file))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Removes scenes extracted from a file that is being unloaded.
+    ///
+    /// Returns the root entities of live scenes loaded from the file. Those scenes will no longer be updated when
+    /// the file is hot reloaded.
+    pub(crate) fn unload_file(&mut self, file: &CobFile) -> Vec<Entity>
+    {
+        if let Some(scene_registry) = &mut self.scene_registry {
+            scene_registry.remove_file(file);
+        }
+
+        #[cfg(feature = "hot_reload")]
+        {
+            self.scene_instances
+                .retain(|scene_ref, _| scene_ref.file.file() != Some(file));
+        }
+
+        self.instances
+            .values()
+            .filter(|i| i.scene_ref.file.file() == Some(file))
+            .map(|i| i.root_entity)
+            .collect()
+    }
+
+    /// Gets the root entity of a live scene instance.
+    pub fn root_entity(&self, id: SceneInstanceId) -> Option<Entity>
+    {
+        self.instances.get(&id).map(|i| i.root_entity)
+    }
+
+    /// Gets the scene that was loaded into a live scene instance.
+    pub fn instance_scene(&self, id: SceneInstanceId) -> Option<&SceneRef>
+    {
+        self.instances.get(&id).map(|i| &i.scene_ref)
+    }
+
+    /// Sets the per-frame budget for scenes loaded with [`LoadSceneExt::load_scene_incremental`].
+    pub fn set_spawn_budget(&mut self, budget: SceneSpawnBudget)
+    {
+        self.spawn_budget = budget;
+    }
+
+    /// Gets the per-frame budget for scenes loaded with [`LoadSceneExt::load_scene_incremental`].
+    pub fn spawn_budget(&self) -> SceneSpawnBudget
+    {
+        self.spawn_budget
+    }
+
+    /// Returns `true` if the scene instance is still being spawned incrementally.
+    pub fn is_spawning(&self, id: SceneInstanceId) -> bool
+    {
+        self.spawning_scenes.iter().any(|s| s.instance.id() == id)
+    }
+
     /// Extracts the scene registry so it can be updated.
     pub(crate) fn take_scene_registry(&mut self) -> SceneRegistry
     {
@@ -425,12 +897,19 @@ impl SceneLoader
             //   hierarchy.
             let node_ref = SceneRef { file: scene.file.clone(), path: inserted.clone() };
             c.syscall(
-                (node_entity, node_ref, scene_instance.node_prep_fn()),
+                (node_entity, node_ref.clone(), scene_instance.node_prep_fn()),
                 load_queued_from_ref,
             );
 
             // Save the entity.
+            let instance = scene_instance.id();
+            c.entity(node_entity)
+                .insert(SceneNode { instance, scene: node_ref });
             scene_instance.insert(inserted.clone(), node_entity);
+            c.react().entity_event(
+                root_entity,
+                SceneNodeAdded { instance, path: inserted.clone(), entity: node_entity },
+            );
         }
     }
 
@@ -487,6 +966,45 @@ impl SceneLoader
         }
     }
 
+    /// Replaces the scene instance spawned inside a scene node in existing scene instances.
+    ///
+    /// Used to update hierarchies after a `=> file::scene` reference is hot reloaded.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn handle_changed_scene_instance_ref(
+        &self,
+        c: &mut Commands,
+        scene: &SceneRef,
+        node: &ScenePath,
+        instance_ref: Option<SceneRef>,
+    )
+    {
+        // Look up scene.
+        let Some(scene_instances) = self.scene_instances.get(scene) else { return };
+
+        // Update each instance.
+        for scene_instance in scene_instances.iter() {
+            // Get the target entity.
+            let Some(node_entity) = scene_instance.get(node) else {
+                tracing::error!("failed updating scene instance of {:?} for {:?} with hot-reloaded scene reference \
+                    {:?}, node {:?} is missing (this is a bug)", scene, scene_instance.root_entity(), instance_ref, node);
+                continue;
+            };
+
+            // Remove the previous scene instance.
+            // - Nodes with scene references can't have child scene nodes, so all descendants belong to it.
+            let Some(mut ec) = c.get_entity(node_entity) else { continue };
+            ec.despawn_descendants();
+
+            // Load the new scene instance.
+            // - We load this 'queued' so the scene registry will be available.
+            let Some(instance_ref) = instance_ref.clone() else { continue };
+            c.syscall(
+                (node_entity, instance_ref, scene_instance.node_prep_fn()),
+                load_scene_instance_queued,
+            );
+        }
+    }
+
     /// Despawns scene branches from existing scene instances.
     ///
     /// Used to repair hierarchies after a scene structure change is hot reloaded.
@@ -528,6 +1046,14 @@ impl SceneLoader
             };
 
             ec.despawn_recursive();
+            c.react().entity_event(
+                scene_instance.root_entity(),
+                SceneNodeRemoved {
+                    instance: scene_instance.id(),
+                    path: deleted.clone(),
+                    entity: node_entity,
+                },
+            );
         }
     }
 
@@ -567,31 +1093,157 @@ impl SceneLoader
     ///
     /// The scene hierarchy is saved temporarily in a `SceneInstance`. It will be discarded when
     /// [`Self::release_active_scene`] is called unless the `hot_reload` feature is active.
-    pub(crate) fn load_scene<T>(&mut self, c: &mut Commands, root_entity: Entity, mut scene_ref: SceneRef) -> bool
+    ///
+    /// Returns `false` if the scene wasn't loaded. Scenes in lazy files that haven't loaded yet will be loaded
+    /// into the target entity once the file is ready.
+    pub(crate) fn load_scene<T>(&mut self, c: &mut Commands, root_entity: Entity, scene_ref: SceneRef) -> bool
+    where
+        T: crate::loading::scene::load_scene_ext::scene_traits::SceneNodeLoader,
+    {
+        self.load_scene_impl(c, root_entity, scene_ref, T::initialize_scene_node, false, None, false)
+            == SceneLoadResult::Loaded
+    }
+
+    /// Loads a scene into a target entity, spawning its nodes incrementally over multiple frames.
+    ///
+    /// See [`LoadSceneExt::load_scene_incremental`].
+    pub(crate) fn load_scene_incremental<T>(&mut self, c: &mut Commands, root_entity: Entity, scene_ref: SceneRef)
     where
         T: crate::loading::scene::load_scene_ext::scene_traits::SceneNodeLoader,
+    {
+        match self.load_scene_impl(c, root_entity, scene_ref, T::initialize_scene_node, false, None, true) {
+            // Scenes without child nodes are finished immediately.
+            SceneLoadResult::Loaded => self.release_active_scene(c),
+            SceneLoadResult::Pending | SceneLoadResult::Spawning | SceneLoadResult::Failed => (),
+        }
+    }
+
+    /// Spawns a child of `parent` and loads a scene referenced by a scene node into it.
+    ///
+    /// The scene instance is released immediately since it can't be edited with [`LoadedScene`].
+    pub(crate) fn load_scene_instance(
+        &mut self,
+        c: &mut Commands,
+        parent: Entity,
+        instance_ref: SceneRef,
+        initializer: fn(&mut EntityCommands),
+    )
+    {
+        if c.get_entity(parent).is_none() {
+            return;
+        }
+        let entity = c.spawn_empty().set_parent(parent).id();
+        match self.load_scene_impl(c, entity, instance_ref, initializer, true, None, false) {
+            SceneLoadResult::Loaded => self.release_active_scene(c),
+            SceneLoadResult::Pending | SceneLoadResult::Spawning => (),
+            SceneLoadResult::Failed => c.entity(entity).despawn_recursive(),
+        }
+    }
+
+    /// Loads scenes whose lazy files have finished loading.
+    pub(crate) fn spawn_pending_scenes(&mut self, c: &mut Commands)
+    {
+        if self.pending_scenes.is_empty() {
+            return;
+        }
+
+        for pending in std::mem::take(&mut self.pending_scenes) {
+            // Skip scenes whose root entities were despawned while waiting.
+            if c.get_entity(pending.root_entity).is_none() {
+                continue;
+            }
+
+            // Discard replacements for scene instances that were despawned while waiting.
+            let replaced_root = pending.replaces.map(|id| self.root_entity(id));
+            if replaced_root == Some(None) {
+                c.entity(pending.root_entity).despawn_recursive();
+                continue;
+            }
+
+            // Scenes that are still waiting will be re-added to the pending list.
+            let result = self.load_scene_impl(
+                c,
+                pending.root_entity,
+                pending.scene_ref,
+                pending.initializer,
+                pending.is_instance,
+                pending.replaces,
+                pending.incremental,
+            );
+            match result {
+                SceneLoadResult::Loaded => self.release_active_scene(c),
+                SceneLoadResult::Pending | SceneLoadResult::Spawning => (),
+                SceneLoadResult::Failed => {
+                    if pending.is_instance || pending.replaces.is_some() {
+                        c.entity(pending.root_entity).despawn_recursive();
+                    }
+                }
+            }
+
+            // Despawn replaced scene instances once their replacements are loaded.
+            let Some(Some(replaced_root)) = replaced_root else { continue };
+            if !matches!(result, SceneLoadResult::Loaded | SceneLoadResult::Spawning) {
+                continue;
+            }
+            let Some(ec) = c.get_entity(replaced_root) else { continue };
+            ec.despawn_recursive();
+        }
+    }
+
+    fn load_scene_impl(
+        &mut self,
+        c: &mut Commands,
+        root_entity: Entity,
+        mut scene_ref: SceneRef,
+        initializer: fn(&mut EntityCommands),
+        is_instance: bool,
+        replaces: Option<SceneInstanceId>,
+        incremental: bool,
+    ) -> SceneLoadResult
     {
         // Reject non-root nodes.
         if scene_ref.path.len() != 1 {
             tracing::warn!("failed loading scene {:?} into {:?}, the requested location has a scene path length of {} \
                 but only root scene nodes (path length 1) can be used to load a scene",
                 scene_ref, root_entity, scene_ref.path.len());
-            return false;
+            return SceneLoadResult::Failed;
         }
 
         // Replace manifest key in the requested scene.
         self.manifest_map().swap_for_file(&mut scene_ref.file);
 
+        // Reject scenes that contain themselves.
+        if self.scene_ref_stack.contains(&scene_ref) {
+            tracing::error!("failed loading scene {:?} into {:?}, the scene contains itself through the scene \
+                references in {:?}", scene_ref, root_entity, self.scene_ref_stack);
+            return SceneLoadResult::Failed;
+        }
+
+        // Wait for lazy files to load.
+        if let SceneFile::File(file) = &scene_ref.file {
+            if self.manifest_map().request_lazy_file(file) {
+                self.pending_scenes.push(PendingScene {
+                    root_entity,
+                    scene_ref,
+                    initializer,
+                    is_instance,
+                    replaces,
+                    incremental,
+                });
+                return SceneLoadResult::Pending;
+            }
+        }
+
         // Look up the requested scene.
         let Some(scene_registry) = &self.scene_registry else {
             tracing::error!("scene load of {:?} into {:?} failed, scene registry is missing; it's likely the scene's \
                 file has not loaded yet; wait to load scenes until in LoadState::Done", scene_ref, root_entity);
-            return false;
+            return SceneLoadResult::Failed;
         };
         let Some(root_scene_layer) = scene_registry.get(&scene_ref) else {
             tracing::error!("failed loading scene {:?} into {:?}, there is no scene at that location OR the \
                 scene's file has not loaded; wait to load scenes until in LoadState::Done", scene_ref, root_entity);
-            return false;
+            return SceneLoadResult::Failed;
         };
 
         // Prepare scene instance.
@@ -618,16 +1270,52 @@ impl SceneLoader
                 SceneInstance::new_for_ref(scene_ref.clone())
             }
         };
+        let instance = replaces.unwrap_or_else(|| {
+            self.next_instance_id += 1;
+            SceneInstanceId(self.next_instance_id)
+        });
         scene_instance.prepare(
             scene_ref.clone(),
+            instance,
+            replaces.is_some(),
             root_entity,
-            T::initialize_scene_node,
+            initializer,
             root_scene_layer.total_child_nodes(),
         );
 
         // Load the root entity.
         let mut root_ec = c.entity(root_entity);
-        root_ec.load_with_initializer(scene_ref.clone(), T::initialize_scene_node);
+        root_ec.load_with_initializer(scene_ref.clone(), initializer);
+        root_ec.insert((CobSceneRoot, SceneNode { instance, scene: scene_ref.clone() }));
+        self.scene_roots.insert(root_entity, instance);
+        self.instances.insert(
+            instance,
+            SceneInstanceInfo {
+                root_entity,
+                scene_ref: scene_ref.clone(),
+                initializer,
+                is_instance,
+            },
+        );
+
+        // Queue the hierarchy for incremental spawning. The root is hidden until all nodes are spawned.
+        if incremental && root_scene_layer.total_child_nodes() > 0 {
+            let mut spawning = SpawningScene {
+                instance: scene_instance,
+                initializer,
+                nodes: VecDeque::with_capacity(root_scene_layer.total_child_nodes()),
+                instance_refs: vec![],
+                parent_stack: vec![],
+                prev_entity: root_entity,
+                prev_path_length: 1,
+                prev_visibility: None,
+            };
+            spawning.queue_nodes(root_scene_layer);
+            self.queued_nodes += spawning.nodes.len();
+            self.spawning_scenes.push_back(spawning);
+            c.queue(move |world: &mut World| hide_spawning_scene_root(world, root_entity));
+            return SceneLoadResult::Spawning;
+        }
 
         // Spawn hierarchy, loading all child paths.
         // - Hierarchy spawn order matches the order in cob files.
@@ -637,8 +1325,12 @@ impl SceneLoader
         parent_stack.clear();
         let mut prev_entity = root_entity;
         let mut prev_path_length = 1;
+        let mut instance_refs = vec![];
+        if let Some(instance_ref) = root_scene_layer.instance_ref() {
+            instance_refs.push((root_entity, instance_ref.clone()));
+        }
 
-        root_scene_layer.traverse(&mut |scene_node_path| {
+        root_scene_layer.traverse(&mut |scene_node_path, node_layer| {
             debug_assert!(scene_node_path.len() > 1);
             let path_change = (scene_node_path.len() as i32) - (prev_path_length as i32);
 
@@ -663,19 +1355,34 @@ impl SceneLoader
 
             // Load the scene node to the entity.
             let node_ref = SceneRef { file: scene_ref.file.clone(), path: scene_node_path.clone() };
-            ec.load_with_initializer(node_ref.clone(), T::initialize_scene_node);
+            ec.load_with_initializer(node_ref.clone(), initializer);
+            ec.insert(SceneNode { instance, scene: node_ref.clone() });
 
             // Save the entity.
             let node_entity = ec.id();
             scene_instance.insert(node_ref.path, node_entity);
+            if let Some(instance_ref) = node_layer.instance_ref() {
+                instance_refs.push((node_entity, instance_ref.clone()));
+            }
 
             prev_entity = node_entity;
             prev_path_length = scene_node_path.len();
         });
 
+        // Spawn scenes referenced by scene nodes.
+        // - These are spawned before the active scene is saved so they don't end up on top of the active scene
+        //   stack.
+        if !instance_refs.is_empty() {
+            self.scene_ref_stack.push(scene_ref);
+            for (node_entity, instance_ref) in instance_refs {
+                self.load_scene_instance(c, node_entity, instance_ref, initializer);
+            }
+            self.scene_ref_stack.pop();
+        }
+
         // Save the scene stack for use when editing the scene contents.
         self.active_scene_stack.push(scene_instance);
-        true
+        SceneLoadResult::Loaded
     }
 
     /// Gets the current active scene (the topmost entry in the active scene stack).
@@ -684,11 +1391,128 @@ impl SceneLoader
         self.active_scene_stack.last()
     }
 
+    /// Spawns the next node of the oldest incrementally spawning scene.
+    ///
+    /// When all of a scene's nodes are spawned, scenes referenced by its nodes are spawned, the root entity is
+    /// made visible, and the scene instance is finished.
+    ///
+    /// Returns `false` if there are no scenes spawning.
+    fn spawn_incremental_node(&mut self, c: &mut Commands) -> bool
+    {
+        let Some(spawning) = self.spawning_scenes.front_mut() else { return false };
+
+        // Discard scenes whose root entities were despawned while spawning.
+        if c.get_entity(spawning.instance.root_entity()).is_none() {
+            let discarded = self.pop_spawning_scene();
+            self.scene_instance_cache.push(discarded.instance);
+            return true;
+        }
+
+        // Spawn the next node.
+        // - This mirrors the hierarchy traversal in `load_scene_impl`.
+        if let Some((scene_node_path, instance_ref)) = spawning.nodes.pop_front() {
+            let path_change = (scene_node_path.len() as i32) - (spawning.prev_path_length as i32);
+            if path_change > 0 {
+                debug_assert_eq!(path_change, 1);
+                spawning.parent_stack.push(spawning.prev_entity);
+            } else if path_change < 0 {
+                let len = spawning.parent_stack.len();
+                spawning
+                    .parent_stack
+                    .truncate(len - (path_change.unsigned_abs() as usize));
+            }
+
+            let mut ec = c.spawn_empty();
+            ec.set_parent(*spawning.parent_stack.last().unwrap());
+
+            let node_ref = SceneRef {
+                file: spawning.instance.scene_ref().file.clone(),
+                path: scene_node_path.clone(),
+            };
+            ec.load_with_initializer(node_ref.clone(), spawning.initializer);
+            ec.insert(SceneNode { instance: spawning.instance.id(), scene: node_ref.clone() });
+
+            let node_entity = ec.id();
+            spawning.instance.insert(node_ref.path, node_entity);
+            if let Some(instance_ref) = instance_ref {
+                spawning.instance_refs.push((node_entity, instance_ref));
+            }
+
+            spawning.prev_entity = node_entity;
+            spawning.prev_path_length = scene_node_path.len();
+            return true;
+        }
+
+        // Finish the scene.
+        let finished = self.pop_spawning_scene();
+        if !finished.instance_refs.is_empty() {
+            self.scene_ref_stack
+                .push(finished.instance.scene_ref().clone());
+            for (node_entity, instance_ref) in finished.instance_refs {
+                self.load_scene_instance(c, node_entity, instance_ref, finished.initializer);
+            }
+            self.scene_ref_stack.pop();
+        }
+        if let Some(prev_visibility) = finished.prev_visibility {
+            c.entity(finished.instance.root_entity())
+                .insert(prev_visibility);
+        }
+        self.finish_scene_instance(c, finished.instance);
+
+        true
+    }
+
+    /// Removes the oldest incrementally spawning scene.
+    ///
+    /// Panics if no scenes are spawning.
+    fn pop_spawning_scene(&mut self) -> SpawningScene
+    {
+        let spawning = self.spawning_scenes.pop_front().unwrap();
+        if self.spawning_scenes.is_empty() {
+            self.queued_nodes = 0;
+        }
+        spawning
+    }
+
+    /// Restarts incrementally spawning scenes from a file that was reloaded.
+    ///
+    /// Nodes that were already spawned are despawned, then the scene's nodes are queued again from its current
+    /// structure. If the scene was removed from the file, the scene is finished with only its root entity.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn restart_spawning_scenes(&mut self, c: &mut Commands, file: &CobFile)
+    {
+        let Some(scene_registry) = &self.scene_registry else { return };
+
+        for spawning in self.spawning_scenes.iter_mut() {
+            if spawning.instance.scene_ref().file.file() != Some(file) {
+                continue;
+            }
+
+            // Despawn spawned nodes. Despawning the root's children despawns the rest of the hierarchy.
+            for (_, node_entity) in spawning.instance.nodes.drain().filter(|(path, _)| path.len() == 2) {
+                let Some(ec) = c.get_entity(node_entity) else { continue };
+                ec.despawn_recursive();
+            }
+
+            // Queue the scene's current nodes.
+            let Some(root_scene_layer) = scene_registry.get(spawning.instance.scene_ref()) else {
+                spawning.nodes.clear();
+                spawning.instance_refs.clear();
+                continue;
+            };
+            spawning.queue_nodes(root_scene_layer);
+            self.queued_nodes += spawning.nodes.len();
+        }
+    }
+
     /// Pops an entry from the active `SceneInstance` stack.
     ///
     /// When `hot_reload` is not enabled, the scene hierarchy cache will be discarded here. We assume the scene
     /// hierarchy only needs to be accessed during construction and for hot reloading nodes.
-    pub(crate) fn release_active_scene(&mut self)
+    ///
+    /// Sends [`SceneSpawned`] (and [`SceneReloaded`] if the instance replaced another instance) to the scene's
+    /// root entity.
+    pub(crate) fn release_active_scene(&mut self, c: &mut Commands)
     {
         // Remove scene stack.
         let Some(released) = self.active_scene_stack.pop() else {
@@ -696,6 +1520,24 @@ impl SceneLoader
             return;
         };
 
+        self.finish_scene_instance(c, released);
+    }
+
+    /// Notifies the root entity of a fully spawned scene instance, then saves the instance for hot reloading or
+    /// reuse.
+    fn finish_scene_instance(&mut self, c: &mut Commands, released: SceneInstance)
+    {
+        // Notify the root entity.
+        let instance = released.id();
+        let root_entity = released.root_entity();
+        let scene = released.scene_ref().clone();
+        c.react()
+            .entity_event(root_entity, SceneSpawned { instance, scene: scene.clone() });
+        if released.replaced {
+            c.react()
+                .entity_event(root_entity, SceneReloaded { instance, scene });
+        }
+
         // On hot reload, save so scene entities can be adjusted when the scene file changes.
         #[cfg(feature = "hot_reload")]
         {
@@ -715,6 +1557,22 @@ impl SceneLoader
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Reports the nodes of scenes loaded with [`LoadSceneExt::load_scene_incremental`] that haven't spawned yet.
+impl AssetLoadProgress for SceneLoader
+{
+    fn pending_assets(&self) -> usize
+    {
+        self.spawning_scenes.iter().map(|s| s.nodes.len()).sum()
+    }
+
+    fn total_assets(&self) -> usize
+    {
+        self.queued_nodes
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Plugin that enables scene loading.
 pub(crate) struct SceneLoaderPlugin;
 
@@ -723,7 +1581,10 @@ impl Plugin for SceneLoaderPlugin
     fn build(&self, app: &mut App)
     {
         let manifest_map = app.world().resource::<CobAssetCache>().manifest_map_clone();
-        app.insert_resource(SceneLoader::new(manifest_map));
+        app.insert_resource(SceneLoader::new(manifest_map))
+            .register_asset_tracker::<SceneLoader>()
+            .add_systems(First, spawn_incremental_scenes.after(FileProcessingSet))
+            .add_systems(Last, cleanup_scene_roots);
     }
 }
 
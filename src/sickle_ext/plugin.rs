@@ -20,7 +20,8 @@ impl Plugin for SickleExtPlugin
             .add_plugins(ControlPlugin)
             .add_plugins(ControlMapPlugin)
             .add_plugins(UiInteractionExtPlugin)
-            .add_plugins(PseudoStatesExtPlugin);
+            .add_plugins(PseudoStatesExtPlugin)
+            .add_plugins(UiCallbacksPlugin);
     }
 }
 
@@ -0,0 +1,264 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use smol_str::SmolStr;
+
+use crate::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for instruction loadables that run a named UI callback when an entity event is emitted.
+trait NamedUiCallback: Loadable
+{
+    type Event: Send + Sync + 'static;
+
+    fn name(&self) -> &str;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks that the UI callback referenced by an extracted loadable is registered.
+fn validate_ui_callback<T: NamedUiCallback>(
+    value: &dyn PartialReflect,
+    loadables: &LoadableRegistry,
+) -> Result<(), String>
+{
+    let Some(value) = T::from_reflect(value) else { return Ok(()) };
+    let name = value.name();
+    if loadables.has_ui_callback(name) {
+        return Ok(());
+    }
+
+    let mut err = format!("unknown UI callback {name:?}");
+    if let Some(suggestion) = closest_match(name, loadables.ui_callback_names()) {
+        err += &format!("; did you mean {suggestion:?}?");
+    }
+    Err(err)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Component)]
+struct UiCallbackReactor<E: Send + Sync + 'static>
+{
+    token: RevokeToken,
+    _p: PhantomData<E>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_ui_callback<T: NamedUiCallback>(callback: T, entity: Entity, world: &mut World)
+{
+    // Clean up the previous reactor so hot-reloaded callbacks replace each other.
+    revert_ui_callback::<T>(entity, world);
+
+    let Some(syscommand) = world.resource::<UiCallbacks>().get(callback.name()) else {
+        tracing::warn!("failed applying {} to {entity:?}; UI callback {:?} is not registered",
+            std::any::type_name::<T>(), callback.name());
+        return;
+    };
+    let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+
+    let mut token = None;
+    emut.world_scope(|world| {
+        let revoke_token = world.react(|rc| {
+            rc.on_revokable(entity_event::<T::Event>(entity), move |mut c: Commands| {
+                c.queue(syscommand);
+            })
+        });
+        world.syscall(
+            (entity, revoke_token.clone()),
+            |In((entity, revoke_token)): In<(Entity, RevokeToken)>, mut c: Commands| {
+                cleanup_reactor_on_despawn(&mut c, entity, revoke_token);
+            },
+        );
+        token = Some(revoke_token);
+    });
+    emut.insert(UiCallbackReactor::<T::Event> { token: token.unwrap(), _p: PhantomData });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn revert_ui_callback<T: NamedUiCallback>(entity: Entity, world: &mut World)
+{
+    let Ok(mut emut) = world.get_entity_mut(entity) else { return };
+    if let Some(reactor) = emut.take::<UiCallbackReactor<T::Event>>() {
+        world.react(move |rc| rc.revoke(reactor.token));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that stores callbacks registered with [`UiCallbackAppExt::register_ui_callback`].
+#[derive(Resource, Default)]
+pub struct UiCallbacks
+{
+    callbacks: HashMap<SmolStr, SystemCommand>,
+}
+
+impl UiCallbacks
+{
+    /// Gets the system command of a registered callback.
+    pub fn get(&self, name: impl AsRef<str>) -> Option<SystemCommand>
+    {
+        self.callbacks.get(name.as_ref()).copied()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Extends `App` with methods for registering named UI callbacks.
+pub trait UiCallbackAppExt
+{
+    /// Registers a callback that can be referenced by name from COB files with [`OnPressed`] and [`OnReleased`].
+    ///
+    /// Callbacks should be registered before COB files are loaded, otherwise references to them will be reported
+    /// as errors when scenes are extracted.
+    fn register_ui_callback<R: ReactorResult, M>(
+        &mut self,
+        name: impl Into<SmolStr>,
+        callback: impl IntoSystem<(), R, M> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl UiCallbackAppExt for App
+{
+    fn register_ui_callback<R: ReactorResult, M>(
+        &mut self,
+        name: impl Into<SmolStr>,
+        callback: impl IntoSystem<(), R, M> + Send + Sync + 'static,
+    ) -> &mut Self
+    {
+        let name = name.into();
+        let mut callback = RawCallbackSystem::new(callback);
+        let syscommand = self
+            .world_mut()
+            .commands()
+            .spawn_system_command(move |world: &mut World| {
+                let result = callback.run_with_cleanup(world, (), |_| {});
+                result.handle(world);
+            });
+        self.world_mut().flush();
+
+        let prev = self
+            .world_mut()
+            .get_resource_or_insert_with::<UiCallbacks>(|| Default::default())
+            .callbacks
+            .insert(name.clone(), syscommand);
+        if let Some(prev) = prev {
+            tracing::warn!("overwriting UI callback {name:?}");
+            self.world_mut().despawn(*prev);
+        }
+        self.world_mut()
+            .get_resource_or_insert_with::<LoadableRegistry>(|| Default::default())
+            .add_ui_callback(name);
+        self
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that runs a callback registered with [`UiCallbackAppExt::register_ui_callback`] when the
+/// entity emits [`Pressed`].
+///
+/// Unknown callback names are reported as errors when scenes are extracted. Use [`Interactive`] to make the entity
+/// emit interaction events.
+///
+/// Example:
+/// ```rust
+/// "settings_button"
+///     Interactive
+///     OnPressed("open_settings")
+/// ```
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct OnPressed(pub String);
+
+impl NamedUiCallback for OnPressed
+{
+    type Event = Pressed;
+
+    fn name(&self) -> &str
+    {
+        self.0.as_str()
+    }
+}
+
+impl Instruction for OnPressed
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        apply_ui_callback(self, entity, world);
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        revert_ui_callback::<Self>(entity, world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Instruction loadable that runs a callback registered with [`UiCallbackAppExt::register_ui_callback`] when the
+/// entity emits [`Released`].
+///
+/// See [`OnPressed`].
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct OnReleased(pub String);
+
+impl NamedUiCallback for OnReleased
+{
+    type Event = Released;
+
+    fn name(&self) -> &str
+    {
+        self.0.as_str()
+    }
+}
+
+impl Instruction for OnReleased
+{
+    fn apply(self, entity: Entity, world: &mut World)
+    {
+        apply_ui_callback(self, entity, world);
+    }
+
+    fn revert(entity: Entity, world: &mut World)
+    {
+        revert_ui_callback::<Self>(entity, world);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) struct UiCallbacksPlugin;
+
+impl Plugin for UiCallbacksPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<UiCallbacks>()
+            .register_instruction_type::<OnPressed>()
+            .register_instruction_type::<OnReleased>();
+
+        let mut loadables = app
+            .world_mut()
+            .get_resource_or_insert_with::<LoadableRegistry>(|| Default::default());
+        loadables.set_validator(TypeId::of::<OnPressed>(), validate_ui_callback::<OnPressed>);
+        loadables.set_validator(TypeId::of::<OnReleased>(), validate_ui_callback::<OnReleased>);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,108 @@
+use bevy_cobweb_ui::prelude::cob::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn assets_section()
+{
+    let res = test_cob(
+        b"#assets
+",
+    );
+    let CobSection::Assets(assets) = &res.sections[0] else { unreachable!() };
+    assert_eq!(assets.entries.len(), 0);
+
+    let res = test_cob(
+        br#"#assets
+images ["a.png" "b.png"]
+fonts ["Fira Sans"]
+font_families [{family: "Fira Sans" fonts: [{path: "fonts/FiraSans.ttf"}]}]
+audio $sounds
+atlases [
+    {texture: "a.png" alias: "a" tile_size: {x: 16 y: 16} columns: 2 rows: 2}
+]
+"#,
+    );
+    let CobSection::Assets(assets) = &res.sections[0] else { unreachable!() };
+    assert_eq!(assets.entries.len(), 5);
+    assert_eq!(assets.entries[0].kind, CobAssetKind::Images);
+    assert_eq!(assets.entries[1].kind, CobAssetKind::Fonts);
+    assert_eq!(assets.entries[2].kind, CobAssetKind::FontFamilies);
+    assert_eq!(assets.entries[3].kind, CobAssetKind::Audio);
+    assert_eq!(assets.entries[4].kind, CobAssetKind::Atlases);
+    let CobValue::Array(images) = &assets.entries[0].value else { unreachable!() };
+    assert_eq!(images.entries.len(), 2);
+    assert!(matches!(assets.entries[3].value, CobValue::Constant(_)));
+
+    // Can be mixed with other sections.
+    test_cob(
+        br#"#defs
+$image = "a.png"
+
+#assets
+images [$image]
+
+#commands
+Example
+"#,
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn asset_kinds()
+{
+    let res = test_cob(
+        br#"#assets
+images ["a.png" {image: "b.png"}]
+"#,
+    );
+    let CobSection::Assets(assets) = &res.sections[0] else { unreachable!() };
+    let CobValue::Array(values) = &assets.entries[0].value else { unreachable!() };
+    let (path, map) = (&values.entries[0], &values.entries[1]);
+
+    assert_eq!(CobAssetKind::Images.command_for(path), Some("LoadImages"));
+    assert_eq!(CobAssetKind::Fonts.command_for(path), Some("LoadFonts"));
+    assert_eq!(CobAssetKind::Audio.command_for(path), Some("LoadAudio"));
+    assert_eq!(CobAssetKind::FontFamilies.command_for(path), None);
+    assert_eq!(CobAssetKind::Atlases.command_for(path), None);
+
+    assert_eq!(CobAssetKind::Images.command_for(map), Some("LoadLocalizedImages"));
+    assert_eq!(CobAssetKind::Fonts.command_for(map), Some("LoadLocalizedFonts"));
+    assert_eq!(CobAssetKind::FontFamilies.command_for(map), Some("RegisterFontFamilies"));
+    assert_eq!(CobAssetKind::Audio.command_for(map), Some("LoadLocalizedAudio"));
+    assert_eq!(CobAssetKind::Atlases.command_for(map), Some("LoadTextureAtlasLayouts"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn assets_errors()
+{
+    // Unknown asset kind
+    test_cob_fail(
+        br#"#assets
+sprites ["a.png"]"#,
+        br#"sprites ["a.png"]"#,
+    );
+    // Missing value
+    test_cob_fail(
+        b"#assets
+images
+",
+        b"",
+    );
+    // Entry not starting with newline
+    test_cob_fail(br#"#assets images ["a.png"]"#, br#"images ["a.png"]"#);
+    // Section not starting on newline
+    test_cob_fail(
+        b" #assets
+",
+        b"#assets\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,185 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{test_cob, test_cob_fail, test_span};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project with one in-memory file, then loads and resolves it with the given flags.
+fn resolve_with_flags(content: &str, flags: &[(&str, bool)]) -> CobProject
+{
+    let file = CobFile::try_new("main.cob").unwrap();
+    let mut project = CobProject::new("test_assets");
+    project.override_file(file, content);
+    for (flag, value) in flags.iter() {
+        project.flags_mut().set(flag, *value);
+    }
+    project.load("main.cob");
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets a constant's value as a string.
+fn constant(project: &CobProject, name: &str) -> Option<String>
+{
+    let resolver = project.resolver(&CobFile::try_new("main.cob").unwrap())?;
+    let value = resolver.loadables.constants.get(name)?;
+    let mut bytes = Vec::default();
+    value
+        .write_to(&mut DefaultRawSerializer::new(&mut bytes))
+        .unwrap();
+    Some(String::from_utf8(bytes).unwrap().trim().into())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conditions_parsing()
+{
+    let res = test_cob(
+        b"#[if(wasm)]
+#defs
+$a = 1
+
+#[if( not(wasm) )]
+#[if(any(a all(b c)))]
+#defs
+#[if(a)]
+$a = 2
+
+#commands
+#[if(a)]
+A
+
+#scenes
+\"root\"
+    #[if(all())]
+    A
+    #[if(a)]
+    \"child\"
+        +m{
+            #[if(b)]
+            B
+        }
+",
+    );
+    assert_eq!(res.sections.len(), 7);
+    let CobSection::Condition(condition) = &res.sections[0] else { unreachable!() };
+    assert!(matches!(&condition.expr, CobConditionExpr::Flag { name, .. } if name == "wasm"));
+    let CobSection::Condition(condition) = &res.sections[3] else { unreachable!() };
+    let CobConditionExpr::Op { op, args, .. } = &condition.expr else { unreachable!() };
+    assert_eq!(*op, CobConditionOp::Any);
+    assert_eq!(args.len(), 2);
+    let CobSection::Defs(defs) = &res.sections[4] else { unreachable!() };
+    assert!(matches!(defs.entries[0], CobDefEntry::Condition(_)));
+    let CobSection::Commands(commands) = &res.sections[5] else { unreachable!() };
+    assert!(matches!(commands.entries[0], CobCommandEntry::Condition(_)));
+    let CobSection::Scenes(scenes) = &res.sections[6] else { unreachable!() };
+    assert!(matches!(scenes.scenes[0].entries[0], CobSceneLayerEntry::Condition(_)));
+    assert!(matches!(scenes.scenes[0].entries[2], CobSceneLayerEntry::Condition(_)));
+
+    // Conditions can't be applied to scene roots or to #manifest/#import sections.
+    test_cob_fail(b"#scenes\n#[if(a)]\n\"root\"\n", b"#[if(a)]\n\"root\"\n");
+    test_cob_fail(b"#[if(a)]\n#manifest\n", b"#[if(a)]\n#manifest\n");
+
+    // Invalid expressions.
+    test_cob_fail(b"#commands\n#[if(not(a b))]\nA\n", b"not(a b))]\nA\n");
+    test_cob_fail(b"#commands\n#[if(a)] A\n", b"A\n");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conditions_evaluation()
+{
+    let mut flags = CobFlags::default();
+    flags.set("a", true);
+    flags.set("b", false);
+
+    let test = |raw: &str| -> Result<bool, String> {
+        let (Some(condition), _, _) = CobCondition::try_parse(CobFill::default(), test_span(raw)).unwrap() else {
+            unreachable!()
+        };
+        condition.evaluate(&flags)
+    };
+
+    assert_eq!(test("#[if(a)]"), Ok(true));
+    assert_eq!(test("#[if(not(a))]"), Ok(false));
+    assert_eq!(test("#[if(any(b a))]"), Ok(true));
+    assert_eq!(test("#[if(all(a b))]"), Ok(false));
+    assert_eq!(test("#[if(any())]"), Ok(false));
+    assert_eq!(test("#[if(all())]"), Ok(true));
+    assert!(test("#[if(unknown)]").is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn conditions_filtering()
+{
+    let content = "#[if(a)]
+#defs
+$x = 10
+
+#[if(not(a))]
+#defs
+$x = 20
+
+#defs
+#[if(b)]
+$y = 1
+#[if(a)]
+#[if(b)]
+$z = 1
+
+#scenes
+\"root\"
+    #[if(a)]
+    \"a\"
+    #[if(b)]
+    \"b\"
+        \"b_child\"
+    #[if(unknown)]
+    \"c\"
+";
+    let file = CobFile::try_new("main.cob").unwrap();
+
+    // a = true
+    let project = resolve_with_flags(content, &[("a", true), ("b", false)]);
+    assert_eq!(constant(&project, "x").as_deref(), Some("10"));
+    assert_eq!(constant(&project, "y"), None);
+    assert_eq!(constant(&project, "z"), None);
+
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    let names: Vec<_> = scenes.scenes[0]
+        .entries
+        .iter()
+        .filter_map(|e| match e {
+            CobSceneLayerEntry::Layer(layer) => Some(layer.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["a"]);
+
+    // The unknown flag is reported.
+    assert!(!project.has_errors());
+    assert_eq!(project.diagnostics().len(), 1);
+
+    // a = false, b = true
+    let project = resolve_with_flags(content, &[("a", false), ("b", true)]);
+    assert_eq!(constant(&project, "x").as_deref(), Some("20"));
+    assert_eq!(constant(&project, "y").as_deref(), Some("1"));
+    assert_eq!(constant(&project, "z"), None);
+
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    let CobSceneLayerEntry::Layer(layer) = &scenes.scenes[0].entries[0] else { unreachable!() };
+    assert_eq!(layer.name.as_str(), "b");
+    assert_eq!(layer.entries.len(), 1);
+    assert_eq!(scenes.scenes[0].entries.len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
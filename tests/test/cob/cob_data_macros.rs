@@ -0,0 +1,239 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project from in-memory files, then loads and resolves the first file.
+fn make_project(files: &[(&str, &str)]) -> CobProject
+{
+    let mut project = CobProject::new("test_assets");
+    for (file, content) in files.iter() {
+        project.override_file(CobFile::try_new(file).unwrap(), *content);
+    }
+    project.load(files[0].0);
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Formats the resolved `#commands` sections of a file.
+fn resolved_commands(project: &CobProject, file: &str) -> String
+{
+    let file = CobFile::try_new(file).unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let cob = Cob {
+        file,
+        sections: resolved
+            .sections
+            .iter()
+            .filter(|s| matches!(s, CobSection::Commands(_)))
+            .cloned()
+            .collect(),
+        end_fill: CobFill::default(),
+    };
+    CobFormatter::new().format_to_string(&cob)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn defs_section_data_macros()
+{
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    let res = test_cob(
+        b"#defs
+*a() = 10
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+    assert_eq!(defs.entries.len(), 1);
+    let CobDefEntry::DataMacro(data_macro) = &defs.entries[0] else { unreachable!() };
+    assert_eq!(data_macro.name.as_str(), "a");
+    assert_eq!(data_macro.params.len(), 0);
+    let CobValue::Number(number) = &data_macro.value else { unreachable!() };
+    assert_eq!(number.number.as_u128().unwrap(), 10);
+
+    let res = test_cob(
+        b"
+#defs
+*a() = 10
+*b(x y_2) = X{ a:$x b:$y_2 }
+*c( x ) = *b($x *a())
+*d(x) = [$x, $a::b::c]
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+    assert_eq!(defs.entries.len(), 4);
+
+    let CobDefEntry::DataMacro(data_macro) = &defs.entries[1] else { unreachable!() };
+    assert_eq!(data_macro.name.as_str(), "b");
+    assert_eq!(data_macro.params.iter().collect::<Vec<_>>(), vec!["x", "y_2"]);
+    assert!(matches!(data_macro.value, CobValue::Enum(_)));
+
+    let CobDefEntry::DataMacro(data_macro) = &defs.entries[2] else { unreachable!() };
+    assert_eq!(data_macro.name.as_str(), "c");
+    assert_eq!(data_macro.params.iter().collect::<Vec<_>>(), vec!["x"]);
+    let CobValue::DataMacro(call) = &data_macro.value else { unreachable!() };
+    assert_eq!(call.path.as_str(), "b");
+    assert_eq!(call.args.entries.len(), 2);
+    let CobValue::Constant(constant) = &call.args.entries[0] else { unreachable!() };
+    assert_eq!(constant.path.as_str(), "x");
+    let CobValue::DataMacro(inner_call) = &call.args.entries[1] else { unreachable!() };
+    assert_eq!(inner_call.path.as_str(), "a");
+    assert_eq!(inner_call.args.entries.len(), 0);
+
+    let CobDefEntry::DataMacro(data_macro) = &defs.entries[3] else { unreachable!() };
+    assert_eq!(data_macro.name.as_str(), "d");
+    let CobValue::Array(array) = &data_macro.value else { unreachable!() };
+    assert_eq!(array.entries.len(), 2);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn data_macro_invocations()
+{
+    let res = test_cob(
+        b"
+#commands
+A(*a())
+B{ x:*b(1 2) y:[*c::d(#FF0000) *e(\"hi\")] }
+",
+    );
+    let CobSection::Commands(commands) = &res.sections[0] else { unreachable!() };
+    assert_eq!(commands.entries.len(), 2);
+
+    let CobCommandEntry::Loadable(loadable) = &commands.entries[0] else { unreachable!() };
+    let CobLoadableVariant::Tuple(tuple) = &loadable.variant else { unreachable!() };
+    let CobValue::DataMacro(call) = &tuple.entries[0] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "a");
+    assert_eq!(call.args.entries.len(), 0);
+
+    let CobCommandEntry::Loadable(loadable) = &commands.entries[1] else { unreachable!() };
+    let CobLoadableVariant::Map(map) = &loadable.variant else { unreachable!() };
+    let CobMapEntry::KeyValue(kv) = &map.entries[0] else { unreachable!() };
+    let CobValue::DataMacro(call) = &kv.value else { unreachable!() };
+    assert_eq!(call.path.as_str(), "b");
+    assert_eq!(call.args.entries.len(), 2);
+    let CobMapEntry::KeyValue(kv) = &map.entries[1] else { unreachable!() };
+    let CobValue::Array(array) = &kv.value else { unreachable!() };
+    let CobValue::DataMacro(call) = &array.entries[0] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "c::d");
+    assert!(matches!(call.args.entries[0], CobValue::Builtin(_)));
+    let CobValue::DataMacro(call) = &array.entries[1] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "e");
+    assert!(matches!(call.args.entries[0], CobValue::String(_)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn data_macros_errors()
+{
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    // Entry not starting with newline
+    test_cob_fail(
+        b"#defs
+ *a() = 10",
+        b"*a() = 10",
+    );
+    // Definition missing parameter list
+    test_cob_fail(
+        b"#defs
+*a = 10
+",
+        b"*a = 10\n",
+    );
+    // Definition contains path segments
+    test_cob_fail(
+        b"#defs
+*a::b() = 10
+",
+        b"*a::b() = 10\n",
+    );
+    // Duplicate parameter
+    test_cob_fail(
+        b"#defs
+*a(x x) = 10
+",
+        b"(x x) = 10\n",
+    );
+    // Whitespace between invocation and arguments
+    test_cob_fail(
+        b"#defs
+$a = *b (10)
+",
+        b"*b (10)\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn data_macro_expansion()
+{
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"other.cob\" as other\n\n#import\nother as o\n\n#defs\n$x = 100\n$y = 200\n\
+            *pair(x y) = P{a:$x b:$y}\n*wrap(x) = *pair($x $y)\n*plain() = $x\n\n\
+            #commands\nA(*pair(1 2))\nB(*wrap(5))\nC(*plain())\nD(*o::double(7))\nE(*o::twice(8))\n",
+        ),
+        ("other.cob", "#defs\n*double(x) = [$x $x]\n*twice(x) = *double($x)\n"),
+    ]);
+    assert!(project.diagnostics().is_empty(), "{:?}", project.diagnostics());
+
+    // - Parameters shadow constants with the same name.
+    // - Nested calls pass parameters through, and constants outside parameters are resolved at definition.
+    // - Imported macros can be called, including macros that call other macros in their own file.
+    assert_eq!(
+        resolved_commands(&project, "main.cob"),
+        "#commands\nA(P{a:1 b:2})\nB(P{a:5 b:200})\nC(100)\nD([7 7])\nE([8 8])\n"
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn data_macro_expansion_errors()
+{
+    // Wrong number of arguments.
+    let project = make_project(&[("main.cob", "#defs\n*pair(x y) = [$x $y]\n\n#commands\nA(*pair(1))\n")]);
+    assert!(project.has_errors());
+    assert!(project
+        .diagnostics()
+        .iter()
+        .any(|d| d.message.contains("expects 2 argument(s) but 1 were provided")));
+
+    // Unknown macro.
+    let project = make_project(&[("main.cob", "#commands\nA(*missing(1))\n")]);
+    assert!(project.has_errors());
+    assert!(project
+        .diagnostics()
+        .iter()
+        .any(|d| d.message.contains("no data macro definition at 'missing'")));
+
+    // Macros that aren't imported can't be called.
+    let project = make_project(&[
+        ("main.cob", "#manifest\n\"other.cob\" as other\n\n#commands\nA(*double(1))\n"),
+        ("other.cob", "#defs\n*double(x) = [$x $x]\n"),
+    ]);
+    assert!(project.has_errors());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,118 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::test_span;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn parse_error_span(raw: &str) -> Span
+{
+    let Err(error) = Cob::parse(test_span(raw)) else { unreachable!() };
+    unwrap_error_content(error)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn diagnostic_locations()
+{
+    // Error in the middle of a line
+    let span = parse_error_span("#scenes\n\"a\"\n    A B\n");
+    let location = CobSourceLocation::from_span(span);
+    assert_eq!(location.line, 3);
+    assert_eq!(location.column, 7);
+    assert_eq!(location.len, 1);
+    assert_eq!(location.source_line, "    A B");
+
+    // Token length
+    let span = parse_error_span("#scenes\n\"a\"\n    A\n    123\n");
+    let location = CobSourceLocation::from_span(span);
+    assert_eq!(location.line, 4);
+    assert_eq!(location.column, 5);
+    assert_eq!(location.len, 3);
+    assert_eq!(location.source_line, "    123");
+
+    // Carriage returns are stripped
+    let span = parse_error_span("#scenes\r\n\"a\" A\r\n");
+    let location = CobSourceLocation::from_span(span);
+    assert_eq!(location.line, 2);
+    assert_eq!(location.column, 5);
+    assert_eq!(location.source_line, "\"a\" A");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn diagnostic_rendering()
+{
+    let span = parse_error_span("#scenes\n\"a\"\n    A B\n");
+    let diagnostic = CobDiagnostic::from_span(CobDiagnosticSeverity::Error, span, "test message");
+    assert!(diagnostic.is_error());
+    assert_eq!(diagnostic.file, "test.cob");
+    assert_eq!(
+        diagnostic.render(),
+        "error: test message\n --> test.cob:3:7\n  |\n3 |     A B\n  |       ^"
+    );
+
+    let diagnostic = CobDiagnostic::warning("test.cob", "test message");
+    assert!(!diagnostic.is_error());
+    assert_eq!(diagnostic.render(), "warning: test message\n  --> test.cob");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn diagnostic_extraction_locations()
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(
+        CobFile::try_new("main.cob").unwrap(),
+        "#defs\n$a = $missing\n\n#commands\nA($b)\n\n#scenes\n\"s\"\n    B($b)\n",
+    );
+    project.load("main.cob");
+    project.resolve();
+
+    let location = |prefix: &str| {
+        let diagnostic = project
+            .diagnostics()
+            .iter()
+            .find(|d| d.message.starts_with(prefix))
+            .unwrap_or_else(|| panic!("missing diagnostic {prefix:?}; {:?}", project.diagnostics()));
+        let location = diagnostic.location.clone().unwrap();
+        (location.line, location.column, location.len)
+    };
+
+    // Def entries are located at the def name.
+    assert_eq!(location("failed extracting constant definition"), (2, 1, 2));
+    // Commands are located at the loadable name.
+    assert_eq!(location("failed resolving command"), (5, 1, 1));
+    // Scenes are located at the scene name.
+    assert_eq!(location("failed resolving scene"), (8, 1, 3));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn closest_match_suggestions()
+{
+    let candidates = ["FlexNode", "BackgroundColor", "BorderColor", "Text"];
+
+    // Typos
+    assert_eq!(closest_match("FlexNod", candidates), Some("FlexNode"));
+    assert_eq!(closest_match("BackgroundColr", candidates), Some("BackgroundColor"));
+    assert_eq!(closest_match("Txt", candidates), Some("Text"));
+
+    // The closest candidate wins.
+    assert_eq!(closest_match("BorderColour", candidates), Some("BorderColor"));
+
+    // Exact matches
+    assert_eq!(closest_match("Text", candidates), Some("Text"));
+
+    // Too far from any candidate
+    assert_eq!(closest_match("Node", candidates), None);
+    assert_eq!(closest_match("Image", candidates), None);
+    assert_eq!(closest_match("", candidates), None);
+    assert_eq!(closest_match("Text", []), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project with one in-memory file, then loads and resolves it.
+fn resolve(content: &str) -> CobProject
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(CobFile::try_new("main.cob").unwrap(), content);
+    project.load("main.cob");
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets a constant's resolved value.
+fn constant(project: &CobProject, name: &str) -> Option<CobValue>
+{
+    let resolver = project.resolver(&CobFile::try_new("main.cob").unwrap())?;
+    match resolver.loadables.constants.get(name)? {
+        CobConstantValue::Value(value) => Some(value.clone()),
+        CobConstantValue::ValueGroup(_) => None,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn val(project: &CobProject, name: &str) -> Option<Val>
+{
+    let CobValue::Builtin(CobBuiltin::Val { val, .. }) = constant(project, name)? else { return None };
+    Some(val)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn color(project: &CobProject, name: &str) -> Option<Srgba>
+{
+    let CobValue::Builtin(CobBuiltin::Color(color)) = constant(project, name)? else { return None };
+    Some(color.color)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn number(project: &CobProject, name: &str) -> Option<CobNumberValue>
+{
+    let CobValue::Number(number) = constant(project, name)? else { return None };
+    Some(number.number)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn expressions_parsing()
+{
+    let res = test_cob(
+        b"#defs
+$a = calc($gap * 2)
+$b = calc( (100% - 2px)/ 2 )
+$c = lighten(#FF0000 0.1)
+$d = mix($a alpha($b 0.5) 0.25)
+
+#commands
+A{ x:calc(1 + 2) }
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+
+    let CobDefEntry::Constant(def) = &defs.entries[0] else { unreachable!() };
+    let CobConstantValue::Value(CobValue::Expr(CobExpr::Calc(calc))) = &def.value else { unreachable!() };
+    assert_eq!(calc.entries.len(), 3);
+    assert!(matches!(calc.entries[0], CobCalcEntry::Operand(CobValue::Constant(_))));
+    assert!(matches!(calc.entries[1], CobCalcEntry::Op { op: CobCalcOp::Mul, .. }));
+
+    let CobDefEntry::Constant(def) = &defs.entries[1] else { unreachable!() };
+    let CobConstantValue::Value(CobValue::Expr(CobExpr::Calc(calc))) = &def.value else { unreachable!() };
+    let CobCalcEntry::Group { entries, .. } = &calc.entries[0] else { unreachable!() };
+    assert_eq!(entries.len(), 3);
+    assert!(matches!(calc.entries[1], CobCalcEntry::Op { op: CobCalcOp::Div, .. }));
+
+    let CobDefEntry::Constant(def) = &defs.entries[3] else { unreachable!() };
+    let CobConstantValue::Value(CobValue::Expr(CobExpr::Function(call))) = &def.value else { unreachable!() };
+    assert_eq!(call.function, CobFunction::Mix);
+    assert!(matches!(
+        &call.args.entries[1],
+        CobValue::Expr(CobExpr::Function(CobFunctionCall { function: CobFunction::Alpha, .. }))
+    ));
+
+    // Invalid expressions.
+    test_cob_fail(b"#defs\n$a = calc(1 2)\n", b"2)\n");
+    test_cob_fail(b"#defs\n$a = calc(1 +)\n", b")\n");
+    test_cob_fail(b"#defs\n$a = calc()\n", b")\n");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn expressions_evaluation()
+{
+    let project = resolve(
+        "#defs
+$gap = 4px
+$double = calc($gap * 2)
+$nested = calc(($gap + 2) * 3 - 1px)
+$scaled = calc(2 * $gap)
+$ratio = calc(10px / 4px)
+$int = calc(6 / 2 - 5)
+$float = calc(7 / 2)
+
+$primary = #FF0000
+$faded = alpha($primary 0.5)
+$gray = mix(#000000 #FFFFFF 0.5)
+$hover = lighten($primary 0.1)
+$pressed = darken($primary calc(0.05 * 2))
+$one = 1
+$dynamic_rgb = rgb($one 0 calc($one - 1))
+$literal_alpha = alpha(rgb(1 0 0) 0.5)
+
+*double(x) = calc($x * 2)
+$from_macro = *double(5px)
+",
+    );
+    assert!(!project.has_errors());
+
+    assert_eq!(val(&project, "double"), Some(Val::Px(8.)));
+    assert_eq!(val(&project, "nested"), Some(Val::Px(17.)));
+    assert_eq!(val(&project, "scaled"), Some(Val::Px(8.)));
+    assert_eq!(number(&project, "ratio"), Some(CobNumberValue::Float64(2.5)));
+    assert_eq!(number(&project, "int"), Some(CobNumberValue::Int(-2)));
+    assert_eq!(number(&project, "float"), Some(CobNumberValue::Float64(3.5)));
+
+    assert_eq!(color(&project, "faded"), Some(Srgba::RED.with_alpha(0.5)));
+    assert_eq!(color(&project, "gray"), Some(Srgba::BLACK.mix(&Srgba::WHITE, 0.5)));
+    assert_eq!(color(&project, "hover"), Some(Srgba::RED.lighter(0.1)));
+    assert_eq!(color(&project, "pressed"), Some(Srgba::RED.darker(0.1)));
+    assert_eq!(color(&project, "dynamic_rgb"), Some(Srgba::RED));
+    assert_eq!(color(&project, "literal_alpha"), Some(Srgba::RED.with_alpha(0.5)));
+
+    assert_eq!(val(&project, "from_macro"), Some(Val::Px(10.)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn expressions_errors()
+{
+    let project = resolve(
+        "#defs
+$mixed_units = calc(10px + 5%)
+$percent_minus_px = calc(100% - 10px)
+$div_zero = calc(1 / 0)
+$color_math = calc(#FF0000 * 2)
+$bad_arg = lighten(4px 0.1)
+$bad_arg_count = mix(#FF0000 #00FF00)
+$string = calc(\"a\" + 1)
+",
+    );
+    assert!(project.has_errors());
+    assert_eq!(project.diagnostics().len(), 7);
+    assert_eq!(
+        project
+            .diagnostics()
+            .iter()
+            .filter(|d| d.message.contains("mixed-unit calc is not supported"))
+            .count(),
+        2
+    );
+    for name in ["mixed_units", "percent_minus_px", "div_zero", "color_math", "bad_arg", "bad_arg_count", "string"]
+    {
+        assert_eq!(constant(&project, name), None);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -94,3 +94,24 @@ fn comments()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn normalize_whitespace()
+{
+    // Unchanged
+    assert_eq!(CobFill::normalize_whitespace(""), "");
+    assert_eq!(CobFill::normalize_whitespace(" "), " ");
+    assert_eq!(CobFill::normalize_whitespace("\n    "), "\n    ");
+    assert_eq!(CobFill::normalize_whitespace(" /*  a  */ "), " /*  a  */ ");
+
+    // Trailing spaces and carriage returns
+    assert_eq!(CobFill::normalize_whitespace("  \r\n  "), "\n  ");
+    assert_eq!(CobFill::normalize_whitespace(" // a  \n"), " // a\n");
+
+    // Repeated spaces and newlines
+    assert_eq!(CobFill::normalize_whitespace("   "), " ");
+    assert_eq!(CobFill::normalize_whitespace("\n\n\n\n  "), "\n\n  ");
+    assert_eq!(CobFill::normalize_whitespace(",   "), ", ");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,138 @@
+use bevy_cobweb_ui::prelude::cob::*;
+
+use super::helpers::test_span;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn test_format(formatter: &CobFormatter, raw: &str, expected: &str)
+{
+    let Ok(cob) = Cob::parse(test_span(raw)) else { panic!("failed parsing input {raw:?}") };
+    let formatted = formatter.format_to_string(&cob);
+    assert_eq!(formatted, expected);
+
+    // Formatting should be idempotent.
+    let Ok(cob) = Cob::parse(test_span(&formatted)) else { panic!("failed parsing output {formatted:?}") };
+    assert_eq!(formatter.format_to_string(&cob), expected);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn format_sections()
+{
+    let formatter = CobFormatter::new();
+
+    test_format(
+        &formatter,
+        "#import\n// first\nb.c as c\na    as _\n\n\n\n#defs\n$x   =  10\n$g = \\ 1  2 \\\n+m = \\\n  A\n\\\n\
+        #scenes\n\"root\"\n  Node{ a:1,  b: 2 }\n  @m{\n   B\n  }\n      // comment\n\n\n  \"child\"\n    \
+        Text(\"hi\")\n\n// end\n\n",
+        "#import\na as _\n// first\nb.c as c\n\n#defs\n$x = 10\n$g = \\ 1 2 \\\n+m = \\\n    A\n\\\n\n\
+        #scenes\n\"root\"\n    Node{a:1 b:2}\n    @m{\n        B\n    }\n    // comment\n\n    \"child\"\n        \
+        Text(\"hi\")\n\n// end\n",
+    );
+
+    // Unsorted imports.
+    test_format(
+        &CobFormatter::new().sort_imports(false),
+        "#import\nb as b\na as a\n",
+        "#import\nb as b\na as a\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn format_values()
+{
+    // Inline values.
+    test_format(
+        &CobFormatter::new(),
+        "#commands\nA( 1,2 /* two */ ,  [ #FF0000  10px ] )\n",
+        "#commands\nA(1 2 /* two */ [#FF0000 10px])\n",
+    );
+
+    // Map spacing.
+    test_format(
+        &CobFormatter::new()
+            .map_colon_space(true)
+            .map_brace_padding(true),
+        "#commands\nA{a:1 b:{}}\n",
+        "#commands\nA{ a: 1 b: {} }\n",
+    );
+
+    // Multi-line values.
+    test_format(
+        &CobFormatter::new().indent_width(2).map_colon_space(true),
+        "#commands\nA{\n      a:1\n  b:[1\n     2]\n}\n",
+        "#commands\nA{\n  a: 1\n  b: [1\n    2]\n}\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn format_conditions()
+{
+    test_format(
+        &CobFormatter::new(),
+        "#defs\n$a = 1\n#[if( any(a,  not( b )) )]\n\n#commands\n#[if(a)]\n\nA\n#scenes\n\"root\"\n #[if(b)]\n\n \
+        \"child\"\n",
+        "#defs\n$a = 1\n\n#[if(any(a not(b)))]\n#commands\n#[if(a)]\nA\n\n#scenes\n\"root\"\n    #[if(b)]\n    \
+        \"child\"\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn format_expressions()
+{
+    test_format(
+        &CobFormatter::new(),
+        "#defs\n$a = calc( $b*2+( 1px -$c ) )\n$d = mix( #FF0000   $e 0.5)\n",
+        "#defs\n$a = calc($b * 2 + (1px - $c))\n$d = mix(#FF0000 $e 0.5)\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn format_line_comments()
+{
+    // Line comments between macro params keep their line break.
+    test_format(
+        &CobFormatter::new(),
+        "#defs\n*m(\n  a // first\n  b\n) = $a\n",
+        "#defs\n*m(a // first\n  b) = $a\n",
+    );
+    let Ok(cob) = Cob::parse(test_span("#defs\n*m(\n  a // first\n  b\n) = $a\n")) else { unreachable!() };
+    let formatted = CobFormatter::new().format_to_string(&cob);
+    let Ok(cob) = Cob::parse(test_span(&formatted)) else { unreachable!() };
+    let CobSection::Defs(defs) = &cob.sections[0] else { unreachable!() };
+    let CobDefEntry::DataMacro(data_macro) = &defs.entries[0] else { unreachable!() };
+    assert_eq!(data_macro.params.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+
+    // Line comments in macro param end fills and before `=`.
+    test_format(
+        &CobFormatter::new(),
+        "#defs\n*m(a // end\n) // eq\n= $a\n+n(a // end\n) = \\\n    A\n\\\n",
+        "#defs\n*m(a // end\n) // eq\n= $a\n+n(a // end\n) = \\\n    A\n\\\n",
+    );
+
+    // Line comments before map colons.
+    test_format(
+        &CobFormatter::new(),
+        "#commands\nA{a // key\n  :1 b:2}\n",
+        "#commands\nA{a // key\n  :1 b:2}\n",
+    );
+
+    // Trailing line comments in multi-line maps.
+    test_format(
+        &CobFormatter::new(),
+        "#commands\nA{\n  a:1 // first\n  b:2 // second\n}\n",
+        "#commands\nA{\n    a:1 // first\n    b:2 // second\n}\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -116,3 +116,70 @@ a.b as a:::b",
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn import_selection()
+{
+    let res = test_cob(
+        b"#import
+a::{b} as _
+a.b::{c d::e, F} as a
+a::{ /* Comment */ b } as _
+",
+    );
+    let CobSection::Import(import) = &res.sections[0] else { unreachable!() };
+    assert_eq!(import.entries.len(), 3);
+    assert_eq!(import.entries[1].key, ManifestKey(Arc::from("a.b")));
+    let selection = import.entries[1].selection.as_ref().unwrap();
+    assert_eq!(&*selection.names(), &[SmolStr::from("c"), SmolStr::from("d::e"), SmolStr::from("F")]);
+    assert_eq!(import.entries[2].selection.as_ref().unwrap().names.len(), 1);
+
+    // Empty selection
+    test_cob_fail(
+        b"#import
+a::{} as _",
+        b"::{} as _",
+    );
+    // Selection with a def symbol
+    test_cob_fail(
+        b"#import
+a::{b $c} as _",
+        b"$c} as _",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn export_section()
+{
+    let res = test_cob(
+        b"#export
+a
+b::C
+
+#defs
+$a = 1
+",
+    );
+    let CobSection::Export(export) = &res.sections[0] else { unreachable!() };
+    assert_eq!(export.entries.len(), 2);
+    assert_eq!(export.entries[0].path, SmolStr::from("a"));
+    assert_eq!(export.entries[1].path, SmolStr::from("b::C"));
+
+    // Entry not starting with newline
+    test_cob_fail(
+        b"#export
+ a",
+        b"a",
+    );
+    // Non-entry in section
+    test_cob_fail(
+        b"#export
+a
+$b",
+        b"$b",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,171 @@
+use bevy_cobweb_ui::prelude::cob::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn defs_section_loadable_macros()
+{
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    let res = test_cob(
+        b"#defs
+@a = \\\\
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+    assert_eq!(defs.entries.len(), 1);
+    let CobDefEntry::LoadableMacro(loadable_macro) = &defs.entries[0] else { unreachable!() };
+    assert_eq!(loadable_macro.name.as_str(), "a");
+    assert_eq!(loadable_macro.value.entries.len(), 0);
+
+    let res = test_cob(
+        b"
+#defs
+@a = \\
+    A
+    B(10)
+\\
+@b = \\
+    @a{}
+    C{x:1}
+\\
+@c = \\
+    @x::b{
+        A(1)
+        -B
+        ^C
+    }
+\\
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+    assert_eq!(defs.entries.len(), 3);
+
+    let CobDefEntry::LoadableMacro(loadable_macro) = &defs.entries[0] else { unreachable!() };
+    assert_eq!(loadable_macro.name.as_str(), "a");
+    assert_eq!(loadable_macro.value.entries.len(), 2);
+    let CobSceneLayerEntry::Loadable(loadable) = &loadable_macro.value.entries[0] else { unreachable!() };
+    assert_eq!(loadable.id.to_canonical(None), "A");
+    let CobSceneLayerEntry::Loadable(loadable) = &loadable_macro.value.entries[1] else { unreachable!() };
+    assert_eq!(loadable.id.to_canonical(None), "B");
+
+    let CobDefEntry::LoadableMacro(loadable_macro) = &defs.entries[1] else { unreachable!() };
+    assert_eq!(loadable_macro.name.as_str(), "b");
+    let CobSceneLayerEntry::LoadableMacroCall(call) = &loadable_macro.value.entries[0] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "a");
+    assert_eq!(call.container.entries.len(), 0);
+    let CobSceneLayerEntry::Loadable(loadable) = &loadable_macro.value.entries[1] else { unreachable!() };
+    assert_eq!(loadable.id.to_canonical(None), "C");
+
+    let CobDefEntry::LoadableMacro(loadable_macro) = &defs.entries[2] else { unreachable!() };
+    assert_eq!(loadable_macro.name.as_str(), "c");
+    let CobSceneLayerEntry::LoadableMacroCall(call) = &loadable_macro.value.entries[0] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "x::b");
+    assert_eq!(call.container.entries.len(), 3);
+    let CobSceneLayerEntry::Loadable(loadable) = &call.container.entries[0] else { unreachable!() };
+    assert_eq!(loadable.id.to_canonical(None), "A");
+    let CobSceneLayerEntry::SceneMacroCommand(command) = &call.container.entries[1] else { unreachable!() };
+    assert_eq!(command.command_type, CobSceneMacroCommandType::Remove);
+    let CobSceneLayerEntry::SceneMacroCommand(command) = &call.container.entries[2] else { unreachable!() };
+    assert_eq!(command.command_type, CobSceneMacroCommandType::MoveToTop);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scenes_loadable_macros()
+{
+    let res = test_cob(
+        b"
+#scenes
+\"a\"
+    @b{}
+    D
+    \"c\"
+        @b{
+            D
+        }
+",
+    );
+    let CobSection::Scenes(scenes) = &res.sections[0] else { unreachable!() };
+    let layer = &scenes.scenes[0];
+    assert_eq!(layer.entries.len(), 3);
+    let CobSceneLayerEntry::LoadableMacroCall(call) = &layer.entries[0] else { unreachable!() };
+    assert_eq!(call.path.as_str(), "b");
+    let CobSceneLayerEntry::Loadable(loadable) = &layer.entries[1] else { unreachable!() };
+    assert_eq!(loadable.id.to_canonical(None), "D");
+    let CobSceneLayerEntry::Layer(layer) = &layer.entries[2] else { unreachable!() };
+    let CobSceneLayerEntry::LoadableMacroCall(call) = &layer.entries[0] else { unreachable!() };
+    assert_eq!(call.container.entries.len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn loadable_macros_errors()
+{
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    // Entry not starting with newline
+    test_cob_fail(
+        b"#defs
+ @a = \\\\",
+        b"@a = \\\\",
+    );
+    // Definition does not start with letter/number
+    test_cob_fail(
+        b"#defs
+@_a = \\\\
+",
+        b"@_a = \\\\\n",
+    );
+    // Definition contains path segments
+    test_cob_fail(
+        b"#defs
+@a::b = \\\\
+",
+        b"::b = \\\\\n",
+    );
+    // Definition contains a scene node
+    test_cob_fail(
+        b"#defs
+@a = \\
+    A
+    \"b\"
+\\
+",
+        b"\\\n    A\n    \"b\"\n\\\n",
+    );
+    // Invocation has whitespace before the container
+    test_cob_fail(
+        b"#scenes
+\"a\"
+    @b {}
+",
+        b"{}\n",
+    );
+    // Invocation contains a scene node
+    test_cob_fail(
+        b"#scenes
+\"a\"
+    @b{
+        \"c\"
+    }
+",
+        b"{\n        \"c\"\n    }\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
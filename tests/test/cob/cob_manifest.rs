@@ -46,6 +46,23 @@ self as a.b
     assert_eq!(manifest.entries[0].key, ManifestKey(Arc::from("a.b")));
     assert_eq!(manifest.entries[1].file, CobManifestFile::File(CobFile::try_new("path/to/b.cob").unwrap()));
     assert_eq!(manifest.entries[1].key, ManifestKey(Arc::from("a.b.c")));
+    assert!(!manifest.entries[1].is_lazy());
+
+    let res = test_cob(
+        b"#manifest
+self as a
+lazy \"levels/forest.cob\" as levels.forest
+lazy  \"b.cob\"  as  b
+",
+    );
+    let CobSection::Manifest(manifest) = &res.sections[0] else { unreachable!() };
+    assert_eq!(manifest.entries.len(), 3);
+    assert!(!manifest.entries[0].is_lazy());
+    assert!(manifest.entries[1].is_lazy());
+    assert_eq!(manifest.entries[1].file, CobManifestFile::File(CobFile::try_new("levels/forest.cob").unwrap()));
+    assert_eq!(manifest.entries[1].key, ManifestKey(Arc::from("levels.forest")));
+    assert!(manifest.entries[2].is_lazy());
+    assert_eq!(manifest.entries[2].key, ManifestKey(Arc::from("b")));
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -115,6 +132,24 @@ self as .a",
 self as a..b",
         b"..b",
     );
+    // Lazy self reference
+    test_cob_fail(
+        b"#manifest
+lazy self as a",
+        b"self as a",
+    );
+    // No fill after 'lazy'
+    test_cob_fail(
+        b"#manifest
+lazy\"a.cob\" as a",
+        b"\"a.cob\" as a",
+    );
+    // No file after 'lazy'
+    test_cob_fail(
+        b"#manifest
+lazy as a",
+        b"as a",
+    );
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,141 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project with one in-memory file, then loads and resolves it.
+fn resolve(content: &str) -> CobProject
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(CobFile::try_new("main.cob").unwrap(), content);
+    project.load("main.cob");
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn patches_parsing()
+{
+    let res = test_cob(
+        b"#patches
+base.menu::main_menu::footer
+    -\"quit_button\"
+    ^\"mods_button\"
+    \"mods_button\"
+        A
+base.hud::hud
+    // Comment
+    #[if(a)]
+    B
+
+#scenes
+\"a\"
+",
+    );
+    assert_eq!(res.sections.len(), 2);
+    let CobSection::Patches(patches) = &res.sections[0] else { unreachable!() };
+    assert_eq!(patches.patches.len(), 2);
+
+    let patch = &patches.patches[0];
+    assert_eq!(patch.file.as_str(), "base.menu");
+    assert_eq!(patch.path.as_str(), "main_menu::footer");
+    assert_eq!(patch.entries.len(), 3);
+    let CobSceneLayerEntry::SceneMacroCommand(command) = &patch.entries[0] else { unreachable!() };
+    assert_eq!(command.command_type, CobSceneMacroCommandType::Remove);
+    assert_eq!(command.node.as_ref().unwrap().as_str(), "quit_button");
+    let CobSceneLayerEntry::SceneMacroCommand(command) = &patch.entries[1] else { unreachable!() };
+    assert_eq!(command.command_type, CobSceneMacroCommandType::MoveToTop);
+    assert_eq!(command.node.as_ref().unwrap().as_str(), "mods_button");
+    let CobSceneLayerEntry::Layer(layer) = &patch.entries[2] else { unreachable!() };
+    assert_eq!(layer.name.as_str(), "mods_button");
+
+    let patch = &patches.patches[1];
+    assert_eq!(patch.scene_ref().path, ScenePath::new("hud"));
+    assert_eq!(patch.entries.len(), 2);
+
+    // Empty section
+    test_cob(b"#patches\n");
+    // Missing scene path
+    test_cob_fail(b"#patches\nbase.menu\n    A\n", b"\n    A\n");
+    // Indented patch target
+    test_cob_fail(b"#patches\n  base.menu::a\n", b"base.menu::a\n");
+    // Items on the same line as the target
+    test_cob_fail(b"#patches\nbase.menu::a A\n", b"A\n");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn patches_resolution()
+{
+    let project = resolve(
+        "#defs
+$label = \"Mods\"
++button = \\
+    Button
+    \"text\"
+\\
+
+#patches
+base.menu::main_menu::footer
+    \"mods_button\"
+        +button{
+            \"text\"
+                A($label)
+        }
+",
+    );
+    assert!(!project.has_errors());
+
+    let file = CobFile::try_new("main.cob").unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Patches(patches) = resolved.sections.last().unwrap() else { unreachable!() };
+    let CobSceneLayerEntry::Layer(button) = &patches.patches[0].entries[0] else { unreachable!() };
+    assert_eq!(button.entries.len(), 2);
+    let CobSceneLayerEntry::Layer(text) = &button.entries[1] else { unreachable!() };
+    let CobSceneLayerEntry::Loadable(loadable) = &text.entries[0] else { unreachable!() };
+    assert!(matches!(&loadable.variant, CobLoadableVariant::Tuple(_)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_macro_node_commands()
+{
+    let project = resolve(
+        "#defs
++row = \\
+    \"a\"
+    \"b\"
+    \"c\"
+\\
+
+#scenes
+\"s\"
+    +row{
+        -\"a\"
+        ^\"c\"
+    }
+",
+    );
+    assert!(!project.has_errors());
+
+    let file = CobFile::try_new("main.cob").unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    let names: Vec<_> = scenes.scenes[0]
+        .entries
+        .iter()
+        .filter_map(|entry| match entry {
+            CobSceneLayerEntry::Layer(layer) => Some(layer.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["c", "b"]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,193 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project from in-memory files, then loads and resolves the first file.
+fn make_project(files: &[(&str, &str)]) -> CobProject
+{
+    let mut project = CobProject::new("test_assets");
+    for (file, content) in files.iter() {
+        project.override_file(CobFile::try_new(file).unwrap(), *content);
+    }
+    project.load(files[0].0);
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_imports()
+{
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"other.cob\" as other\n\n#import\nother as o\n\n#defs\n$a = 1\n",
+        ),
+        ("other.cob", "#defs\n$b = 2\n"),
+    ]);
+    assert!(project.diagnostics().is_empty());
+    assert_eq!(
+        project.get_manifest_file(&ManifestKey::new("other")),
+        Some(&CobFile::try_new("other.cob").unwrap())
+    );
+
+    let resolver = project
+        .resolver(&CobFile::try_new("main.cob").unwrap())
+        .unwrap();
+    assert!(resolver.loadables.constants.get("a").is_some());
+    assert!(resolver.loadables.constants.get("o::b").is_some());
+    assert!(resolver.loadables.constants.get("b").is_none());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_errors()
+{
+    // Unknown import.
+    let project = make_project(&[("main.cob", "#import\nmissing as m\n")]);
+    assert!(project.has_errors());
+
+    // Syntax error.
+    let project = make_project(&[("main.cob", "#commands\nA\nB C\n")]);
+    assert!(project.has_errors());
+    assert_eq!(project.diagnostics()[0].location.as_ref().unwrap().line, 3);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_selective_imports()
+{
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"colors.cob\" as colors\n\n#import\ncolors::{warm, BLUE} as c\n",
+        ),
+        (
+            "colors.cob",
+            "#manifest\n\"warm.cob\" as warm\n\n#import\nwarm as warm\n\n#defs\n$BLUE = 1\n$GREEN = 2\n",
+        ),
+        ("warm.cob", "#defs\n$RED = 3\n*shade(x) = $x\n"),
+    ]);
+    assert!(project.diagnostics().is_empty());
+
+    let resolver = project
+        .resolver(&CobFile::try_new("main.cob").unwrap())
+        .unwrap();
+    assert!(resolver.loadables.constants.get("c::BLUE").is_some());
+    assert!(resolver.loadables.constants.get("c::warm::RED").is_some());
+    assert!(resolver.loadables.data_macros.get("c::warm::shade").is_some());
+    assert!(resolver.loadables.constants.get("c::GREEN").is_none());
+
+    // Selected names must match an exported definition.
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"other.cob\" as other\n\n#import\nother::{missing} as o\n",
+        ),
+        ("other.cob", "#defs\n$a = 1\n"),
+    ]);
+    assert!(!project.has_errors());
+    assert_eq!(project.diagnostics().len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_exports()
+{
+    let project = make_project(&[
+        ("main.cob", "#manifest\n\"theme.cob\" as theme\n\n#import\ntheme as _\n"),
+        (
+            "theme.cob",
+            "#manifest\n\"palette.cob\" as palette\n\n#import\npalette as p\n\n#export\nprimary\np::RED\n\n\
+            #defs\n$primary = $p::RED\n$helper = 0\n",
+        ),
+        ("palette.cob", "#defs\n$RED = 1\n$BLUE = 2\n"),
+    ]);
+    assert!(project.diagnostics().is_empty());
+
+    let resolver = project
+        .resolver(&CobFile::try_new("main.cob").unwrap())
+        .unwrap();
+    assert!(resolver.loadables.constants.get("primary").is_some());
+    assert!(resolver.loadables.constants.get("p::RED").is_some());
+    assert!(resolver.loadables.constants.get("p::BLUE").is_none());
+    assert!(resolver.loadables.constants.get("helper").is_none());
+
+    // Exports don't affect the exporting file.
+    let resolver = project
+        .resolver(&CobFile::try_new("theme.cob").unwrap())
+        .unwrap();
+    assert!(resolver.loadables.constants.get("helper").is_some());
+    assert!(resolver.loadables.constants.get("p::BLUE").is_some());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_def_collisions()
+{
+    // Two imports provide the same constant.
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"a.cob\" as a\n\"b.cob\" as b\n\n#import\na as _\nb as _\n",
+        ),
+        ("a.cob", "#defs\n$x = 1\n"),
+        ("b.cob", "#defs\n$x = 2\n"),
+    ]);
+    assert!(project.has_errors());
+
+    // A local definition collides with an import.
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"a.cob\" as a\n\n#import\na as _\n\n#defs\n$x = 3\n",
+        ),
+        ("a.cob", "#defs\n$x = 1\n"),
+    ]);
+    assert!(project.has_errors());
+
+    // Importing the same definitions through different files is not a collision.
+    let project = make_project(&[
+        (
+            "main.cob",
+            "#manifest\n\"a.cob\" as a\n\"b.cob\" as b\n\n#import\na as _\nb as _\n",
+        ),
+        ("a.cob", "#defs\n$x = 1\n"),
+        ("b.cob", "#import\na as _\n\n#defs\n$y = 3\n"),
+    ]);
+    assert!(project.diagnostics().is_empty());
+
+    let resolver = project
+        .resolver(&CobFile::try_new("main.cob").unwrap())
+        .unwrap();
+    assert!(resolver.loadables.constants.get("x").is_some());
+    assert!(resolver.loadables.constants.get("y").is_some());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn project_assets()
+{
+    let project = make_project(&[(
+        "main.cob",
+        "#defs\n$sounds = [\"click.ogg\"]\n\n#assets\nimages [\"a.png\" {image: \"b.png\"}]\naudio $sounds\n",
+    )]);
+    assert!(project.diagnostics().is_empty());
+
+    // Assets must be arrays.
+    let project = make_project(&[("main.cob", "#assets\nimages \"a.png\"\n")]);
+    assert!(project.has_errors());
+
+    // Font families can't be names.
+    let project = make_project(&[("main.cob", "#assets\nfont_families [\"Fira Sans\"]\n")]);
+    assert!(project.has_errors());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,139 @@
+use bevy_cobweb_ui::prelude::cob::*;
+
+use super::helpers::test_span;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn parse_with_recovery(raw: &str) -> (Cob, Vec<CobDiagnostic>)
+{
+    let Ok(result) = Cob::parse_with_recovery(test_span(raw)) else { unreachable!() };
+    result
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn get_scenes(cob: &Cob) -> Vec<&CobSceneLayer>
+{
+    cob.sections
+        .iter()
+        .filter_map(|s| match s {
+            CobSection::Scenes(scenes) => Some(scenes.scenes.iter()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn error_lines(diagnostics: &[CobDiagnostic]) -> Vec<u32>
+{
+    diagnostics
+        .iter()
+        .map(|d| d.location.as_ref().unwrap().line)
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn recovery_no_errors()
+{
+    let (cob, diagnostics) = parse_with_recovery("#scenes\n\"a\"\n    A\n");
+    assert!(diagnostics.is_empty());
+    assert_eq!(get_scenes(&cob).len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn recovery_at_sections()
+{
+    // Broken defs section
+    let (cob, diagnostics) = parse_with_recovery(
+        "#defs
+$a =
+
+#scenes
+\"a\"
+    A
+",
+    );
+    assert_eq!(error_lines(&diagnostics), vec![4]);
+    assert_eq!(cob.sections.len(), 1);
+    assert_eq!(get_scenes(&cob).len(), 1);
+
+    // Unexpected content between sections
+    let (cob, diagnostics) = parse_with_recovery(
+        "#commands
+A
+B C
+D
+
+#scenes
+\"a\"
+    A
+",
+    );
+    assert_eq!(error_lines(&diagnostics), vec![3]);
+    assert_eq!(cob.sections.len(), 1);
+    assert_eq!(get_scenes(&cob).len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn recovery_at_scene_nodes()
+{
+    // Multiple errors in different scenes
+    let (cob, diagnostics) = parse_with_recovery(
+        "#scenes
+\"a\"
+    A
+    1
+    B
+\"b\"
+    C{
+        x:10
+    }
+    D E
+    F
+",
+    );
+    assert_eq!(error_lines(&diagnostics), vec![4, 10]);
+    let scenes = get_scenes(&cob);
+    assert_eq!(scenes.len(), 2);
+    assert_eq!(scenes[0].entries.len(), 2);
+    assert_eq!(scenes[1].entries.len(), 3);
+
+    // Invalid scene root
+    let (cob, diagnostics) = parse_with_recovery(
+        "#scenes
+\"a-b\"
+    A
+\"c\"
+    B
+",
+    );
+    assert_eq!(error_lines(&diagnostics), vec![2]);
+    let scenes = get_scenes(&cob);
+    assert_eq!(scenes.len(), 1);
+    assert_eq!(scenes[0].name.as_str(), "c");
+
+    // Invalid nested scene node
+    let (cob, diagnostics) = parse_with_recovery(
+        "#scenes
+\"a\"
+    \"b-c\"
+        A
+    \"d\"
+        B
+",
+    );
+    assert_eq!(error_lines(&diagnostics), vec![3]);
+    let scenes = get_scenes(&cob);
+    assert_eq!(scenes.len(), 1);
+    assert_eq!(scenes[0].entries.len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
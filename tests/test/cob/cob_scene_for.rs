@@ -0,0 +1,243 @@
+use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::{test_cob, test_cob_fail};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a project with one in-memory file, then loads and resolves it.
+fn resolve(content: &str) -> CobProject
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(CobFile::try_new("main.cob").unwrap(), content);
+    project.load("main.cob");
+    project.resolve();
+    project
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the first resolved scene.
+fn scene(project: &CobProject) -> &CobSceneLayer
+{
+    let file = CobFile::try_new("main.cob").unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    &scenes.scenes[0]
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn layers(layer: &CobSceneLayer) -> Vec<&CobSceneLayer>
+{
+    layer
+        .entries
+        .iter()
+        .filter_map(|entry| match entry {
+            CobSceneLayerEntry::Layer(layer) => Some(layer),
+            _ => None,
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes the arguments of the first loadable in a layer.
+fn loadable_args(layer: &CobSceneLayer) -> String
+{
+    let Some(CobSceneLayerEntry::Loadable(loadable)) = layer.entries.first() else { unreachable!() };
+    let mut buff = Vec::<u8>::default();
+    let mut serializer = DefaultRawSerializer::new(&mut buff);
+    loadable.variant.write_to(&mut serializer).unwrap();
+    String::from_utf8(buff).unwrap()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_for_parsing()
+{
+    let res = test_cob(
+        b"#scenes
+\"menu\"
+    \"header\"
+    #for row in $rows
+        \"row\"
+            A($row::label)
+    #for  n  in [1 2]
+        // Comment
+        #[if(a)]
+        \"n\"
+        +m{}
+",
+    );
+    let CobSection::Scenes(scenes) = &res.sections[0] else { unreachable!() };
+    let entries = &scenes.scenes[0].entries;
+    assert_eq!(entries.len(), 3);
+
+    let CobSceneLayerEntry::For(for_entry) = &entries[1] else { unreachable!() };
+    assert_eq!(for_entry.binding.as_str(), "row");
+    assert!(matches!(for_entry.iterable, CobValue::Constant(_)));
+    assert_eq!(for_entry.entries.len(), 1);
+
+    let CobSceneLayerEntry::For(for_entry) = &entries[2] else { unreachable!() };
+    assert_eq!(for_entry.binding.as_str(), "n");
+    assert!(matches!(for_entry.iterable, CobValue::Array(_)));
+    assert_eq!(for_entry.entries.len(), 3);
+
+    // Missing `in`
+    test_cob_fail(b"#scenes\n\"a\"\n    #for row $rows\n", b"$rows\n");
+    // Missing binding
+    test_cob_fail(b"#scenes\n\"a\"\n    #for $rows\n", b"$rows\n");
+    // Loadables directly in the loop
+    test_cob_fail(
+        b"#scenes\n\"a\"\n    #for row in $rows\n        A\n",
+        b"#for row in $rows\n        A\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_for_expansion()
+{
+    let project = resolve(
+        "#defs
+$rows = [
+    {label:\"Volume\" key:\"volume\"}
+    {label:\"Music\" key:\"music\"}
+]
++item(label) = \\
+    A($label)
+\\
+
+#scenes
+\"menu\"
+    \"header\"
+    #for row in $rows
+        \"row\"
+            +item(label=$row::label){}
+            #for n in [1 2]
+                \"n\"
+                    B($row::key $n)
+    #for n in [1 2 3]
+        \"\"
+",
+    );
+    assert!(!project.has_errors());
+
+    let scene = scene(&project);
+    let names: Vec<_> = layers(scene).iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["header", "row_0", "row_1", "", "", ""]);
+
+    let rows = layers(scene);
+    assert_eq!(loadable_args(rows[1]), "(\"Volume\")");
+    assert_eq!(loadable_args(rows[2]), "(\"Music\")");
+
+    // Nested loops can use outer bindings.
+    let nested = layers(rows[2]);
+    let names: Vec<_> = nested.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["n_0", "n_1"]);
+    assert_eq!(loadable_args(nested[1]), "(\"music\" 2)");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_for_errors()
+{
+    let project = resolve(
+        "#defs
+$not_array = 1
+
+#scenes
+\"a\"
+    #for x in $not_array
+        \"x\"
+\"b\"
+    #for x in [1]
+        \"x\"
+            A($x::field)
+",
+    );
+    assert!(project.has_errors());
+    assert_eq!(project.diagnostics().len(), 2);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_for_name_collisions()
+{
+    // Collision with a sibling node
+    let project = resolve(
+        "#scenes
+\"a\"
+    \"row_1\"
+    #for x in [1 2]
+        \"row\"
+",
+    );
+    assert!(project.has_errors());
+    assert!(project.diagnostics()[0]
+        .message
+        .contains("scene node \"row_1\" generated by a #for loop has the same name as another node"));
+
+    // Collision with another loop
+    let project = resolve(
+        "#scenes
+\"a\"
+    #for x in [1]
+        \"row\"
+    #for y in [2]
+        \"row\"
+",
+    );
+    assert!(project.has_errors());
+    assert!(project.diagnostics()[0]
+        .message
+        .contains("scene node \"row_0\" generated by a #for loop has the same name as another node"));
+
+    // Loops with different node names don't collide.
+    let project = resolve(
+        "#scenes
+\"a\"
+    #for x in [1]
+        \"row\"
+    #for y in [2]
+        \"column\"
+",
+    );
+    assert!(!project.has_errors(), "{:?}", project.diagnostics());
+    let names: Vec<_> = layers(scene(&project))
+        .iter()
+        .map(|layer| layer.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["row_0", "column_0"]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_for_macro_params()
+{
+    let project = resolve(
+        "#defs
++list(items) = \\
+    #for x in $items
+        \"row\"
+\\
+
+#scenes
+\"a\"
+    +list(items=[1 2]){}
+",
+    );
+    assert!(project.has_errors());
+    assert!(project
+        .diagnostics()
+        .iter()
+        .any(|d| d.message.contains("#for x cannot iterate over scene macro parameter $items")));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,4 +1,5 @@
 use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
 
 use super::helpers::{test_cob, test_cob_fail};
 
@@ -146,6 +147,254 @@ fn scene_macros_errors()
 ",
         b"::b = \\\\\n",
     );
+    // Duplicate parameter
+    test_cob_fail(
+        b"#defs
++a(x x) = \\\\
+",
+        b"(x x) = \\\\\n",
+    );
+    // Missing default value
+    test_cob_fail(
+        b"#defs
++a(x=) = \\\\
+",
+        b") = \\\\\n",
+    );
+    // Positional argument
+    test_cob_fail(
+        b"#scenes
+\"a\"
+    +b(10){}
+",
+        b"10){}\n",
+    );
+    // Unnamed argument
+    test_cob_fail(
+        b"#scenes
+\"a\"
+    +b(x){}
+",
+        b"x){}\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_macro_params()
+{
+    let res = test_cob(
+        b"#defs
++a(label accent=#444444 size=( 10px 20px )) = \\
+    A($label)
+\\
+
+#scenes
+\"s\"
+    +a(label=\"hi\" size=(1px 2px)){}
+    +a( label=$b ){
+        B
+    }
+",
+    );
+    let CobSection::Defs(defs) = &res.sections[0] else { unreachable!() };
+    let CobDefEntry::SceneMacro(scene_macro) = &defs.entries[0] else { unreachable!() };
+    let params = scene_macro.params.as_ref().unwrap();
+    assert_eq!(params.iter().collect::<Vec<_>>(), vec!["label", "accent", "size"]);
+    assert!(params.entries[0].default.is_none());
+    assert!(matches!(params.entries[1].default, Some(CobValue::Builtin(_))));
+    assert!(matches!(params.entries[2].default, Some(CobValue::Tuple(_))));
+
+    let CobSection::Scenes(scenes) = &res.sections[1] else { unreachable!() };
+    let CobSceneLayerEntry::SceneMacroCall(call) = &scenes.scenes[0].entries[0] else { unreachable!() };
+    let args = call.args.as_ref().unwrap();
+    assert_eq!(args.entries.len(), 2);
+    assert_eq!(args.entries[0].as_str(), "label");
+    assert!(matches!(args.entries[0].value, CobValue::String(_)));
+    assert_eq!(args.entries[1].as_str(), "size");
+    let CobSceneLayerEntry::SceneMacroCall(call) = &scenes.scenes[0].entries[1] else { unreachable!() };
+    assert!(matches!(call.args.as_ref().unwrap().entries[0].value, CobValue::Constant(_)));
+    assert_eq!(call.container.entries.len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the first tuple entry of each loadable at the root of a resolved scene.
+fn resolved_scene_args(project: &CobProject, scene: usize) -> Vec<(String, String)>
+{
+    let file = CobFile::try_new("main.cob").unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    scenes.scenes[scene]
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let CobSceneLayerEntry::Loadable(loadable) = entry else { return None };
+            let CobLoadableVariant::Tuple(tuple) = &loadable.variant else { return None };
+            let mut buff = Vec::<u8>::default();
+            let mut serializer = DefaultRawSerializer::new(&mut buff);
+            tuple.entries[0].write_to(&mut serializer).unwrap();
+            let value = String::from_utf8(buff).unwrap();
+            Some((loadable.id.to_canonical(None), String::from(value.trim())))
+        })
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_macro_param_expansion()
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(
+        CobFile::try_new("main.cob").unwrap(),
+        "#defs
+$label = \"constant\"
++inner(value) = \\
+    B($value)
+\\
++button(label accent=1) = \\
+    A($label)
+    C($accent)
+    +inner(value=$accent){}
+\\
+
+#scenes
+\"a\"
+    +button(label=\"Submit\"){}
+\"b\"
+    +button(label=$label accent=2){
+        C(3)
+    }
+",
+    );
+    project.load("main.cob");
+    project.resolve();
+    assert!(!project.has_errors());
+
+    let expected = |label: &str, accent: &str, c: &str| -> Vec<(String, String)> {
+        vec![
+            (String::from("A"), String::from(label)),
+            (String::from("C"), String::from(c)),
+            (String::from("B"), String::from(accent)),
+        ]
+    };
+
+    assert_eq!(resolved_scene_args(&project, 0), expected("\"Submit\"", "1", "1"));
+    // Call content overrides macro content.
+    assert_eq!(resolved_scene_args(&project, 1), expected("\"constant\"", "2", "3"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_macro_param_errors()
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(
+        CobFile::try_new("main.cob").unwrap(),
+        "#defs
++button(label accent=1) = \\
+    A($label)
+\\
+
+#scenes
+\"missing\"
+    +button{}
+\"unknown\"
+    +button(label=1 color=2){}
+\"ok\"
+    +button(label=1){}
+",
+    );
+    project.load("main.cob");
+    project.resolve();
+    assert!(project.has_errors());
+
+    let errors: Vec<_> = project
+        .diagnostics()
+        .iter()
+        .map(|d| d.message.clone())
+        .collect();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("scene macro +button is missing argument \"label\""));
+    assert!(errors[1].contains("scene macro +button has no parameter \"color\""));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes `node::path Loadable(args)` for every loadable in a resolved scene, depth first.
+fn resolved_scene_loadables(project: &CobProject, scene: usize) -> Vec<String>
+{
+    fn collect(path: &str, entries: &[CobSceneLayerEntry], out: &mut Vec<String>)
+    {
+        for entry in entries.iter() {
+            match entry {
+                CobSceneLayerEntry::Loadable(loadable) => {
+                    let mut buff = Vec::<u8>::default();
+                    let mut serializer = DefaultRawSerializer::new(&mut buff);
+                    loadable.write_to(&mut serializer).unwrap();
+                    out.push(format!("{path} {}", String::from_utf8(buff).unwrap().trim()));
+                }
+                CobSceneLayerEntry::Layer(layer) => {
+                    collect(&format!("{path}::{}", layer.name.as_str()), &layer.entries, out);
+                }
+                _ => panic!("unexpected entry in resolved scene: {entry:?}"),
+            }
+        }
+    }
+
+    let file = CobFile::try_new("main.cob").unwrap();
+    let resolved = project.get(&file).unwrap().resolved.as_ref().unwrap();
+    let CobSection::Scenes(scenes) = resolved.sections.last().unwrap() else { unreachable!() };
+    let layer = &scenes.scenes[scene];
+    let mut out = vec![];
+    collect(layer.name.as_str(), &layer.entries, &mut out);
+    out
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_macro_param_substitution()
+{
+    let mut project = CobProject::new("test_assets");
+    project.override_file(
+        CobFile::try_new("main.cob").unwrap(),
+        "#defs
++inner(value) = \\
+    B($value)
+\\
++card(label size) = \\
+    \"header\"
+        \"title\"
+            A($label)
+            +inner(value=$size){}
+    #for i in [1 2]
+        \"row\"
+            C([$label $i])
+\\
+
+#scenes
+\"a\"
+    +card(label=\"x\" size=4){}
+",
+    );
+    project.load("main.cob");
+    project.resolve();
+    assert!(!project.has_errors(), "{:?}", project.diagnostics());
+
+    // Parameters are substituted in nested scene nodes, nested macro calls, and loop content.
+    assert_eq!(
+        resolved_scene_loadables(&project, 0),
+        vec![
+            "a::header::title A(\"x\")",
+            "a::header::title B(4)",
+            "a::row_0 C([\"x\" 1])",
+            "a::row_1 C([\"x\" 2])",
+        ]
+    );
 }
 
 //-------------------------------------------------------------------------------------------------------------------
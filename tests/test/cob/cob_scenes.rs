@@ -1,4 +1,5 @@
 use bevy_cobweb_ui::prelude::cob::*;
+use bevy_cobweb_ui::prelude::*;
 
 use super::helpers::{test_cob, test_cob_fail};
 
@@ -124,3 +125,47 @@ A
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_references()
+{
+    let res = test_cob(
+        b"#scenes
+\"a\" => widgets.card::card
+\"b\"
+    \"slot\" => widgets.card::Card_2
+        A
+    \"\" =>  /* Comment */ card::card
+",
+    );
+    let CobSection::Scenes(scenes) = &res.sections[0] else { unreachable!() };
+    assert_eq!(scenes.scenes.len(), 2);
+    let instance_ref = scenes.scenes[0].instance_ref.as_ref().unwrap();
+    assert_eq!(instance_ref.file.as_str(), "widgets.card");
+    assert_eq!(instance_ref.scene.as_str(), "card");
+    assert_eq!(scenes.scenes[0].entries.len(), 0);
+
+    let CobSceneLayerEntry::Layer(layer) = &scenes.scenes[1].entries[0] else { unreachable!() };
+    assert_eq!(layer.instance_ref.as_ref().unwrap().scene.as_str(), "Card_2");
+    assert_eq!(layer.entries.len(), 1);
+    let scene_ref = layer.instance_ref.as_ref().unwrap().scene_ref();
+    assert_eq!(scene_ref.file, SceneFile::new("widgets.card"));
+    assert_eq!(scene_ref.path, ScenePath::new("Card_2"));
+
+    let CobSceneLayerEntry::Layer(layer) = &scenes.scenes[1].entries[1] else { unreachable!() };
+    assert_eq!(layer.instance_ref.as_ref().unwrap().file.as_str(), "card");
+
+    // Missing manifest key
+    test_cob_fail(b"#scenes\n\"a\" => ::card\n", b"::card\n");
+    // Missing scene
+    test_cob_fail(b"#scenes\n\"a\" => widgets.card\n", b"\n");
+    // Non-root scene
+    test_cob_fail(b"#scenes\n\"a\" => widgets.card::card::title\n", b"::card::title\n");
+    // Reference on a new line
+    test_cob_fail(
+        b"#scenes\n\"a\"\n    => widgets.card::card\n",
+        b"=> widgets.card::card\n",
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,7 +1,9 @@
+mod runtime;
 mod serde_types;
 mod test_fns;
 mod utils;
 
+pub use runtime::*;
 pub use serde_types::*;
 pub use test_fns::*;
 pub use utils::*;
@@ -0,0 +1,105 @@
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::state::state::State;
+use bevy::winit::WinitPlugin;
+use bevy_cobweb_ui::prelude::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Maximum number of updates to wait for loading to finish.
+const MAX_LOAD_UPDATES: usize = 1000;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// COB diagnostics emitted while a runtime app was updated.
+#[derive(Resource, Default)]
+pub struct CollectedDiagnostics(pub Vec<CobDiagnostic>);
+
+fn collect_diagnostics(mut events: EventReader<CobDiagnostic>, mut collected: ResMut<CollectedDiagnostics>)
+{
+    collected.0.extend(events.read().cloned());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a headless app with [`CobwebUiPlugin`] for tests that spawn scenes.
+///
+/// Assets are loaded from `tests/assets`. Call [`load_runtime_app`] after registering COB files.
+pub fn prepare_runtime_app() -> App
+{
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<WinitPlugin>()
+            .disable::<LogPlugin>()
+            .set(RenderPlugin {
+                render_creation: WgpuSettings { backends: None, ..default() }.into(),
+                ..default()
+            })
+            .set(AssetPlugin { file_path: "tests/assets".into(), ..default() }),
+    )
+    .add_plugins(CobwebUiPlugin)
+    .init_resource::<CollectedDiagnostics>()
+    .add_systems(Last, collect_diagnostics);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Finishes building the app, then updates it until [`LoadState::Done`].
+pub fn load_runtime_app(app: &mut App)
+{
+    app.finish();
+    app.cleanup();
+    update_until(app, |world| *world.resource::<State<LoadState>>().get() == LoadState::Done);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Updates the app until `condition` returns `true`.
+///
+/// Panics if the condition isn't met after many updates.
+pub fn update_until(app: &mut App, condition: impl Fn(&mut World) -> bool)
+{
+    for _ in 0..MAX_LOAD_UPDATES {
+        app.update();
+        if (condition)(app.world_mut()) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    panic!("condition not met after {MAX_LOAD_UPDATES} updates");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the scene nodes of a scene instance, sorted by scene path.
+pub fn scene_nodes(world: &mut World, instance: SceneInstanceId) -> Vec<(String, Entity)>
+{
+    let mut nodes: Vec<_> = world
+        .query::<(Entity, &SceneNode)>()
+        .iter(world)
+        .filter(|(_, node)| node.instance() == instance)
+        .map(|(entity, node)| (node.scene_ref().path.iter().collect::<Vec<_>>().join("::"), entity))
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the messages of COB diagnostics emitted so far.
+pub fn diagnostic_messages(world: &World) -> Vec<String>
+{
+    world
+        .resource::<CollectedDiagnostics>()
+        .0
+        .iter()
+        .map(|d| d.message.clone())
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -350,6 +350,69 @@ impl Instruction for BuiltinCollection
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuiltinRect(pub UiRect);
+
+impl Instruction for BuiltinRect
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuiltinRectCollection
+{
+    pub all: UiRect,
+    pub axes: UiRect,
+    pub sides: UiRect,
+}
+
+impl Instruction for BuiltinRectCollection
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A user-defined struct with the same fields as `UiRect`.
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RectLikeStruct
+{
+    pub top: Val,
+    pub right: Val,
+    pub bottom: Val,
+    pub left: Val,
+}
+
+impl Instruction for RectLikeStruct
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A struct with four `Val` fields that don't match the rect shorthand fields.
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FourValStruct
+{
+    pub a: Val,
+    pub b: Val,
+    pub c: Val,
+    pub d: Val,
+}
+
+impl Instruction for FourValStruct
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Component, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReflectDefaulted
 {
@@ -473,6 +536,10 @@ impl Plugin for SerdeTypesPlugin
             .register_instruction_type::<EnumGeneric<SingleGeneric<u32>>>()
             .register_instruction_type::<BuiltinColor>()
             .register_instruction_type::<BuiltinCollection>()
+            .register_instruction_type::<BuiltinRect>()
+            .register_instruction_type::<BuiltinRectCollection>()
+            .register_instruction_type::<RectLikeStruct>()
+            .register_instruction_type::<FourValStruct>()
             .register_instruction_type::<ReflectDefaulted>()
             .register_instruction_type::<ReflectDefaultNewtype>()
             //.register_instruction_type::<ReflectDefaultTupleStruct>()
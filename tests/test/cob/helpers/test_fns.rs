@@ -46,7 +46,7 @@ fn test_equivalence_impl<T: Loadable + Debug + Serialize + for<'de> Deserialize<
         Err(err) => panic!("{command_raw}, ERR={err:?}"),
     };
     let CobSection::Commands(commands) = &mut cob_parsed.sections[0] else { unreachable!() };
-    let CobCommandEntry(cmd_loadable) = &mut commands.entries[0];
+    let CobCommandEntry::Loadable(cmd_loadable) = &mut commands.entries[0] else { unreachable!() };
     cmd_loadable.fill = CobFill::default(); // Clear fill so equality test works.
     assert_eq!(*cmd_loadable, loadable_parsed);
 
@@ -308,3 +308,40 @@ pub fn test_string_conversion(raw: &str, converted: &str, reserialized: &str, nu
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Tests that `value` survives the trip `rust -> Cob -> raw -> Cob -> rust`, and returns the raw COB.
+pub fn test_round_trip<T>(w: &World, value: T) -> String
+where
+    T: Loadable + Debug + Serialize + for<'de> Deserialize<'de>,
+{
+    let type_registry = w.resource::<AppTypeRegistry>().read();
+    let registration = type_registry.get(std::any::TypeId::of::<T>()).unwrap();
+
+    // Rust value to cob raw
+    let loadable_from_rust = CobLoadable::extract(&value, &type_registry).unwrap();
+    let mut buff = Vec::<u8>::default();
+    let mut serializer = DefaultRawSerializer::new(&mut buff);
+    loadable_from_rust.write_to(&mut serializer).unwrap();
+    let raw = String::from_utf8(buff).unwrap();
+
+    // Cob raw to Cob loadable
+    let loadable_parsed = match CobLoadable::try_parse(CobFill::default(), test_span(&raw)) {
+        Ok((Some(loadable_parsed), _, _)) => loadable_parsed,
+        Err(err) => panic!("{raw}, ERR={err:?}"),
+        _ => panic!("{raw}, TRY FAILED"),
+    };
+
+    // Rust value from cob loadable parsed (direct)
+    let direct_value = T::deserialize(&loadable_parsed).unwrap();
+    assert_eq!(value, direct_value, "{raw}");
+
+    // Rust value from cob loadable parsed (reflect)
+    let deserializer = TypedReflectDeserializer::new(registration, &type_registry);
+    let reflected_inst = deserializer.deserialize(&loadable_parsed).unwrap();
+    let extracted_inst = T::from_reflect(reflected_inst.as_partial_reflect()).unwrap();
+    assert_eq!(value, extracted_inst, "{raw}");
+
+    raw
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const LAZY_FILE: &str = "lazy/level.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_lazy_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.load("lazy/main.cob");
+    load_runtime_app(&mut app);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn arena() -> SceneRef
+{
+    SceneRef::from(("level", "arena"))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn lazy_file_loads_on_request()
+{
+    let mut app = prepare_lazy_app();
+
+    // Loading is done without the lazy file.
+    app.update();
+    let scene_loader = app.world().resource::<SceneLoader>();
+    assert!(scene_loader.is_scene_ready(&SceneRef::from(("lazy/main.cob", "hud"))));
+    assert!(!scene_loader.is_scene_ready(&arena()));
+    assert!(scene_loader.unused_lazy_files().is_empty());
+
+    // The file is loaded once a scene from it is requested.
+    let handle = app
+        .world_mut()
+        .resource_mut::<SceneLoader>()
+        .request_scene(arena());
+    assert_eq!(handle.scene(), &arena());
+    update_until(&mut app, |world| handle.is_ready(world.resource::<SceneLoader>()));
+    assert_eq!(
+        app.world().resource::<SceneLoader>().unused_lazy_files(),
+        vec![CobFile::try_new(LAZY_FILE).unwrap()]
+    );
+
+    // Ready scenes are spawned immediately.
+    let root = app.world_mut().load_scene(arena());
+    assert_eq!(app.world().get::<Children>(root).map(|c| c.len()), Some(2));
+    assert!(app
+        .world()
+        .resource::<SceneLoader>()
+        .unused_lazy_files()
+        .is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn pending_lazy_scene_spawns_when_ready()
+{
+    let mut app = prepare_lazy_app();
+
+    // Loading a scene from a lazy file that isn't ready only spawns the root entity.
+    let root = app.world_mut().load_scene(arena());
+    assert!(app.world().get_entity(root).is_ok());
+    assert!(app.world().get::<Children>(root).is_none());
+
+    // The scene is spawned once its file is extracted.
+    update_until(&mut app, |world| world.get::<SceneNode>(root).is_some());
+    let instance = app.world().get::<SceneNode>(root).unwrap().instance();
+    let paths: Vec<_> = scene_nodes(app.world_mut(), instance)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec!["arena", "arena::floor", "arena::wall"]);
+    assert_eq!(
+        app.world().resource::<SceneLoader>().instance_scene(instance),
+        Some(&SceneRef::from((LAZY_FILE, "arena")))
+    );
+    assert!(diagnostic_messages(app.world()).is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
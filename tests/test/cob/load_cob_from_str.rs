@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Default)]
+struct AppliedCommands(Vec<String>);
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+struct RecordCommand(String);
+
+impl Command for RecordCommand
+{
+    fn apply(self, world: &mut World)
+    {
+        world.resource_mut::<AppliedCommands>().0.push(self.0);
+    }
+}
+
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+struct NodeLabel(String);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_str_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.init_resource::<AppliedCommands>()
+        .register_command_type::<RecordCommand>()
+        .register_component_type::<NodeLabel>();
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn applied_commands(app: &App) -> Vec<String>
+{
+    app.world().resource::<AppliedCommands>().0.clone()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn node_label(app: &App, entity: Entity) -> Option<String>
+{
+    app.world().get::<NodeLabel>(entity).map(|l| l.0.clone())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_cob_from_str_extracts_commands_and_scenes()
+{
+    // Files loaded from strings are parsed directly, so the assets directory is never read.
+    let mut app = prepare_str_app();
+    app.load_cob_from_str(
+        "virtual://ui/main.cob",
+        "#manifest\n\"virtual://ui/widgets.cob\" as widgets\n\n\
+        #commands\nRecordCommand(\"main\")\n\n\
+        #scenes\n\"menu\"\n    NodeLabel(\"menu\")\n    \"title\"\n        NodeLabel(\"title\")\n",
+    )
+    .load_cob_from_str(
+        "virtual://ui/widgets.cob",
+        "#commands\nRecordCommand(\"widgets\")\n\n#scenes\n\"button\"\n    NodeLabel(\"button\")\n",
+    );
+    load_runtime_app(&mut app);
+
+    assert!(diagnostic_messages(app.world()).is_empty(), "{:?}", diagnostic_messages(app.world()));
+    // Manifest entries' commands are ordered before the file's own commands.
+    assert_eq!(applied_commands(&app), vec!["widgets", "main"]);
+
+    let menu = app.world_mut().load_scene(("virtual://ui/main.cob", "menu"));
+    let title = app.world().get::<Children>(menu).unwrap()[0];
+    assert_eq!(node_label(&app, menu).as_deref(), Some("menu"));
+    assert_eq!(node_label(&app, title).as_deref(), Some("title"));
+
+    // Manifest keys work for files loaded from strings.
+    let button = app.world_mut().load_scene(("widgets", "button"));
+    assert_eq!(node_label(&app, button).as_deref(), Some("button"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_cob_from_str_command()
+{
+    let mut app = prepare_str_app();
+    app.load_cob_from_str("main.cob", "#commands\nRecordCommand(\"main\")\n")
+        .add_systems(Startup, |mut c: Commands| {
+            c.load_cob_from_str("extra.cob", "#commands\nRecordCommand(\"extra\")\n\n#scenes\n\"extra\"\n");
+            c.load_cob_from_str("invalid", "#commands\nRecordCommand(\"invalid\")\n");
+        });
+    load_runtime_app(&mut app);
+
+    // Files added with the command are loaded after other files.
+    assert_eq!(applied_commands(&app), vec!["main", "extra"]);
+    assert!(app
+        .world()
+        .resource::<SceneLoader>()
+        .is_scene_ready(&SceneRef::from(("extra.cob", "extra"))));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_cob_from_str_parse_errors()
+{
+    let mut app = prepare_str_app();
+    app.load_cob_from_str("broken.cob", "#commands\nRecordCommand(\"a\")\nRecordCommand(\n");
+    load_runtime_app(&mut app);
+
+    // Parse errors are reported as diagnostics for the file's name.
+    let diagnostics = &app.world().resource::<CollectedDiagnostics>().0;
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|d| d.file == "broken.cob"), "{diagnostics:?}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const INCREMENTAL_FILE: &str = "incremental.cob";
+
+/// Root entities that received [`SceneSpawned`].
+#[derive(Resource, Default)]
+struct SpawnedScenes(Vec<Entity>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_incremental_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.init_resource::<SpawnedScenes>().load_cob_from_str(
+        INCREMENTAL_FILE,
+        "#scenes\n\"list\"\n    Visibility::Visible\n    \"a\"\n    \"b\"\n        \"c\"\n    \"d\"\n    \"e\"\n",
+    );
+    load_runtime_app(&mut app);
+    app.world_mut()
+        .resource_mut::<SceneLoader>()
+        .set_spawn_budget(SceneSpawnBudget { max_nodes: 2, max_time: Duration::from_secs(1) });
+    app.world_mut().react(|rc| {
+        rc.on_persistent(
+            any_entity_event::<SceneSpawned>(),
+            |event: EntityEvent<SceneSpawned>, mut spawned: ResMut<SpawnedScenes>| {
+                spawned.0.push(event.entity());
+            },
+        )
+    });
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn load_incremental(app: &mut App) -> Entity
+{
+    let world = app.world_mut();
+    let root = world.resource_scope(|world, mut scene_loader: Mut<SceneLoader>| {
+        let mut c = world.commands();
+        c.load_scene_incremental((INCREMENTAL_FILE, "list"), &mut scene_loader)
+    });
+    world.flush();
+    root
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn num_nodes(app: &mut App, root: Entity) -> usize
+{
+    let instance = app.world().get::<SceneNode>(root).unwrap().instance();
+    scene_nodes(app.world_mut(), instance).len()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn load_progress(app: &App) -> (usize, usize)
+{
+    let scene_loader = app.world().resource::<SceneLoader>();
+    (scene_loader.pending_assets(), scene_loader.total_assets())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_scene_incremental_budget()
+{
+    let mut app = prepare_incremental_app();
+    let root = load_incremental(&mut app);
+    let id = app.world().get::<SceneNode>(root).unwrap().instance();
+
+    // The root is hidden while spawning, and only `max_nodes` nodes are spawned per update.
+    assert_eq!(app.world().get::<Visibility>(root), Some(&Visibility::Hidden));
+    assert_eq!(num_nodes(&mut app, root), 1);
+    assert_eq!(load_progress(&app), (5, 5));
+
+    app.update();
+    assert_eq!(app.world().get::<Visibility>(root), Some(&Visibility::Hidden));
+    assert_eq!(num_nodes(&mut app, root), 3);
+    assert_eq!(load_progress(&app), (3, 5));
+    // Other assets are done loading, so only the scene's nodes are pending.
+    assert_eq!(app.world().resource::<LoadProgress>().loading_progress().0, 3);
+    assert!(app.world().resource::<SceneLoader>().is_spawning(id));
+    assert!(app.world().resource::<SpawnedScenes>().0.is_empty());
+
+    app.update();
+    assert_eq!(num_nodes(&mut app, root), 5);
+    assert_eq!(app.world().resource::<LoadProgress>().loading_progress().0, 1);
+    assert!(app.world().resource::<SpawnedScenes>().0.is_empty());
+
+    // `SceneSpawned` is sent after the last node, and the root's own visibility is restored.
+    app.update();
+    assert_eq!(num_nodes(&mut app, root), 6);
+    assert_eq!(app.world().resource::<SpawnedScenes>().0, vec![root]);
+    assert_eq!(app.world().get::<Visibility>(root), Some(&Visibility::Visible));
+    assert!(!app.world().resource::<SceneLoader>().is_spawning(id));
+    assert_eq!(load_progress(&app), (0, 0));
+
+    let b = app.world().get::<Children>(root).unwrap()[1];
+    assert_eq!(app.world().get::<Children>(b).unwrap().len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_scene_incremental_progress_accumulates()
+{
+    let mut app = prepare_incremental_app();
+    load_incremental(&mut app);
+    app.update();
+    assert_eq!(load_progress(&app), (3, 5));
+
+    // Scenes queued while others are spawning add to the total.
+    load_incremental(&mut app);
+    assert_eq!(load_progress(&app), (8, 10));
+
+    update_until(&mut app, |world| world.resource::<SpawnedScenes>().0.len() == 2);
+    assert_eq!(load_progress(&app), (0, 0));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_scene_incremental_despawned_root()
+{
+    let mut app = prepare_incremental_app();
+    let root = load_incremental(&mut app);
+    let id = app.world().get::<SceneNode>(root).unwrap().instance();
+    app.update();
+
+    // Scenes whose roots are despawned while spawning are discarded.
+    app.world_mut().entity_mut(root).despawn_recursive();
+    app.update();
+    assert!(!app.world().resource::<SceneLoader>().is_spawning(id));
+    assert!(scene_nodes(app.world_mut(), id).is_empty());
+    assert_eq!(load_progress(&app), (0, 0));
+
+    app.update();
+    assert!(app.world().resource::<SpawnedScenes>().0.is_empty());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const WORLD_FILE: &str = "world.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_world_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.load_cob_from_str(
+        WORLD_FILE,
+        "#scenes\n\"panel\"\n    Visibility::Visible\n    \"header\"\n        Visibility::Hidden\n    \"body\"\n",
+    );
+    load_runtime_app(&mut app);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_scene_with_world()
+{
+    let mut app = prepare_world_app();
+
+    // The hierarchy and its loadables are available without updating the app.
+    let root = app.world_mut().load_scene((WORLD_FILE, "panel"));
+    let children = app.world().get::<Children>(root).unwrap().to_vec();
+    assert_eq!(children.len(), 2);
+    assert_eq!(app.world().get::<Visibility>(root), Some(&Visibility::Visible));
+    assert_eq!(app.world().get::<Visibility>(children[0]), Some(&Visibility::Hidden));
+    assert_eq!(app.world().get::<Parent>(root), None);
+
+    let instance = app.world().get::<SceneNode>(root).unwrap().instance();
+    let paths: Vec<_> = scene_nodes(app.world_mut(), instance)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec!["panel", "panel::body", "panel::header"]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_scene_with_entity_world_mut()
+{
+    let mut app = prepare_world_app();
+    let parent = app.world_mut().spawn_empty().id();
+
+    // Edits made in the callback are applied before the call returns.
+    let root = app
+        .world_mut()
+        .entity_mut(parent)
+        .load_scene_and_edit((WORLD_FILE, "panel"), |loaded_scene| {
+            loaded_scene.get("header").insert(Visibility::Inherited);
+        });
+    assert_eq!(app.world().get::<Parent>(root).map(|p| p.get()), Some(parent));
+    let header = app.world().get::<Children>(root).unwrap()[0];
+    assert_eq!(app.world().get::<Visibility>(header), Some(&Visibility::Inherited));
+
+    // Loading a single scene node into an entity applies its loadables immediately.
+    let node = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .entity_mut(node)
+        .load(SceneRef::from((WORLD_FILE, "panel::header")));
+    assert_eq!(app.world().get::<Visibility>(node), Some(&Visibility::Hidden));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::test_cob;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+enum SchemaMode
+{
+    #[default]
+    A,
+    B(u32),
+}
+
+impl Instruction for SchemaMode
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Default)]
+struct SchemaStruct
+{
+    size: f32,
+    mode: SchemaMode,
+}
+
+impl Default for SchemaStruct
+{
+    fn default() -> Self
+    {
+        Self { size: 2.0, mode: SchemaMode::A }
+    }
+}
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+struct SchemaTuple(bool);
+
+impl Instruction for SchemaTuple
+{
+    fn apply(self, _: Entity, _: &mut World) {}
+    fn revert(_: Entity, _: &mut World) {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_schemas() -> LoadableSchemas
+{
+    let mut app = App::new();
+    app.register_type::<SchemaMode>()
+        .register_component_type::<SchemaStruct>()
+        .register_instruction_type::<SchemaTuple>()
+        .register_instruction_type::<SchemaMode>();
+    LoadableSchemas::from_world(app.world())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn schema_from_registry()
+{
+    let schemas = make_schemas();
+    let names: Vec<_> = schemas.loadables.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["SchemaMode", "SchemaStruct", "SchemaTuple"]);
+
+    // Struct with a reflected default.
+    let schema = schemas.get("SchemaStruct").unwrap();
+    assert_eq!(schema.kinds, vec![LoadableKind::Component]);
+    assert_eq!(schema.fields.len(), 2);
+    assert_eq!(schema.fields[0].name, "size");
+    assert_eq!(schema.fields[0].type_path, "f32");
+    assert_eq!(schema.fields[0].default.as_deref(), Some("2"));
+    assert_eq!(schema.fields[1].name, "mode");
+    assert_eq!(schema.fields[1].variants, vec!["A", "B"]);
+    assert_eq!(schema.fields[1].default.as_deref(), Some("A"));
+    assert!(schema.default.is_some());
+
+    // Tuple struct without a reflected default.
+    let schema = schemas.get("SchemaTuple").unwrap();
+    assert_eq!(schema.kinds, vec![LoadableKind::Instruction]);
+    assert_eq!(schema.fields.len(), 1);
+    assert_eq!(schema.fields[0].name, "0");
+    assert_eq!(schema.fields[0].type_path, "bool");
+    assert_eq!(schema.fields[0].default, None);
+    assert_eq!(schema.default, None);
+
+    // Enum.
+    let schema = schemas.get("SchemaMode").unwrap();
+    assert_eq!(schema.variants.len(), 2);
+    assert_eq!(schema.variants[0].name, "A");
+    assert_eq!(schema.variants[0].fields.len(), 0);
+    assert_eq!(schema.variants[1].name, "B");
+    assert_eq!(schema.variants[1].fields[0].type_path, "u32");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn schema_formats()
+{
+    let schemas = make_schemas();
+
+    // JSON round trip.
+    let json = schemas.to_json();
+    assert_eq!(LoadableSchemas::from_json(&json).unwrap(), schemas);
+    assert!(json.contains("\"type\": \"f32\""));
+
+    // COB output is valid COB.
+    let cob = schemas.to_cob();
+    test_cob(cob.as_bytes());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
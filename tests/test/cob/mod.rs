@@ -1,10 +1,31 @@
 pub mod helpers;
 
+mod cob_assets;
 mod cob_commands;
+mod cob_conditions;
 mod cob_constants;
+mod cob_data_macros;
+mod cob_diagnostics;
+mod cob_expressions;
 mod cob_fill;
+mod cob_formatter;
 mod cob_import;
+mod cob_loadable_macros;
 mod cob_manifest;
+mod cob_patches;
+mod cob_project;
+mod cob_recovery;
+mod cob_scene_for;
 mod cob_scene_macros;
 mod cob_scenes;
+mod lazy_files;
+mod load_cob_from_str;
+mod load_scene_incremental;
+mod load_scene_world;
+mod loadable_schema;
+mod scene_codegen;
+mod scene_instances;
+mod scene_references;
 mod serde;
+mod ui_callbacks;
+mod unload_cob_file;
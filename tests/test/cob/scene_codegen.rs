@@ -0,0 +1,70 @@
+use cob_codegen::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_paths_generation()
+{
+    let content = "#defs
++footer = \\
+    \"content\"
+\\
+
+#scenes
+\"game_menu\"
+    FlexNode{
+        \"not_a_node\"
+    }
+    \"header\" // Comment
+        TextLine{text:\"header\"}
+    /* \"commented\" */
+    \"\"
+        \"hidden\"
+    #for row in [1 2]
+        \"row\"
+    \"footer\"
+        +footer{}
+        \"button\" => widgets::button
+
+#commands
+\"not_a_scene\"
+";
+    let code = generate_scene_paths([("ui/main.cob", content)]).unwrap();
+    assert!(code.contains("pub mod ui_main"));
+    assert!(code.contains("pub const FILE: &str = \"ui/main.cob\";"));
+    assert!(code.contains("pub mod game_menu"));
+    assert!(code.contains("pub const SCENE: &str = \"game_menu\";"));
+    assert!(code.contains("pub const HEADER: &str = \"header\";"));
+    assert!(code.contains("pub const FOOTER: &str = \"footer\";"));
+    assert!(code.contains("pub const FOOTER_BUTTON: &str = \"footer::button\";"));
+    assert!(!code.contains("FOOTER_CONTENT"));
+    assert!(!code.contains("NOT_A_NODE"));
+    assert!(!code.contains("COMMENTED"));
+    assert!(!code.contains("HIDDEN"));
+    assert!(!code.contains("ROW"));
+    assert!(!code.contains("not_a_scene"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_paths_collisions()
+{
+    let content = "#scenes
+\"menu\"
+    \"a_b\"
+    \"a\"
+        \"b\"
+";
+    let errors = generate_scene_paths([("main.cob", content)]).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].file, "main.cob");
+
+    // Files without the COB extension.
+    assert!(generate_scene_paths([("main.txt", content)]).is_err());
+
+    // Files with the same module name.
+    assert!(generate_scene_paths([("a_b.cob", ""), ("a/b.cob", "")]).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const INSTANCES_FILE: &str = "instances.cob";
+
+/// Scene events in the order they were sent, with the entity they were sent to.
+#[derive(Resource, Default)]
+struct SceneEvents(Vec<(&'static str, Entity, SceneInstanceId)>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_instances_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.init_resource::<SceneEvents>().load_cob_from_str(
+        INSTANCES_FILE,
+        "#scenes\n\"card\"\n    FlexNode\n    \"header\"\n        \"title\"\n    \"body\"\n\n\
+        \"banner\"\n    FlexNode\n    \"text\"\n",
+    );
+    load_runtime_app(&mut app);
+    app.world_mut().react(|rc| {
+        rc.on_persistent(
+            any_entity_event::<SceneSpawned>(),
+            |event: EntityEvent<SceneSpawned>, mut events: ResMut<SceneEvents>| {
+                let (entity, event) = event.read();
+                events.0.push(("spawned", entity, event.instance));
+            },
+        );
+        rc.on_persistent(
+            any_entity_event::<SceneReloaded>(),
+            |event: EntityEvent<SceneReloaded>, mut events: ResMut<SceneEvents>| {
+                let (entity, event) = event.read();
+                events.0.push(("reloaded", entity, event.instance));
+            },
+        );
+    });
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn instance_of(app: &App, entity: Entity) -> SceneInstanceId
+{
+    app.world().get::<SceneNode>(entity).unwrap().instance()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn take_events(app: &mut App) -> Vec<(&'static str, Entity, SceneInstanceId)>
+{
+    std::mem::take(&mut app.world_mut().resource_mut::<SceneEvents>().0)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn scene_node_instance()
+{
+    let mut app = prepare_instances_app();
+    let first = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    let second = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    let id = instance_of(&app, first);
+    assert_ne!(id, instance_of(&app, second));
+
+    // Every node of an instance, including the root, belongs to the same instance.
+    let nodes = scene_nodes(app.world_mut(), id);
+    let paths: Vec<_> = nodes.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, vec!["card", "card::body", "card::header", "card::header::title"]);
+    assert!(nodes.iter().any(|(_, entity)| *entity == first));
+    assert_eq!(app.world().resource::<SceneLoader>().root_entity(id), Some(first));
+    assert_eq!(
+        app.world().resource::<SceneLoader>().instance_scene(id),
+        Some(&SceneRef::from((INSTANCES_FILE, "card")))
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn reload_scene_instance()
+{
+    let mut app = prepare_instances_app();
+    let parent = app.world_mut().spawn_empty().id();
+    let before = app.world_mut().spawn_empty().id();
+    let old_root = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    let after = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .entity_mut(parent)
+        .add_children(&[before, old_root, after]);
+    let id = instance_of(&app, old_root);
+    take_events(&mut app);
+
+    app.world_mut().commands().reload_scene_instance(id);
+    app.update();
+
+    // The new root keeps the instance id and the old root's position.
+    let new_root = app.world().resource::<SceneLoader>().root_entity(id).unwrap();
+    assert_ne!(new_root, old_root);
+    assert!(app.world().get_entity(old_root).is_err());
+    assert_eq!(instance_of(&app, new_root), id);
+    assert_eq!(&**app.world().get::<Children>(parent).unwrap(), &[before, new_root, after]);
+    assert_eq!(scene_nodes(app.world_mut(), id).len(), 4);
+    assert_eq!(take_events(&mut app), vec![("spawned", new_root, id), ("reloaded", new_root, id)]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn swap_scene_from_inner_node()
+{
+    let mut app = prepare_instances_app();
+    let old_root = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    let id = instance_of(&app, old_root);
+    let (_, title) = scene_nodes(app.world_mut(), id)
+        .into_iter()
+        .find(|(path, _)| path == "card::header::title")
+        .unwrap();
+    take_events(&mut app);
+
+    app.world_mut()
+        .commands()
+        .swap_scene(title, (INSTANCES_FILE, "banner"));
+    app.update();
+
+    let scene_loader = app.world().resource::<SceneLoader>();
+    let new_root = scene_loader.root_entity(id).unwrap();
+    assert_eq!(scene_loader.instance_scene(id), Some(&SceneRef::from((INSTANCES_FILE, "banner"))));
+    assert!(app.world().get_entity(old_root).is_err());
+    assert!(app.world().get_entity(title).is_err());
+    let paths: Vec<_> = scene_nodes(app.world_mut(), id)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec!["banner", "banner::text"]);
+    assert_eq!(take_events(&mut app), vec![("spawned", new_root, id), ("reloaded", new_root, id)]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn swap_scene_failed()
+{
+    let mut app = prepare_instances_app();
+    let parent = app.world_mut().spawn_empty().id();
+    let root = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    app.world_mut().entity_mut(parent).add_child(root);
+    let id = instance_of(&app, root);
+
+    // Swapping to a missing scene keeps the old instance.
+    app.world_mut()
+        .commands()
+        .swap_scene(root, (INSTANCES_FILE, "missing"));
+    app.update();
+
+    assert_eq!(app.world().resource::<SceneLoader>().root_entity(id), Some(root));
+    assert_eq!(&**app.world().get::<Children>(parent).unwrap(), &[root]);
+    assert_eq!(scene_nodes(app.world_mut(), id).len(), 4);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn despawn_scene_instance()
+{
+    let mut app = prepare_instances_app();
+    let root = app.world_mut().load_scene((INSTANCES_FILE, "card"));
+    let id = instance_of(&app, root);
+
+    app.world_mut().commands().despawn_scene_instance(id);
+    app.update();
+
+    assert!(app.world().get_entity(root).is_err());
+    assert!(scene_nodes(app.world_mut(), id).is_empty());
+    assert_eq!(app.world().resource::<SceneLoader>().root_entity(id), None);
+    assert_eq!(app.world().resource::<SceneLoader>().instance_scene(id), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const REFS_FILE: &str = "refs.cob";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_refs_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.load_cob_from_str(
+        REFS_FILE,
+        "#manifest\nself as refs\n\n\
+        #scenes\n\
+        \"card\"\n    FlexNode\n    \"title\"\n\n\
+        \"page\"\n    FlexNode\n    \"slot\" => refs::card\n\n\
+        \"loop\"\n    FlexNode\n    \"inner\" => refs::loop\n",
+    );
+    load_runtime_app(&mut app);
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn child_node(app: &mut App, root: Entity, path: &str) -> Entity
+{
+    let instance = app.world().get::<SceneNode>(root).unwrap().instance();
+    scene_nodes(app.world_mut(), instance)
+        .into_iter()
+        .find(|(node_path, _)| node_path == path)
+        .unwrap()
+        .1
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn load_referenced_scene()
+{
+    let mut app = prepare_refs_app();
+    let root = app.world_mut().load_scene((REFS_FILE, "page"));
+    let page = app.world().get::<SceneNode>(root).unwrap().instance();
+    let slot = child_node(&mut app, root, "page::slot");
+
+    // The referenced scene is spawned as a separate instance in a child of the scene node.
+    let slot_children = app.world().get::<Children>(slot).unwrap().to_vec();
+    assert_eq!(slot_children.len(), 1);
+    let card_root = slot_children[0];
+    let card = app.world().get::<SceneNode>(card_root).unwrap().instance();
+    assert_ne!(card, page);
+    assert_eq!(
+        app.world().resource::<SceneLoader>().instance_scene(card),
+        Some(&SceneRef::from((REFS_FILE, "card")))
+    );
+    let paths: Vec<_> = scene_nodes(app.world_mut(), card)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec!["card", "card::title"]);
+    assert_eq!(scene_nodes(app.world_mut(), page).len(), 2);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn reject_scene_reference_cycle()
+{
+    let mut app = prepare_refs_app();
+    let root = app.world_mut().load_scene((REFS_FILE, "loop"));
+    app.update();
+
+    // A scene that references itself is only spawned once, and the reference is skipped.
+    let inner = child_node(&mut app, root, "loop::inner");
+    assert!(app
+        .world()
+        .get::<Children>(inner)
+        .is_none_or(|children| children.is_empty()));
+    let num_loops = app
+        .world_mut()
+        .query::<&SceneNode>()
+        .iter(app.world())
+        .filter(|node| node.scene_ref() == &SceneRef::from((REFS_FILE, "loop")))
+        .count();
+    assert_eq!(num_loops, 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
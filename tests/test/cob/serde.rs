@@ -503,6 +503,183 @@ fn builtins()
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[test]
+fn builtin_colors()
+{
+    let a = prepare_test_app();
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(rgb(0.1 0.2 0.3))",
+        "rgb(0.1 0.2 0.3)",
+        BuiltinColor(Color::srgb(0.1, 0.2, 0.3)),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(rgba(0.1 0.2 0.3 0.5))",
+        "rgba(0.1 0.2 0.3 0.5)",
+        BuiltinColor(Color::srgba(0.1, 0.2, 0.3, 0.5)),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(hsl(210 0.5 0.4))",
+        "hsl(210 0.5 0.4)",
+        BuiltinColor(Color::hsl(210., 0.5, 0.4)),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(hsla(210 0.5 0.4 0.8))",
+        "hsla(210 0.5 0.4 0.8)",
+        BuiltinColor(Color::hsla(210., 0.5, 0.4, 0.8)),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(oklch(0.7 0.1 120))",
+        "oklch(0.7 0.1 120)",
+        BuiltinColor(Color::oklch(0.7, 0.1, 120.)),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinColor(oklcha(0.7 0.1 120 0.5))",
+        "oklcha(0.7 0.1 120 0.5)",
+        BuiltinColor(Color::oklcha(0.7, 0.1, 120., 0.5)),
+    );
+
+    // Lossy conversion: srgb colors will be written as hex when possible
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinColor(rgb(1 0 0))",
+        "BuiltinColor(#FF0000)",
+        BuiltinColor(Color::Srgba(Srgba::RED)),
+    );
+    // Lossy conversion: alpha will be dropped if it equals 1
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinColor(hsla(210 0.5 0.4 1))",
+        "BuiltinColor(hsl(210 0.5 0.4))",
+        BuiltinColor(Color::hsl(210., 0.5, 0.4)),
+    );
+    // Lossy conversion: named colors will be written as hex
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinColor(css::red)",
+        "BuiltinColor(#FF0000)",
+        BuiltinColor(Color::Srgba(bevy::color::palettes::css::RED)),
+    );
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinColor(basic::aqua)",
+        "BuiltinColor(#00FFFF)",
+        BuiltinColor(Color::Srgba(bevy::color::palettes::basic::AQUA)),
+    );
+
+    // Invalid colors.
+    test_cob_fail(b"#defs\n$a = rgb(1 0)\n", b"rgb(1 0)\n");
+    test_cob_fail(b"#defs\n$a = hsla(1 0 0)\n", b"hsla(1 0 0)\n");
+    test_cob_fail(b"#defs\n$a = css::not_a_color\n", b"not_a_color\n");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn builtin_rects()
+{
+    let a = prepare_test_app();
+    test_equivalence(
+        a.world(),
+        "BuiltinRect(4px)",
+        "4px",
+        BuiltinRect(UiRect::all(Val::Px(4.))),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinRect(4px 8%)",
+        "(4px 8%)",
+        BuiltinRect(UiRect::axes(Val::Percent(8.), Val::Px(4.))),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinRect(1px 2px 3px auto)",
+        "(1px 2px 3px auto)",
+        BuiltinRect(UiRect::new(Val::Auto, Val::Px(2.), Val::Px(1.), Val::Px(3.))),
+    );
+    test_equivalence(
+        a.world(),
+        "BuiltinRectCollection{all:4px axes:(4px 8px) sides:(1px 2px 3px 4px)}",
+        "{all:4px axes:(4px 8px) sides:(1px 2px 3px 4px)}",
+        BuiltinRectCollection {
+            all: UiRect::all(Val::Px(4.)),
+            axes: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+            sides: UiRect::new(Val::Px(4.), Val::Px(2.), Val::Px(1.), Val::Px(3.)),
+        },
+    );
+
+    // Lossy conversion: rects will be written with the shortest shorthand
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinRect{top:1px right:2px bottom:1px left:2px}",
+        "BuiltinRect(1px 2px)",
+        BuiltinRect(UiRect::axes(Val::Px(2.), Val::Px(1.))),
+    );
+    test_equivalence_lossy(
+        a.world(),
+        "BuiltinRectCollection{all:(4px 4px) axes:[4px 8px 4px 8px] sides:(1px 2px 3px 4px)}",
+        "BuiltinRectCollection{all:4px axes:(4px 8px) sides:(1px 2px 3px 4px)}",
+        BuiltinRectCollection {
+            all: UiRect::all(Val::Px(4.)),
+            axes: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+            sides: UiRect::new(Val::Px(4.), Val::Px(2.), Val::Px(1.), Val::Px(3.)),
+        },
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn rect_shorthand_round_trip()
+{
+    let a = prepare_test_app();
+    let w = a.world();
+
+    // UiRect uses the shortest shorthand.
+    assert_eq!(test_round_trip(w, BuiltinRect(UiRect::all(Val::Px(4.)))), "BuiltinRect(4px)");
+    assert_eq!(
+        test_round_trip(w, BuiltinRect(UiRect::axes(Val::Percent(8.), Val::Px(4.)))),
+        "BuiltinRect(4px 8%)"
+    );
+    assert_eq!(
+        test_round_trip(w, BuiltinRect(UiRect::new(Val::Auto, Val::Px(2.), Val::Px(1.), Val::Px(3.)))),
+        "BuiltinRect(1px 2px 3px auto)"
+    );
+    assert_eq!(
+        test_round_trip(w, BuiltinRect(UiRect::new(Val::Px(1.), Val::Px(2.), Val::Px(3.), Val::Px(3.)))),
+        "BuiltinRect(3px 2px 3px 1px)"
+    );
+
+    // Other structs with the rect fields use the shorthand too, since they can also be parsed from it.
+    assert_eq!(
+        test_round_trip(
+            w,
+            RectLikeStruct { top: Val::Px(1.), right: Val::Px(2.), bottom: Val::Px(1.), left: Val::Px(2.) }
+        ),
+        "RectLikeStruct(1px 2px)"
+    );
+    assert_eq!(
+        test_round_trip(
+            w,
+            RectLikeStruct { top: Val::Px(1.), right: Val::Vw(2.), bottom: Val::Auto, left: Val::Percent(4.) }
+        ),
+        "RectLikeStruct(1px 2vw auto 4%)"
+    );
+
+    // Structs with other fields are not written with the shorthand.
+    assert_eq!(
+        test_round_trip(w, FourValStruct { a: Val::Px(1.), b: Val::Px(1.), c: Val::Px(1.), d: Val::Px(1.) }),
+        "FourValStruct{a:1px b:1px c:1px d:1px}"
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[test]
 fn reflect_defaulted()
 {
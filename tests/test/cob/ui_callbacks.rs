@@ -0,0 +1,32 @@
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn ui_callback_unknown_name()
+{
+    let mut app = prepare_runtime_app();
+    app.register_ui_callback("open_settings", || {})
+        .load_cob_from_str(
+            "callbacks.cob",
+            "#scenes\n\"menu\"\n    \"settings\"\n        OnPressed(\"opn_settings\")\n    \"quit\"\n        \
+            OnReleased(\"open_settings\")\n",
+        );
+    load_runtime_app(&mut app);
+
+    let messages = diagnostic_messages(app.world());
+    let errors: Vec<_> = messages
+        .iter()
+        .filter(|m| m.contains("unknown UI callback"))
+        .collect();
+    assert_eq!(errors.len(), 1, "{messages:?}");
+    assert!(
+        errors[0].contains("unknown UI callback \"opn_settings\"; did you mean \"open_settings\"?"),
+        "{}",
+        errors[0]
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
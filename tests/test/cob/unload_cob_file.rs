@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use super::helpers::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Default)]
+struct UnloadedFiles(Vec<CobFileUnloaded>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_unload_app() -> App
+{
+    let mut app = prepare_runtime_app();
+    app.init_resource::<UnloadedFiles>()
+        .load_cob_from_str("main.cob", "#manifest\n\"other.cob\" as other\n")
+        .load_cob_from_str("other.cob", "#scenes\n\"box\"\n    \"inner\"\n        FlexNode\n");
+    load_runtime_app(&mut app);
+    app.world_mut().react(|rc| {
+        rc.on_persistent(
+            broadcast::<CobFileUnloaded>(),
+            |event: BroadcastEvent<CobFileUnloaded>, mut unloaded: ResMut<UnloadedFiles>| {
+                unloaded.0.push(event.read().clone());
+            },
+        )
+    });
+    app
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_box_ready(app: &App) -> bool
+{
+    app.world()
+        .resource::<SceneLoader>()
+        .is_scene_ready(&SceneRef::from(("other", "box")))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn unload_cob_file_reports_live_entities()
+{
+    let mut app = prepare_unload_app();
+    let root = app.world_mut().load_scene(("other", "box"));
+    let inner = app.world().get::<Children>(root).unwrap()[0];
+    assert!(is_box_ready(&app));
+
+    app.world_mut().commands().queue(UnloadCobFile::new("other"));
+    app.update();
+
+    let unloaded = &app.world().resource::<UnloadedFiles>().0;
+    assert_eq!(unloaded.len(), 1);
+    assert_eq!(unloaded[0].file.as_str(), "other.cob");
+    #[cfg(not(feature = "hot_reload"))]
+    let expected = vec![root];
+    #[cfg(feature = "hot_reload")]
+    let expected = {
+        let mut expected = vec![root, inner];
+        expected.sort();
+        expected
+    };
+    assert_eq!(unloaded[0].live_entities, expected);
+
+    // The file's scenes are removed, but spawned scenes are not despawned.
+    assert!(!is_box_ready(&app));
+    assert!(app.world().get_entity(root).is_ok());
+    assert!(app.world().get_entity(inner).is_ok());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn unload_cob_file_without_live_scenes()
+{
+    let mut app = prepare_unload_app();
+    let root = app.world_mut().load_scene(("other", "box"));
+    app.world_mut().entity_mut(root).despawn_recursive();
+    app.update();
+
+    // Unknown manifest keys are ignored.
+    app.world_mut()
+        .commands()
+        .queue(UnloadCobFile::new("missing"));
+    app.update();
+    assert!(app.world().resource::<UnloadedFiles>().0.is_empty());
+
+    app.world_mut().commands().queue(UnloadCobFile::new("other"));
+    app.update();
+
+    let unloaded = &app.world().resource::<UnloadedFiles>().0;
+    assert_eq!(unloaded.len(), 1);
+    assert_eq!(unloaded[0].file.as_str(), "other.cob");
+    assert!(unloaded[0].live_entities.is_empty());
+    assert!(!is_box_ready(&app));
+}
+
+//-------------------------------------------------------------------------------------------------------------------